@@ -24,9 +24,27 @@ struct ParsedAttribute {
     id: u16,
     access: Option<AttributeAccess>,
     ident: proc_macro2::Ident,
+    locked: bool,
 }
 
-pub fn attributes_match(item: &mut ItemStruct) -> TokenStream {
+/// `RwLock<T>` attributes are read and written through the lock, so they can be set
+/// through a shared reference.
+fn is_rw_lock(ty: &syn::Type) -> bool {
+    match ty {
+        syn::Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .is_some_and(|segment| segment.ident == "RwLock"),
+        _ => false,
+    }
+}
+
+/// Generates `execute_attribute_service` for the `#[attribute]` fields of `item`, and
+/// `execute_shared_attribute_service` which can only set `RwLock` attributes.
+/// `extra_services` are additional match arms keyed by service id, placed before the
+/// `ServiceNotSupported` fallback. They must only need a shared reference.
+pub fn attributes_match(item: &mut ItemStruct, extra_services: &[TokenStream]) -> TokenStream {
     let mut parsed_attrs = Vec::new();
     let mut darling_errors = Vec::new();
     let mut syn_errors = Vec::new();
@@ -70,6 +88,7 @@ pub fn attributes_match(item: &mut ItemStruct) -> TokenStream {
                         id: attr_id,
                         access: args.access,
                         ident: field_ident,
+                        locked: is_rw_lock(&field.ty),
                     });
                 }
                 Err(e) => {
@@ -85,27 +104,68 @@ pub fn attributes_match(item: &mut ItemStruct) -> TokenStream {
 
     let mut get_arms = Vec::new();
     let mut set_arms = Vec::new();
+    let mut shared_set_arms = Vec::new();
 
     for attr in parsed_attrs {
         let attr_id = attr.id;
         let field_ident = attr.ident;
 
         // Currently, all attributes with ID support GET
-        get_arms.push(quote! {
-            #attr_id => {
-                self.#field_ident.encode(resp)?;
-                Ok(())
-            }
-        });
+        if attr.locked {
+            get_arms.push(quote! {
+                #attr_id => {
+                    self.#field_ident
+                        .read()
+                        .map_err(|_| {
+                            log::error!(concat!("Failed to get read guard for attribute ", stringify!(#field_ident)));
+                            CipError::GeneralError
+                        })?
+                        .encode(resp)?;
+                    Ok(())
+                }
+            });
+        } else {
+            get_arms.push(quote! {
+                #attr_id => {
+                    self.#field_ident.encode(resp)?;
+                    Ok(())
+                }
+            });
+        }
+
+        // Add SET support if specifically requested, other attributes exist but are not settable
+        if attr.access != Some(AttributeAccess::Set) {
+            let not_settable = quote! {
+                #attr_id => Err(CipError::AttributeNotSetable),
+            };
+            set_arms.push(not_settable.clone());
+            shared_set_arms.push(not_settable);
+            continue;
+        }
 
-        // Add SET support if specifically requested
-        if attr.access == Some(AttributeAccess::Set) {
+        if attr.locked {
+            let locked_set = quote! {
+                #attr_id => {
+                    let mut write_guard = self.#field_ident.write().map_err(|_| {
+                        log::error!(concat!("Failed to get write guard for attribute ", stringify!(#field_ident)));
+                        CipError::GeneralError
+                    })?;
+                    *write_guard = FromBytes::decode(req)?;
+                    Ok(())
+                }
+            };
+            set_arms.push(locked_set.clone());
+            shared_set_arms.push(locked_set);
+        } else {
             set_arms.push(quote! {
                 #attr_id => {
                     self.#field_ident = FromBytes::decode(req)?;
                     Ok(())
                 }
             });
+            shared_set_arms.push(quote! {
+                #attr_id => Err(CipError::AttributeNotSetable),
+            });
         }
     }
 
@@ -122,23 +182,63 @@ pub fn attributes_match(item: &mut ItemStruct) -> TokenStream {
             resp: &mut bytes::BytesMut
         ) -> CipResult {
             match service_id {
-                0x0E => {
+                0x0E => self.read_attribute_single(req, resp),
+                0x10 => {
+                    if req.remaining() < 2 {
+                        return Err(CipError::NotEnoughData);
+                    }
                     let attr_id = req.get_u16_le();
                     match attr_id {
-                        #( #get_arms )*
+                        #( #set_arms )*
                         _ => Err(CipError::AttributeNotSupported),
                     }
                 }
+                #( #extra_services )*
+                _ => Err(CipError::ServiceNotSupported),
+            }
+        }
+
+        /// Attribute and class services through a shared reference, used by
+        /// `CipObject::execute_shared`. Set_Attribute_Single only reaches `RwLock` attributes.
+        #[allow(dead_code)]
+        pub fn execute_shared_attribute_service(&self,
+            service_id: u8,
+            req: &mut bytes::Bytes,
+            resp: &mut bytes::BytesMut
+        ) -> CipResult {
+            match service_id {
+                0x0E => self.read_attribute_single(req, resp),
                 0x10 => {
+                    if req.remaining() < 2 {
+                        return Err(CipError::NotEnoughData);
+                    }
                     let attr_id = req.get_u16_le();
                     match attr_id {
-                        #( #set_arms )*
+                        #( #shared_set_arms )*
                         _ => Err(CipError::AttributeNotSupported),
                     }
                 }
+                #( #extra_services )*
                 _ => Err(CipError::ServiceNotSupported),
             }
         }
+
+        /// Get_Attribute_Single through a shared reference, used by the read path of
+        /// `CipObject::execute_read_service`.
+        #[allow(dead_code)]
+        pub fn read_attribute_single(&self,
+            req: &mut bytes::Bytes,
+            resp: &mut bytes::BytesMut
+        ) -> CipResult {
+            if req.remaining() < 2 {
+                return Err(CipError::NotEnoughData);
+            }
+            let attr_id = req.get_u16_le();
+            match attr_id {
+                #( #get_arms )*
+                _ => Err(CipError::AttributeNotSupported),
+            }
+        }
     };
 
     TokenStream::from(expanded)
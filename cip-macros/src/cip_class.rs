@@ -15,6 +15,14 @@ struct ClassDeriveArgs {
     singleton: bool,
     #[darling(default)]
     custom_services: bool,
+    #[darling(default)]
+    create: bool,
+    #[darling(default)]
+    max_instance: Option<u16>,
+    #[darling(default)]
+    on_create: Option<Path>,
+    #[darling(default)]
+    on_delete: Option<Path>,
 }
 
 pub fn cip_class_derive_impl(item: TokenStream) -> TokenStream {
@@ -64,10 +72,204 @@ pub fn cip_class_derive_impl(item: TokenStream) -> TokenStream {
         }
     }
 
+    if args.create {
+        if is_singleton {
+            errors.push(
+                syn::Error::new(
+                    struct_ident_span,
+                    "Singleton CipClass cannot support Create",
+                )
+                .to_compile_error(),
+            );
+        } else if let Err(e) = cip_utils::ensure_field(
+            struct_ident_span,
+            &input.fields,
+            "factory",
+            None,
+            "CipClass with create = true must have a 'factory: InstanceFactory' field",
+            None,
+        ) {
+            errors.push(e.to_compile_error());
+        }
+    }
+
+    if args.on_create.is_some() && !args.create {
+        errors.push(
+            syn::Error::new(
+                struct_ident_span,
+                "CipClass with on_create must have create = true",
+            )
+            .to_compile_error(),
+        );
+    }
+
+    if args.on_delete.is_some() && !args.create {
+        errors.push(
+            syn::Error::new(
+                struct_ident_span,
+                "CipClass with on_delete must have create = true",
+            )
+            .to_compile_error(),
+        );
+    }
+
     let id_path = args.id;
     let class_name = args.name;
+    let max_instance = args.max_instance.unwrap_or(u16::MAX);
+    let create = args.create && !is_singleton;
+
+    // Only emitted for an explicit max_instance, any u16 id is valid otherwise
+    let max_instance_check = args.max_instance.map(|max| {
+        quote! {
+            if instance.id() > #max {
+                return Err(CipError::InvalidParameter);
+            }
+        }
+    });
 
-    let attribute_services = attributes_match(&mut input);
+    // Called with the instance id once the instance is inserted and the write guard released,
+    // an error removes the instance again and fails the Create
+    let on_create_call = args.on_create.map(|on_create| {
+        quote! {
+            if let Err(error) = Self::#on_create(self, instance_id) {
+                match self.instances.write() {
+                    Ok(mut write_guard) => {
+                        write_guard.remove(&instance_id);
+                    }
+                    Err(_) => log::error!(concat!("Failed to get write guard for ", stringify!(#name), " instances")),
+                }
+                return Err(error);
+            }
+        }
+    });
+
+    let create_impl = if create {
+        quote! {
+            /// Creates an instance through the factory with the lowest free instance id. The id
+            /// is chosen and the instance inserted under one write guard, so the factory must
+            /// not access the instances of this class. The `on_create` hook, if any, runs
+            /// after the guard is released.
+            pub fn create_instance(&self, req: &mut bytes::Bytes) -> Result<u16, CipError> {
+                let mut write_guard = self.instances.write().map_err(|_| {
+                    log::error!(concat!("Failed to get write guard for ", stringify!(#name), " instances"));
+                    CipError::GeneralError
+                })?;
+
+                let instance_id = (1..=#max_instance)
+                    .find(|id| !write_guard.contains_key(id))
+                    .ok_or(CipError::ResourceUnavailable)?;
+
+                let instance = (self.factory)(instance_id, req)?;
+                if instance.id() != instance_id || instance.class_id() != self.id() {
+                    log::error!(
+                        concat!("Factory of ", stringify!(#name), " built instance {} of class {} for instance {}"),
+                        instance.id(),
+                        instance.class_id(),
+                        instance_id
+                    );
+                    return Err(CipError::GeneralError);
+                }
+
+                write_guard.insert(instance_id, instance);
+                drop(write_guard);
+                #on_create_call
+                Ok(instance_id)
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Called with the instance id once the instance is removed and the write guard released
+    let on_delete_call = args.on_delete.map(|on_delete| {
+        quote! {
+            Self::#on_delete(self, instance_id);
+        }
+    });
+
+    let delete_impl = if create {
+        quote! {
+            fn delete_instance(&self, instance_id: u16) -> CipResult {
+                self.instances
+                    .write()
+                    .map_err(|_| {
+                        log::error!(concat!("Failed to get write guard for ", stringify!(#name), " instances"));
+                        CipError::GeneralError
+                    })?
+                    .remove(&instance_id)
+                    .ok_or(CipError::ObjectDoesNotExist)?;
+                #on_delete_call
+                Ok(())
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let find_next_impl = if is_singleton {
+        quote! {
+            fn find_next_instances(&self, after: u16, max_count: u8) -> Result<Vec<u16>, CipError> {
+                if after >= 1 || max_count == 0 {
+                    return Ok(Vec::new());
+                }
+
+                Ok(vec![1])
+            }
+        }
+    } else {
+        quote! {
+            fn find_next_instances(&self, after: u16, max_count: u8) -> Result<Vec<u16>, CipError> {
+                let read_guard = self.instances.read().map_err(|_| {
+                    log::error!(concat!("Failed to get read guard for ", stringify!(#name), " instances"));
+                    CipError::GeneralError
+                })?;
+
+                let mut instance_ids = read_guard
+                    .keys()
+                    .copied()
+                    .filter(|id| *id > after)
+                    .collect::<Vec<u16>>();
+                instance_ids.sort_unstable();
+                instance_ids.truncate(max_count as usize);
+                Ok(instance_ids)
+            }
+        }
+    };
+
+    // Find_Next_Object_Instance addressed to the class starts after instance 0. The message
+    // router calls find_next_instances itself when the path names an instance to start after.
+    let find_next_service = quote! {
+        0x11 => {
+            if !bytes::Buf::has_remaining(req) {
+                return Err(CipError::NotEnoughData);
+            }
+            let max_count = bytes::Buf::get_u8(req);
+            let instance_ids = self.find_next_instances(0, max_count)?;
+
+            bytes::BufMut::put_u8(resp, instance_ids.len() as u8);
+            for instance_id in instance_ids {
+                bytes::BufMut::put_u16_le(resp, instance_id);
+            }
+            Ok(())
+        }
+    };
+
+    let mut class_services = vec![find_next_service];
+
+    let create_services = if create {
+        vec![quote! {
+            0x08 => {
+                let instance_id = self.create_instance(req)?;
+                bytes::BufMut::put_u16_le(resp, instance_id);
+                Ok(())
+            }
+        }]
+    } else {
+        Vec::new()
+    };
+
+    class_services.extend(create_services);
+    let attribute_services = attributes_match(&mut input, &class_services);
     let object_impl = cip_utils::generate_default_cip_object(&name, args.custom_services);
 
     let instance_impl = if is_singleton {
@@ -119,14 +321,22 @@ pub fn cip_class_derive_impl(item: TokenStream) -> TokenStream {
                     return Err(CipError::InvalidParameter);
                 }
 
-                self.instances
-                    .write()
-                    .map_err(|_| {
-                        log::error!(concat!("Failed to get write guard for ", stringify!(#name), " instances"));
-                        CipError::GeneralError
-                    })?
-                    .insert(instance.id(), instance);
+                if instance.id() == 0 {
+                    return Err(CipError::InvalidParameter);
+                }
+
+                #max_instance_check
+
+                let mut write_guard = self.instances.write().map_err(|_| {
+                    log::error!(concat!("Failed to get write guard for ", stringify!(#name), " instances"));
+                    CipError::GeneralError
+                })?;
+
+                if write_guard.contains_key(&instance.id()) {
+                    return Err(CipError::ObjectAlreadyExists);
+                }
 
+                write_guard.insert(instance.id(), instance);
                 Ok(())
             }
         }
@@ -137,6 +347,8 @@ pub fn cip_class_derive_impl(item: TokenStream) -> TokenStream {
 
         impl #name {
             #attribute_services
+
+            #create_impl
         }
 
         #object_impl
@@ -151,6 +363,10 @@ pub fn cip_class_derive_impl(item: TokenStream) -> TokenStream {
             #instance_impl
 
             #add_instance_impl
+
+            #delete_impl
+
+            #find_next_impl
         }
     };
 
@@ -0,0 +1,100 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{Data, DeriveInput, Error, Fields, Index, parse_macro_input};
+
+/// Returns the fields of a struct in declaration order, or a compile error for enums and unions.
+fn struct_fields<'a>(input: &'a DeriveInput, derive_name: &str) -> Result<&'a Fields, Error> {
+    match &input.data {
+        Data::Struct(data) => Ok(&data.fields),
+        _ => Err(Error::new(
+            input.ident.span(),
+            format!("#[derive({})] is only supported on structs", derive_name),
+        )),
+    }
+}
+
+/// Generates the accessors (`self.name` or `self.0`) for each field.
+fn field_accessors(fields: &Fields) -> Vec<TokenStream2> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| match &field.ident {
+            Some(ident) => quote! { self.#ident },
+            None => {
+                let index = Index::from(i);
+                quote! { self.#index }
+            }
+        })
+        .collect()
+}
+
+pub fn cip_encode_derive_impl(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+    let fields = match struct_fields(&input, "CipEncode") {
+        Ok(fields) => fields,
+        Err(e) => return TokenStream::from(e.to_compile_error()),
+    };
+
+    let struct_name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    let accessors = field_accessors(fields);
+
+    let expanded = quote! {
+        impl #impl_generics ToBytes for #struct_name #ty_generics #where_clause {
+            fn encode<CipBuf: bytes::BufMut>(&self, buffer: &mut CipBuf) -> Result<(), BinaryError> {
+                let expected = ToBytes::encoded_len(self);
+                if bytes::BufMut::remaining_mut(buffer) < expected {
+                    return Err(BinaryError::BufferTooSmall {
+                        expected,
+                        actual: bytes::BufMut::remaining_mut(buffer),
+                    });
+                }
+
+                #( ToBytes::encode(&#accessors, buffer)?; )*
+                Ok(())
+            }
+
+            fn encoded_len(&self) -> usize {
+                0 #( + ToBytes::encoded_len(&#accessors) )*
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+pub fn cip_decode_derive_impl(item: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+    let fields = match struct_fields(&input, "CipDecode") {
+        Ok(fields) => fields,
+        Err(e) => return TokenStream::from(e.to_compile_error()),
+    };
+
+    let struct_name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    // Fields are decoded into locals first so they are read in declaration order.
+    let locals: Vec<_> = (0..fields.len())
+        .map(|i| format_ident!("field_{}", i))
+        .collect();
+    let construct = match fields {
+        Fields::Named(named) => {
+            let names = named.named.iter().map(|field| &field.ident);
+            quote! { Self { #( #names: #locals ),* } }
+        }
+        Fields::Unnamed(_) => quote! { Self( #( #locals ),* ) },
+        Fields::Unit => quote! { Self },
+    };
+
+    let expanded = quote! {
+        impl #impl_generics FromBytes for #struct_name #ty_generics #where_clause {
+            fn decode<CipBuf: bytes::Buf>(buffer: &mut CipBuf) -> Result<Self, BinaryError> {
+                #( let #locals = FromBytes::decode(buffer)?; )*
+                Ok(#construct)
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
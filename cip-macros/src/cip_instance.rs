@@ -57,7 +57,7 @@ pub fn cip_instance_derive_impl(item: TokenStream) -> TokenStream {
         compile_errors.push(e.to_compile_error());
     }
 
-    let attribute_services = attributes_match(&mut input);
+    let attribute_services = attributes_match(&mut input, &[]);
     let object_impl = cip_utils::generate_default_cip_object(&struct_name, args.custom_services);
 
     let expanded = quote! {
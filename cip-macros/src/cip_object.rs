@@ -1,11 +1,29 @@
 use proc_macro::TokenStream;
 use quote::quote;
-use syn::{ImplItem, ItemImpl, LitInt, parse_macro_input, spanned::Spanned};
+use syn::{
+    ImplItem, ItemImpl, LitInt, Token, parse::ParseStream, parse_macro_input, spanned::Spanned,
+};
+
+/// Parses `0x4B` or `0x4B, async`.
+fn parse_service_args(input: ParseStream) -> syn::Result<(LitInt, bool)> {
+    let lit = input.parse::<LitInt>()?;
+    if input.is_empty() {
+        return Ok((lit, false));
+    }
+
+    input.parse::<Token![,]>()?;
+    input.parse::<Token![async]>()?;
+    Ok((lit, true))
+}
 
 pub fn cip_object_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
     let mut input = parse_macro_input!(item as ItemImpl);
     let struct_name = &input.self_ty;
     let mut service_arms = Vec::new();
+    let mut shared_service_arms = Vec::new();
+    let mut async_service_arms = Vec::new();
+    let mut shared_async_service_arms = Vec::new();
+    let mut status_service_arms = Vec::new();
     let mut errors = Vec::new();
 
     for item in &mut input.items {
@@ -15,14 +33,18 @@ pub fn cip_object_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
         };
 
         let mut service_id: Option<u8> = None;
+        let mut is_async = false;
 
         method.attrs.retain(|attr| {
             if !attr.path().is_ident("service") {
                 return true;
             }
             
-            let lit = match attr.parse_args::<LitInt>() {
-                Ok(lit) => lit,
+            let lit = match attr.parse_args_with(parse_service_args) {
+                Ok((lit, async_service)) => {
+                    is_async = async_service;
+                    lit
+                }
                 Err(_) => {
                     errors.push(
                         syn::Error::new(
@@ -51,14 +73,144 @@ pub fn cip_object_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
             return false;
         });
 
-        if let Some(id) = service_id {
-            let method_name = &method.sig.ident;
+        let Some(id) = service_id else {
+            continue;
+        };
+
+        let method_name = &method.sig.ident;
+        let shared_receiver = method
+            .sig
+            .receiver()
+            .is_some_and(|receiver| receiver.reference.is_some() && receiver.mutability.is_none());
+        // Services returning ServiceResult report the general status of a successful reply
+        let status_type = match &method.sig.output {
+            syn::ReturnType::Type(_, ty) => match &**ty {
+                syn::Type::Path(path) if path.path.segments.last().is_some_and(|segment| segment.ident == "ServiceResult") => Some(ty),
+                _ => None,
+            },
+            syn::ReturnType::Default => None,
+        };
+        let returns_status = status_type.is_some();
+        if is_async != method.sig.asyncness.is_some() {
+            errors.push(
+                syn::Error::new(
+                    method.sig.span(),
+                    "Services declared with #[service(id, async)] must be async fn, and async fn services must be declared with #[service(id, async)]",
+                )
+                .to_compile_error(),
+            );
+        } else if is_async && returns_status {
+            errors.push(
+                syn::Error::new(
+                    status_type.span(),
+                    "Async services must return CipResult",
+                )
+                .to_compile_error(),
+            );
+        } else if is_async {
+            // Async services can only be awaited, the blocking path reports them as unsupported
+            service_arms.push(quote! {
+                #id => Err(CipError::ServiceNotSupported),
+            });
+            shared_service_arms.push(quote! {
+                #id => Err(CipError::ServiceNotSupported),
+            });
+            async_service_arms.push(quote! {
+                #id => Some(Box::pin(self.#method_name(req, resp))),
+            });
+            if shared_receiver {
+                shared_async_service_arms.push(quote! {
+                    #id => Some(Box::pin(self.#method_name(req, resp))),
+                });
+            }
+        } else if returns_status {
+            // Callers without a status only see that the service succeeded
+            service_arms.push(quote! {
+                #id => self.#method_name(req, resp).map(|_| ()),
+            });
+            if shared_receiver {
+                shared_service_arms.push(quote! {
+                    #id => self.#method_name(req, resp).map(|_| ()),
+                });
+                status_service_arms.push(quote! {
+                    #id => self.#method_name(req, resp),
+                });
+            } else {
+                shared_service_arms.push(quote! {
+                    #id => Err(CipError::ServiceNotSupported),
+                });
+            }
+        } else {
             service_arms.push(quote! {
                 #id => self.#method_name(req, resp),
             });
+            // Services taking `&mut self` cannot run on objects held by the registry
+            if shared_receiver {
+                shared_service_arms.push(quote! {
+                    #id => self.#method_name(req, resp),
+                });
+            } else {
+                shared_service_arms.push(quote! {
+                    #id => Err(CipError::ServiceNotSupported),
+                });
+            }
         }
     }
 
+    let async_impl = if async_service_arms.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            fn execute_async_service<'a>(
+                &'a mut self,
+                service_id: u8,
+                req: &'a mut bytes::Bytes,
+                resp: &'a mut bytes::BytesMut
+            ) -> Option<std::pin::Pin<Box<dyn std::future::Future<Output = CipResult> + Send + 'a>>> {
+                match service_id {
+                    #( #async_service_arms )*
+                    _ => None,
+                }
+            }
+        }
+    };
+
+    let shared_async_impl = if shared_async_service_arms.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            fn execute_shared_async<'a>(
+                &'a self,
+                service_id: u8,
+                req: &'a mut bytes::Bytes,
+                resp: &'a mut bytes::BytesMut
+            ) -> Option<std::pin::Pin<Box<dyn std::future::Future<Output = CipResult> + Send + 'a>>> {
+                match service_id {
+                    #( #shared_async_service_arms )*
+                    _ => None,
+                }
+            }
+        }
+    };
+
+    let status_impl = if status_service_arms.is_empty() {
+        quote! {}
+    } else {
+        quote! {
+            fn execute_shared_with_status(
+                &self,
+                service_id: u8,
+                req: &mut bytes::Bytes,
+                resp: &mut bytes::BytesMut
+            ) -> ServiceResult {
+                match service_id {
+                    #( #status_service_arms )*
+                    _ => self.execute_shared(service_id, req, resp).map(|()| ReplyStatus::Success),
+                }
+            }
+        }
+    };
+
     let expanded = quote! {
         #( #errors )*
 
@@ -76,6 +228,24 @@ pub fn cip_object_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
                     _ => self.execute_attribute_service(service_id, req, resp),
                 }
             }
+
+            fn execute_shared(
+                &self,
+                service_id: u8,
+                req: &mut bytes::Bytes,
+                resp: &mut bytes::BytesMut
+            ) -> CipResult {
+                match service_id {
+                    #( #shared_service_arms )*
+                    _ => self.execute_shared_attribute_service(service_id, req, resp),
+                }
+            }
+
+            #async_impl
+
+            #shared_async_impl
+
+            #status_impl
         }
     };
 
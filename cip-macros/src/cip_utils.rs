@@ -43,7 +43,8 @@ pub fn ensure_field(
     Err(Error::new(struct_ident_span, missing_error_msg))
 }
 
-/// Generates the default `CipObject` implementation routing to `execute_attribute_service`.
+/// Generates the default `CipObject` implementation routing to `execute_attribute_service`,
+/// with the attribute services also available through a shared reference.
 /// Returns an empty `TokenStream` if `custom_services` is true.
 pub fn generate_default_cip_object(struct_name: &syn::Ident, custom_services: bool) -> TokenStream {
     if custom_services {
@@ -60,6 +61,27 @@ pub fn generate_default_cip_object(struct_name: &syn::Ident, custom_services: bo
             ) -> CipResult {
                 self.execute_attribute_service(service_id, req, resp)
             }
+
+            fn execute_read_service(
+                &self,
+                service_id: u8,
+                req: &mut bytes::Bytes,
+                resp: &mut bytes::BytesMut
+            ) -> Option<CipResult> {
+                match service_id {
+                    0x0E => Some(self.read_attribute_single(req, resp)),
+                    _ => None,
+                }
+            }
+
+            fn execute_shared(
+                &self,
+                service_id: u8,
+                req: &mut bytes::Bytes,
+                resp: &mut bytes::BytesMut
+            ) -> CipResult {
+                self.execute_shared_attribute_service(service_id, req, resp)
+            }
         }
     }
 }
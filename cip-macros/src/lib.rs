@@ -2,6 +2,7 @@ use proc_macro::TokenStream;
 
 mod cip_attribute;
 mod cip_class;
+mod cip_codec;
 mod cip_instance;
 mod cip_object;
 mod cip_utils;
@@ -19,6 +20,34 @@ mod cip_utils;
 ///     }
 /// }
 /// ```
+///
+/// ### Example for async service
+/// `#[service(0x4B, async)]` marks an `async fn` service. It is started through
+/// `execute_async_service` and awaited by the message router, `execute_service` reports
+/// it as not supported.
+/// ```rust,ignore
+/// #[cip_object_impl]
+/// impl MyObject {
+///     #[service(0x4B, async)]
+///     async fn read_sensor(&mut self, _req: &mut bytes::Bytes, resp: &mut bytes::BytesMut) -> CipResult {
+///        ...
+///     }
+/// }
+/// ```
+///
+/// ### Example for service with reply status
+/// Services returning `ServiceResult` can reply with a status such as Partial Transfer
+/// (0x06) and their data. `execute_shared_with_status` reports the status, the other entry
+/// points only report success. `ServiceResult` and `ReplyStatus` must be in scope.
+/// ```rust,ignore
+/// #[cip_object_impl]
+/// impl MyObject {
+///     #[service(0x4C)]
+///     fn read_fragment(&self, req: &mut bytes::Bytes, resp: &mut bytes::BytesMut) -> ServiceResult {
+///        ...
+///     }
+/// }
+/// ```
 #[proc_macro_attribute]
 pub fn cip_object_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
     cip_object::cip_object_impl(_attr, item)
@@ -43,13 +72,49 @@ pub fn cip_object_impl(_attr: TokenStream, item: TokenStream) -> TokenStream {
 ///     instances: RwLock<HashMap<u16, Arc<dyn CipInstance>>>,
 /// }
 /// ```
-#[proc_macro_derive(CipClass, attributes(cip))]
+///
+/// ### Example for class supporting Create and Delete
+/// `create = true` adds the Create (0x08) service and implements `CipClass::delete_instance`.
+/// Create allocates the lowest free instance id up to `max_instance` and builds the instance
+/// with `factory`. The message router calls `delete_instance` for Delete (0x09) requests
+/// addressed to an instance. `on_create` names a method `fn(&self, u16) -> CipResult` of the
+/// class called after an instance is created, outside the instances lock; an error removes
+/// the instance again and fails the Create. `on_delete` names a method `fn(&self, u16)` of
+/// the class called after an instance is deleted.
+/// ```rust,ignore
+/// #[derive(CipClass)]
+/// #[cip(id = ClassCode::Assembly, name = "Assembly", singleton = false, create = true, max_instance = 32, on_create = instance_created, on_delete = instance_deleted)]
+/// pub struct AssemblyClass {
+///     factory: InstanceFactory,
+///     instances: RwLock<HashMap<u16, Arc<dyn CipInstance>>>,
+/// }
+///
+/// impl AssemblyClass {
+///     fn instance_created(&self, instance_id: u16) -> CipResult {
+///         log::info!("Assembly {} created", instance_id);
+///         Ok(())
+///     }
+///
+///     fn instance_deleted(&self, instance_id: u16) {
+///         log::info!("Assembly {} deleted", instance_id);
+///     }
+/// }
+/// ```
+#[proc_macro_derive(CipClass, attributes(attribute, cip))]
 pub fn cip_class_derive(item: TokenStream) -> TokenStream {
     cip_class::cip_class_derive_impl(item)
 }
 
 /// Implement a CIP instance.
 ///
+/// Registered instances are reached through `execute_shared(&self)`. Settable attributes
+/// declared as `RwLock<T>` and services taking `&self` work on that path; other settable
+/// attributes report `AttributeNotSetable` and `&mut self` services `ServiceNotSupported`.
+/// To mutate a whole instance instead, wrap it in a `SharedInstance`. Its services then
+/// run behind a per-instance `RwLock`, with Get_Attribute_Single taking only the read lock.
+/// On both paths, Set_Attribute_Single on a `get` attribute reports `AttributeNotSetable`
+/// and on an unknown attribute `AttributeNotSupported`.
+///
 /// ### Example
 /// ```rust,ignore
 /// #[derive(CipInstance)]
@@ -63,3 +128,27 @@ pub fn cip_class_derive(item: TokenStream) -> TokenStream {
 pub fn cip_instance_derive(item: TokenStream) -> TokenStream {
     cip_instance::cip_instance_derive_impl(item)
 }
+
+/// Implement `ToBytes` for a struct by encoding its fields in declaration order.
+/// Every field must implement `ToBytes`; `ToBytes` and `BinaryError` must be in scope.
+///
+/// ### Example
+/// ```rust,ignore
+/// #[derive(CipEncode, CipDecode)]
+/// pub struct PortEntry {
+///     port_type: UInt,
+///     port_number: UInt,
+///     link_address: [USInt; 6],
+/// }
+/// ```
+#[proc_macro_derive(CipEncode)]
+pub fn cip_encode_derive(item: TokenStream) -> TokenStream {
+    cip_codec::cip_encode_derive_impl(item)
+}
+
+/// Implement `FromBytes` for a struct by decoding its fields in declaration order.
+/// Every field must implement `FromBytes`; `FromBytes` and `BinaryError` must be in scope.
+#[proc_macro_derive(CipDecode)]
+pub fn cip_decode_derive(item: TokenStream) -> TokenStream {
+    cip_codec::cip_decode_derive_impl(item)
+}
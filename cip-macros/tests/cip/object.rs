@@ -1,10 +1,37 @@
-use std::{any::Any, sync::Arc};
+use std::{any::Any, future::Future, pin::Pin, sync::Arc};
 
 use bytes::{Bytes, BytesMut};
 use super::{ClassCode, error::CipError};
 
 pub type CipResult = Result<(), CipError>;
 
+/// General status of a successful reply.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplyStatus {
+    Success,
+    /// The reply holds part of the data, the client continues where it ends.
+    PartialTransfer,
+}
+
+impl From<ReplyStatus> for u8 {
+    fn from(value: ReplyStatus) -> Self {
+        match value {
+            ReplyStatus::Success => CipError::Success as u8,
+            ReplyStatus::PartialTransfer => CipError::PartialTransfer as u8,
+        }
+    }
+}
+
+/// Result of a service whose successful reply may carry a status other than Success.
+pub type ServiceResult = Result<ReplyStatus, CipError>;
+
+/// Future of an async service, borrowing the object and the request and response buffers.
+pub type CipFuture<'a> = Pin<Box<dyn Future<Output = CipResult> + Send + 'a>>;
+
+/// Builds the instance created by the Create service from its instance id and request data.
+pub type InstanceFactory =
+    Box<dyn Fn(u16, &mut Bytes) -> Result<Arc<dyn CipInstance>, CipError> + Send + Sync>;
+
 pub trait CipObject: Send + Sync {
     fn execute_service(
         &mut self,
@@ -12,6 +39,59 @@ pub trait CipObject: Send + Sync {
         req: &mut Bytes,
         resp: &mut BytesMut,
     ) -> CipResult;
+
+    /// Starts an async service, `None` when `service_id` is not an async service.
+    fn execute_async_service<'a>(
+        &'a mut self,
+        _service_id: u8,
+        _req: &'a mut Bytes,
+        _resp: &'a mut BytesMut,
+    ) -> Option<CipFuture<'a>> {
+        None
+    }
+
+    /// Starts an async service through a shared reference, `None` when `service_id` is not
+    /// an async service taking `&self`.
+    fn execute_shared_async<'a>(
+        &'a self,
+        _service_id: u8,
+        _req: &'a mut Bytes,
+        _resp: &'a mut BytesMut,
+    ) -> Option<CipFuture<'a>> {
+        None
+    }
+
+    /// Executes a service that only reads the object, `None` when the service needs
+    /// `execute_service`.
+    fn execute_read_service(
+        &self,
+        _service_id: u8,
+        _req: &mut Bytes,
+        _resp: &mut BytesMut,
+    ) -> Option<CipResult> {
+        None
+    }
+
+    /// Executes a service through a shared reference, the path used for objects held by
+    /// the registry. Objects that support Set or other writes through this path keep their
+    /// state behind interior mutability. Defaults to the services of `execute_read_service`.
+    fn execute_shared(&self, service_id: u8, req: &mut Bytes, resp: &mut BytesMut) -> CipResult {
+        self.execute_read_service(service_id, req, resp)
+            .unwrap_or(Err(CipError::ServiceNotSupported))
+    }
+
+    /// Executes a service like `execute_shared`, also reporting the general status of a
+    /// successful reply. The message router executes the services of the registry through
+    /// it, services returning [`ServiceResult`] are executed with their status.
+    fn execute_shared_with_status(
+        &self,
+        service_id: u8,
+        req: &mut Bytes,
+        resp: &mut BytesMut,
+    ) -> ServiceResult {
+        self.execute_shared(service_id, req, resp)
+            .map(|()| ReplyStatus::Success)
+    }
 }
 
 pub trait CipClass: CipObject {
@@ -19,6 +99,17 @@ pub trait CipClass: CipObject {
     fn name(&self) -> &'static str;
     fn get_instance(&self, instance_id: u16) -> Result<Arc<dyn CipInstance>, CipError>;
     fn add_instance(&self, instance: Arc<dyn CipInstance>) -> Result<(), CipError>;
+
+    /// Removes an instance, the Delete service (0x09) addressed to the instance path.
+    fn delete_instance(&self, _instance_id: u16) -> CipResult {
+        Err(CipError::ServiceNotSupported)
+    }
+
+    /// Returns the ids of up to `max_count` instances with an id greater than `after`,
+    /// sorted. Find_Next_Object_Instance (0x11) starts after the instance of its path.
+    fn find_next_instances(&self, _after: u16, _max_count: u8) -> Result<Vec<u16>, CipError> {
+        Err(CipError::ServiceNotSupported)
+    }
 }
 
 pub trait CipInstance: CipObject {
@@ -0,0 +1,25 @@
+#![allow(unused_imports)]
+use bytes::Buf;
+use cip_macros::CipClass;
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use crate::cip::{
+    ClassCode,
+    error::CipError,
+    object::{CipClass, CipInstance, CipObject, CipResult},
+};
+
+#[path = "../cip/mod.rs"]
+mod cip;
+
+#[derive(CipClass)]
+#[cip(id = ClassCode::Identity, name = "Normal Class", create = true)]
+struct MyNormalClass {
+    // Missing `factory` field
+    pub instances: RwLock<HashMap<u16, Arc<dyn CipInstance>>>,
+}
+
+fn main() {}
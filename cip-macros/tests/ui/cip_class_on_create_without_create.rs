@@ -0,0 +1,30 @@
+#![allow(unused_imports)]
+use bytes::Buf;
+use cip_macros::CipClass;
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use crate::cip::{
+    ClassCode,
+    error::CipError,
+    object::{CipClass, CipInstance, CipObject, CipResult},
+};
+
+#[path = "../cip/mod.rs"]
+mod cip;
+
+#[derive(CipClass)]
+#[cip(id = ClassCode::Identity, name = "Normal Class", on_create = instance_created)]
+struct MyNormalClass {
+    pub instances: RwLock<HashMap<u16, Arc<dyn CipInstance>>>,
+}
+
+impl MyNormalClass {
+    fn instance_created(&self, _instance_id: u16) -> CipResult {
+        Ok(())
+    }
+}
+
+fn main() {}
@@ -0,0 +1,28 @@
+#![allow(unused_imports)]
+use bytes::Buf;
+use cip_macros::CipClass;
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use crate::cip::{
+    ClassCode,
+    error::CipError,
+    object::{CipClass, CipInstance, CipObject, CipResult},
+};
+
+#[path = "../cip/mod.rs"]
+mod cip;
+
+#[derive(CipClass)]
+#[cip(id = ClassCode::Identity, name = "Normal Class", on_delete = instance_deleted)]
+struct MyNormalClass {
+    pub instances: RwLock<HashMap<u16, Arc<dyn CipInstance>>>,
+}
+
+impl MyNormalClass {
+    fn instance_deleted(&self, _instance_id: u16) {}
+}
+
+fn main() {}
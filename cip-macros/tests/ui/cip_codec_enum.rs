@@ -0,0 +1,9 @@
+use cip_macros::CipEncode;
+
+#[derive(CipEncode)]
+pub enum NotAStruct {
+    A,
+    B,
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+#![allow(unused_imports)]
+use cip_macros::cip_object_impl;
+
+use crate::cip::{
+    ClassCode,
+    error::CipError,
+    object::{CipClass, CipInstance, CipObject, CipResult, ReplyStatus, ServiceResult},
+};
+
+#[path = "../cip/mod.rs"]
+mod cip;
+
+struct MyObject;
+
+#[cip_object_impl]
+impl MyObject {
+    #[service(0x4B, async)]
+    async fn my_service(&mut self, _req: &mut bytes::Bytes, _resp: &mut bytes::BytesMut) -> ServiceResult {
+        Ok(ReplyStatus::Success)
+    }
+}
+
+fn main() {}
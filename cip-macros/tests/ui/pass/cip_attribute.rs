@@ -105,5 +105,5 @@ fn main() {
     let mut set_resp_2 = BytesMut::new();
 
     let res = instance.execute_service(0x10, &mut set_req_2_bytes, &mut set_resp_2);
-    assert!(matches!(res, Err(CipError::AttributeNotSupported)));
+    assert!(matches!(res, Err(CipError::AttributeNotSetable)));
 }
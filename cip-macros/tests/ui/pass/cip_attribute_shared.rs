@@ -0,0 +1,118 @@
+#![allow(unused_imports)]
+use std::sync::RwLock;
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use cip_macros::{CipInstance, cip_object_impl};
+
+use crate::cip::{
+    ClassCode,
+    error::CipError,
+    object::{CipInstance, CipObject, CipResult},
+};
+
+#[path = "../../cip/mod.rs"]
+mod cip;
+
+// Dummy ToBytes / FromBytes for testing
+pub trait ToBytes {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), CipError>;
+}
+
+pub trait FromBytes: Sized {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, CipError>;
+}
+
+impl ToBytes for u32 {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), CipError> {
+        buffer.put_u32_le(*self);
+        Ok(())
+    }
+}
+
+impl FromBytes for u32 {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, CipError> {
+        if buffer.remaining() < 4 {
+            return Err(CipError::GeneralError);
+        }
+        Ok(buffer.get_u32_le())
+    }
+}
+
+#[derive(CipInstance)]
+#[cip(custom_services = true)]
+pub struct SharedAttributeInstance {
+    id: u16,
+    class_id: ClassCode,
+
+    #[attribute(id = 0x01, access = "set")]
+    locked_attr: RwLock<u32>,
+
+    #[attribute(id = 0x02, access = "set")]
+    plain_attr: u32,
+}
+
+#[cip_object_impl]
+impl SharedAttributeInstance {
+    #[service(0x4B)]
+    fn read_shared(&self, _req: &mut Bytes, resp: &mut BytesMut) -> CipResult {
+        resp.put_u8(0x01);
+        Ok(())
+    }
+
+    #[service(0x4C)]
+    fn reset(&mut self, _req: &mut Bytes, _resp: &mut BytesMut) -> CipResult {
+        self.plain_attr = 0;
+        Ok(())
+    }
+}
+
+fn attribute_request(attr_id: u16, value: Option<u32>) -> Bytes {
+    let mut req = BytesMut::new();
+    req.put_u16_le(attr_id);
+    if let Some(value) = value {
+        req.put_u32_le(value);
+    }
+    req.freeze()
+}
+
+fn main() {
+    let instance = SharedAttributeInstance {
+        id: 1,
+        class_id: ClassCode::Identity,
+        locked_attr: RwLock::new(42),
+        plain_attr: 100,
+    };
+
+    // Set and Get of the RwLock attribute through a shared reference
+    let result = instance.execute_shared(
+        0x10,
+        &mut attribute_request(0x01, Some(999)),
+        &mut BytesMut::new(),
+    );
+    assert!(result.is_ok());
+
+    let mut resp = BytesMut::new();
+    let result = instance.execute_shared(0x0E, &mut attribute_request(0x01, None), &mut resp);
+    assert!(result.is_ok());
+    assert_eq!(resp.get_u32_le(), 999);
+
+    // Plain attributes need `&mut self` to be set
+    let result = instance.execute_shared(
+        0x10,
+        &mut attribute_request(0x02, Some(555)),
+        &mut BytesMut::new(),
+    );
+    assert!(matches!(result, Err(CipError::AttributeNotSetable)));
+    assert_eq!(instance.plain_attr, 100);
+
+    // Only services taking `&self` are reachable through a shared reference
+    let mut resp = BytesMut::new();
+    assert!(instance.execute_shared(0x4B, &mut Bytes::new(), &mut resp).is_ok());
+    assert_eq!(resp.as_ref(), &[0x01]);
+    let result = instance.execute_shared(0x4C, &mut Bytes::new(), &mut BytesMut::new());
+    assert!(matches!(result, Err(CipError::ServiceNotSupported)));
+
+    // A request without the attribute id is rejected
+    let result = instance.execute_shared(0x0E, &mut Bytes::from_static(&[0x01]), &mut resp);
+    assert!(matches!(result, Err(CipError::NotEnoughData)));
+}
@@ -0,0 +1,97 @@
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc, RwLock,
+        atomic::{AtomicU16, Ordering},
+    },
+};
+
+use bytes::{Buf, Bytes, BytesMut};
+use cip_macros::{CipClass, CipInstance};
+
+use crate::cip::{
+    ClassCode,
+    error::CipError,
+    object::{CipClass, CipInstance, CipObject, CipResult, InstanceFactory},
+};
+
+#[path = "../../cip/mod.rs"]
+mod cip;
+
+#[derive(CipClass)]
+#[cip(id = ClassCode::Identity, name = "Assembly", singleton = false, create = true, max_instance = 2, on_create = instance_created, on_delete = instance_deleted)]
+pub struct AssemblyClass {
+    factory: InstanceFactory,
+    pub instances: RwLock<HashMap<u16, Arc<dyn CipInstance>>>,
+    created: AtomicU16,
+    deleted: AtomicU16,
+}
+
+impl AssemblyClass {
+    fn instance_created(&self, instance_id: u16) -> CipResult {
+        // The instance is visible once the hook runs
+        self.get_instance(instance_id)?;
+        self.created.store(instance_id, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn instance_deleted(&self, instance_id: u16) {
+        self.deleted.store(instance_id, Ordering::Relaxed);
+    }
+}
+
+#[derive(CipInstance)]
+pub struct AssemblyInstance {
+    id: u16,
+    class_id: ClassCode,
+}
+
+fn main() {
+    let mut class = AssemblyClass {
+        factory: Box::new(|id, _req| {
+            Ok(Arc::new(AssemblyInstance {
+                id,
+                class_id: ClassCode::Identity,
+            }))
+        }),
+        instances: RwLock::new(HashMap::new()),
+        created: AtomicU16::new(0),
+        deleted: AtomicU16::new(0),
+    };
+
+    let mut resp = BytesMut::new();
+    assert!(
+        class
+            .execute_service(0x08, &mut Bytes::new(), &mut resp)
+            .is_ok()
+    );
+    assert_eq!(resp.as_ref(), &[0x01, 0x00]);
+    assert_eq!(class.create_instance(&mut Bytes::new()).unwrap(), 2);
+    assert_eq!(class.created.load(Ordering::Relaxed), 2);
+    assert!(matches!(
+        class.create_instance(&mut Bytes::new()),
+        Err(CipError::ResourceUnavailable)
+    ));
+
+    let duplicate = Arc::new(AssemblyInstance {
+        id: 1,
+        class_id: ClassCode::Identity,
+    });
+    assert!(matches!(
+        class.add_instance(duplicate),
+        Err(CipError::ObjectAlreadyExists)
+    ));
+
+    let mut req = Bytes::from_static(&[0x01, 0x00]);
+    assert!(matches!(
+        class.execute_service(0x09, &mut req, &mut BytesMut::new()),
+        Err(CipError::ServiceNotSupported)
+    ));
+    assert!(class.delete_instance(1).is_ok());
+    assert!(class.get_instance(1).is_err());
+    assert_eq!(class.deleted.load(Ordering::Relaxed), 1);
+    assert!(matches!(
+        class.delete_instance(1),
+        Err(CipError::ObjectDoesNotExist)
+    ));
+}
@@ -0,0 +1,58 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use bytes::{Buf, Bytes, BytesMut};
+use cip_macros::{CipClass, CipInstance};
+
+use crate::cip::{
+    ClassCode,
+    error::CipError,
+    object::{CipClass, CipInstance, CipObject, CipResult},
+};
+
+#[path = "../../cip/mod.rs"]
+mod cip;
+
+#[derive(CipClass)]
+#[cip(id = ClassCode::TcpIpInterface, name = "TCP/IP Interface", singleton = false)]
+pub struct TcpIpInterfaceClass {
+    pub instances: RwLock<HashMap<u16, Arc<dyn CipInstance>>>,
+}
+
+#[derive(CipInstance)]
+pub struct TcpIpInstance {
+    id: u16,
+    class_id: ClassCode,
+}
+
+fn main() {
+    let mut class = TcpIpInterfaceClass {
+        instances: RwLock::new(HashMap::new()),
+    };
+
+    for id in [7, 2, 5, 1] {
+        let instance = Arc::new(TcpIpInstance {
+            id,
+            class_id: ClassCode::TcpIpInterface,
+        });
+        assert!(class.add_instance(instance).is_ok());
+    }
+
+    // At most 2 instances from the start of the class
+    let mut req = Bytes::from_static(&[0x02]);
+    let mut resp = BytesMut::new();
+    assert!(class.execute_service(0x11, &mut req, &mut resp).is_ok());
+    assert_eq!(resp.as_ref(), &[0x02, 0x01, 0x00, 0x02, 0x00]);
+
+    assert_eq!(class.find_next_instances(0, 10).unwrap(), vec![1, 2, 5, 7]);
+    assert_eq!(class.find_next_instances(1, 2).unwrap(), vec![2, 5]);
+    assert!(class.find_next_instances(7, 10).unwrap().is_empty());
+
+    let mut req = Bytes::new();
+    assert!(matches!(
+        class.execute_service(0x11, &mut req, &mut BytesMut::new()),
+        Err(CipError::NotEnoughData)
+    ));
+}
@@ -0,0 +1,81 @@
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use cip_macros::{CipDecode, CipEncode};
+
+// Minimal stand-ins for the binary traits of the main crate.
+#[derive(Debug, PartialEq)]
+pub enum BinaryError {
+    BufferTooSmall { expected: usize, actual: usize },
+    Truncated { expected: usize, actual: usize },
+}
+
+pub trait FromBytes: Sized {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError>;
+}
+
+pub trait ToBytes {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError>;
+    fn encoded_len(&self) -> usize;
+}
+
+impl FromBytes for u16 {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        if buffer.remaining() < 2 {
+            return Err(BinaryError::Truncated {
+                expected: 2,
+                actual: buffer.remaining(),
+            });
+        }
+        Ok(buffer.get_u16_le())
+    }
+}
+
+impl ToBytes for u16 {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        buffer.put_u16_le(*self);
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        2
+    }
+}
+
+#[derive(Debug, PartialEq, CipEncode, CipDecode)]
+pub struct Range {
+    low: u16,
+    high: u16,
+}
+
+#[derive(Debug, PartialEq, CipEncode, CipDecode)]
+pub struct Limits(Range, u16);
+
+fn main() {
+    let limits = Limits(Range { low: 1, high: 2 }, 3);
+    assert_eq!(limits.encoded_len(), 6);
+
+    let mut buffer = BytesMut::new();
+    limits.encode(&mut buffer).expect("Failed to encode");
+    assert_eq!(buffer.as_ref(), &[0x01, 0x00, 0x02, 0x00, 0x03, 0x00]);
+
+    let decoded = Limits::decode(&mut buffer.freeze()).expect("Failed to decode");
+    assert_eq!(decoded, limits);
+
+    let mut small = [0u8; 4];
+    let result = limits.encode(&mut &mut small[..]);
+    assert_eq!(
+        result,
+        Err(BinaryError::BufferTooSmall {
+            expected: 6,
+            actual: 4
+        })
+    );
+
+    let result = Range::decode(&mut Bytes::from_static(&[0x01, 0x00, 0x02]));
+    assert_eq!(
+        result,
+        Err(BinaryError::Truncated {
+            expected: 2,
+            actual: 1
+        })
+    );
+}
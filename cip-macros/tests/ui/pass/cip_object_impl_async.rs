@@ -0,0 +1,81 @@
+use std::{
+    future::Future,
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use cip_macros::{CipInstance, cip_object_impl};
+
+use crate::cip::{
+    ClassCode,
+    error::CipError,
+    object::{CipInstance, CipObject, CipResult},
+};
+
+#[path = "../../cip/mod.rs"]
+mod cip;
+
+#[derive(CipInstance)]
+#[cip(custom_services = true)]
+struct SensorInstance {
+    id: u16,
+    class_id: ClassCode,
+}
+
+#[cip_object_impl]
+impl SensorInstance {
+    #[service(0x4B, async)]
+    async fn read_sensor(&mut self, _req: &mut Bytes, resp: &mut BytesMut) -> CipResult {
+        resp.put_u16_le(self.id);
+        Ok(())
+    }
+
+    #[service(0x4C, async)]
+    async fn read_class(&self, _req: &mut Bytes, resp: &mut BytesMut) -> CipResult {
+        resp.put_u16_le(self.class_id.into());
+        Ok(())
+    }
+}
+
+fn main() {
+    let mut instance = SensorInstance {
+        id: 7,
+        class_id: ClassCode::Identity,
+    };
+
+    let mut resp = BytesMut::new();
+    let mut req = Bytes::new();
+    {
+        let service = instance
+            .execute_async_service(0x4B, &mut req, &mut resp)
+            .expect("0x4B is an async service");
+        let mut service = pin!(service);
+        let result = service
+            .as_mut()
+            .poll(&mut Context::from_waker(Waker::noop()));
+        assert!(matches!(result, Poll::Ready(Ok(()))));
+    }
+    assert_eq!(resp.get_u16_le(), 7);
+
+    let mut req = Bytes::new();
+    {
+        let service = instance
+            .execute_shared_async(0x4C, &mut req, &mut resp)
+            .expect("0x4C is a shared async service");
+        let mut service = pin!(service);
+        let result = service
+            .as_mut()
+            .poll(&mut Context::from_waker(Waker::noop()));
+        assert!(matches!(result, Poll::Ready(Ok(()))));
+    }
+    assert_eq!(resp.get_u16_le(), 0x01);
+
+    let mut req = Bytes::new();
+    assert!(instance.execute_shared_async(0x4B, &mut req, &mut resp).is_none());
+    assert!(instance.execute_async_service(0x0E, &mut req, &mut resp).is_none());
+    assert!(matches!(
+        instance.execute_service(0x4B, &mut Bytes::new(), &mut BytesMut::new()),
+        Err(CipError::ServiceNotSupported)
+    ));
+}
@@ -0,0 +1,67 @@
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use cip_macros::{CipInstance, cip_object_impl};
+
+use crate::cip::{
+    ClassCode,
+    error::CipError,
+    object::{CipInstance, CipObject, CipResult, ReplyStatus, ServiceResult},
+};
+
+#[path = "../../cip/mod.rs"]
+mod cip;
+
+#[derive(CipInstance)]
+#[cip(custom_services = true)]
+struct LogInstance {
+    id: u16,
+    class_id: ClassCode,
+}
+
+#[cip_object_impl]
+impl LogInstance {
+    /// Replies with one byte from the requested offset, Partial Transfer until the last.
+    #[service(0x4C)]
+    fn read_entry(&self, req: &mut Bytes, resp: &mut BytesMut) -> ServiceResult {
+        let offset = req.get_u8();
+        resp.put_u8(offset);
+        match offset {
+            0..3 => Ok(ReplyStatus::PartialTransfer),
+            _ => Ok(ReplyStatus::Success),
+        }
+    }
+
+    #[service(0x4D)]
+    fn clear(&mut self, _req: &mut Bytes, _resp: &mut BytesMut) -> ServiceResult {
+        Ok(ReplyStatus::Success)
+    }
+}
+
+fn main() {
+    let mut instance = LogInstance {
+        id: 1,
+        class_id: ClassCode::Identity,
+    };
+
+    let mut resp = BytesMut::new();
+    let partial =
+        instance.execute_shared_with_status(0x4C, &mut Bytes::from_static(&[0x01]), &mut resp);
+    let last =
+        instance.execute_shared_with_status(0x4C, &mut Bytes::from_static(&[0x03]), &mut resp);
+    assert!(matches!(partial, Ok(ReplyStatus::PartialTransfer)));
+    assert!(matches!(last, Ok(ReplyStatus::Success)));
+    assert_eq!(resp.as_ref(), &[0x01, 0x03]);
+
+    let result = instance.execute_service(0x4C, &mut Bytes::from_static(&[0x00]), &mut resp);
+    assert!(matches!(result, Ok(())));
+    assert!(instance
+        .execute_service(0x4D, &mut Bytes::new(), &mut BytesMut::new())
+        .is_ok());
+    assert!(matches!(
+        instance.execute_shared_with_status(0x4D, &mut Bytes::new(), &mut BytesMut::new()),
+        Err(CipError::ServiceNotSupported)
+    ));
+    assert!(matches!(
+        instance.execute_shared_with_status(0x0E, &mut Bytes::from_static(&[0x01, 0x00]), &mut BytesMut::new()),
+        Err(_)
+    ));
+}
@@ -2,15 +2,37 @@ use std::fmt::Display;
 
 pub mod cip_identity;
 pub mod common;
+pub mod connection_configuration;
+pub mod connection_manager;
 pub mod data_types;
+pub mod dlr;
+pub mod dynamic;
+pub mod events;
+pub mod file;
+pub mod lldp;
+pub mod message_router;
+pub mod parameter;
+pub mod port;
+pub mod qos;
 pub mod registry;
 pub mod tcp_ip_interface;
+pub mod time_sync;
 
 #[repr(u16)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ClassCode {
     Identity = 0x01,
+    ConnectionManager = 0x06,
+    Parameter = 0x0F,
+    File = 0x37,
+    TimeSync = 0x43,
+    Dlr = 0x47,
+    Qos = 0x48,
+    ConnectionConfiguration = 0xF3,
+    Port = 0xF4,
     TcpIpInterface = 0xF5,
+    LldpManagement = 0x109,
+    LldpDataTable = 0x10A,
     UserDefined(u16),
 }
 
@@ -18,7 +40,17 @@ impl From<u16> for ClassCode {
     fn from(id: u16) -> Self {
         match id {
             0x01 => ClassCode::Identity,
+            0x06 => ClassCode::ConnectionManager,
+            0x0F => ClassCode::Parameter,
+            0x37 => ClassCode::File,
+            0x43 => ClassCode::TimeSync,
+            0x47 => ClassCode::Dlr,
+            0x48 => ClassCode::Qos,
+            0xF3 => ClassCode::ConnectionConfiguration,
+            0xF4 => ClassCode::Port,
             0xF5 => ClassCode::TcpIpInterface,
+            0x109 => ClassCode::LldpManagement,
+            0x10A => ClassCode::LldpDataTable,
             _ => ClassCode::UserDefined(id),
         }
     }
@@ -28,7 +60,17 @@ impl From<&ClassCode> for u16 {
     fn from(id: &ClassCode) -> Self {
         match id {
             ClassCode::Identity => 0x01,
+            ClassCode::ConnectionManager => 0x06,
+            ClassCode::Parameter => 0x0F,
+            ClassCode::File => 0x37,
+            ClassCode::TimeSync => 0x43,
+            ClassCode::Dlr => 0x47,
+            ClassCode::Qos => 0x48,
+            ClassCode::ConnectionConfiguration => 0xF3,
+            ClassCode::Port => 0xF4,
             ClassCode::TcpIpInterface => 0xF5,
+            ClassCode::LldpManagement => 0x109,
+            ClassCode::LldpDataTable => 0x10A,
             ClassCode::UserDefined(id) => *id,
         }
     }
@@ -38,7 +80,17 @@ impl From<ClassCode> for u16 {
     fn from(id: ClassCode) -> Self {
         match id {
             ClassCode::Identity => 0x01,
+            ClassCode::ConnectionManager => 0x06,
+            ClassCode::Parameter => 0x0F,
+            ClassCode::File => 0x37,
+            ClassCode::TimeSync => 0x43,
+            ClassCode::Dlr => 0x47,
+            ClassCode::Qos => 0x48,
+            ClassCode::ConnectionConfiguration => 0xF3,
+            ClassCode::Port => 0xF4,
             ClassCode::TcpIpInterface => 0xF5,
+            ClassCode::LldpManagement => 0x109,
+            ClassCode::LldpDataTable => 0x10A,
             ClassCode::UserDefined(id) => id,
         }
     }
@@ -48,9 +100,21 @@ impl Display for ClassCode {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ClassCode::Identity => write!(f, "{:#04x}: Identity", u16::from(self)),
+            ClassCode::ConnectionManager => {
+                write!(f, "{:#04x}: Connection Manager", u16::from(self))
+            }
+            ClassCode::Parameter => write!(f, "{:#04x}: Parameter", u16::from(self)),
+            ClassCode::File => write!(f, "{:#04x}: File", u16::from(self)),
+            ClassCode::TimeSync => write!(f, "{:#04x}: Time Sync", u16::from(self)),
+            ClassCode::Dlr => write!(f, "{:#04x}: DLR", u16::from(self)),
+            ClassCode::Qos => write!(f, "{:#04x}: QoS", u16::from(self)),
+            ClassCode::ConnectionConfiguration => write!(f, "{:#04x}: Connection Configuration", u16::from(self)),
+            ClassCode::Port => write!(f, "{:#04x}: Port", u16::from(self)),
             ClassCode::TcpIpInterface => {
                 write!(f, "{:#04x}: TCP/IP Interface", u16::from(self))
             }
+            ClassCode::LldpManagement => write!(f, "{:#04x}: LLDP Management", u16::from(self)),
+            ClassCode::LldpDataTable => write!(f, "{:#04x}: LLDP Data Table", u16::from(self)),
             ClassCode::UserDefined(id) => write!(f, "{:#04x}: User Defined", id),
         }
     }
@@ -7,7 +7,10 @@ use super::{
     ClassCode,
     common::error::CipError,
     common::object::{CipClass, CipInstance, CipObject, CipResult},
-    data_types::short_string::ShortString,
+    data_types::{
+        short_string::ShortString,
+        string_i::{LanguageCode, StringI},
+    },
 };
 use crate::common::binary::{BinaryError, FromBytes, ToBytes};
 
@@ -22,12 +25,106 @@ pub struct IdentityInfo {
     pub product_name: &'static str,
 }
 
+/// Identity as written in configuration files, with the revision as "major.minor".
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct IdentityInfoConfig<'a> {
+    vendor_id: u16,
+    device_type: u16,
+    product_code: u16,
+    revision: Revision,
+    serial_number: u32,
+    product_name: std::borrow::Cow<'a, str>,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for IdentityInfo {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        IdentityInfoConfig {
+            vendor_id: self.vendor_id,
+            device_type: self.device_type,
+            product_code: self.product_code,
+            revision: Revision {
+                major: self.revision_major,
+                minor: self.revision_minor,
+            },
+            serial_number: self.serial_number,
+            product_name: self.product_name.into(),
+        }
+        .serialize(serializer)
+    }
+}
+
+/// Deserialized product names are interned to get the `'static` lifetime, so reloading a
+/// configuration does not allocate a name that was already loaded.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for IdentityInfo {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let config = IdentityInfoConfig::deserialize(deserializer)?;
+        Ok(Self {
+            vendor_id: config.vendor_id,
+            device_type: config.device_type,
+            product_code: config.product_code,
+            revision_major: config.revision.major,
+            revision_minor: config.revision.minor,
+            serial_number: config.serial_number,
+            product_name: intern_product_name(&config.product_name),
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+fn intern_product_name(name: &str) -> &'static str {
+    static NAMES: std::sync::Mutex<std::collections::BTreeSet<&'static str>> =
+        std::sync::Mutex::new(std::collections::BTreeSet::new());
+
+    let mut names = NAMES
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    match names.get(name) {
+        Some(interned) => interned,
+        None => {
+            let interned: &'static str = Box::leak(name.into());
+            names.insert(interned);
+            interned
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Revision {
     pub major: u8,
     pub minor: u8,
 }
 
+/// Formats as `major.minor`, ex: "1.2".
+impl std::fmt::Display for Revision {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+impl std::str::FromStr for Revision {
+    type Err = BinaryError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || BinaryError::InvalidData {
+            message: "Invalid revision".to_string(),
+            expected: "major.minor".to_string(),
+            actual: text.to_string(),
+        };
+
+        let (major, minor) = text.split_once('.').ok_or_else(invalid)?;
+        Ok(Self {
+            major: major.parse().map_err(|_| invalid())?,
+            minor: minor.parse().map_err(|_| invalid())?,
+        })
+    }
+}
+
+#[cfg(feature = "serde")]
+crate::common::serde_text::impl_serde_text!(Revision);
+
 impl FromBytes for Revision {
     fn decode<T: bytes::Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
         if buffer.remaining() < 2 {
@@ -97,6 +194,98 @@ impl From<DeviceState> for u8 {
     }
 }
 
+impl FromBytes for DeviceState {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        Ok(u8::decode(buffer)?.into())
+    }
+}
+
+impl ToBytes for DeviceState {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        u8::from(*self).encode(buffer)
+    }
+
+    fn encoded_len(&self) -> usize {
+        1
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LanguageList(pub Vec<LanguageCode>);
+
+impl FromBytes for LanguageList {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        let mut languages = Vec::new();
+        while buffer.remaining() >= LanguageCode::LEN {
+            languages.push(LanguageCode::decode(buffer)?);
+        }
+
+        Ok(Self(languages))
+    }
+}
+
+impl ToBytes for LanguageList {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        for language in &self.0 {
+            language.encode(buffer)?;
+        }
+
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        self.0.len() * LanguageCode::LEN
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Semaphore {
+    pub client_vendor_id: u16,
+    pub client_serial_number: u32,
+    pub timer_ms: u16,
+}
+
+impl Semaphore {
+    pub const LEN: usize = 8;
+}
+
+impl FromBytes for Semaphore {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        if buffer.remaining() < Self::LEN {
+            return Err(BinaryError::Truncated {
+                expected: Self::LEN,
+                actual: buffer.remaining(),
+            });
+        }
+
+        Ok(Self {
+            client_vendor_id: buffer.get_u16_le(),
+            client_serial_number: buffer.get_u32_le(),
+            timer_ms: buffer.get_u16_le(),
+        })
+    }
+}
+
+impl ToBytes for Semaphore {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < Self::LEN {
+            return Err(BinaryError::BufferTooSmall {
+                expected: Self::LEN,
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        buffer.put_u16_le(self.client_vendor_id);
+        buffer.put_u32_le(self.client_serial_number);
+        buffer.put_u16_le(self.timer_ms);
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        Self::LEN
+    }
+}
+
 #[derive(Debug)]
 pub enum Test {
     Id = 0x01,
@@ -136,14 +325,60 @@ impl IdentityClass {
 pub struct IdentityInstance {
     id: u16,
     class_id: ClassCode,
+
+    #[attribute(id = 1, access = "get")]
     pub vendor_id: u16,
+
+    #[attribute(id = 2, access = "get")]
     pub device_type: u16,
+
+    #[attribute(id = 3, access = "get")]
     pub product_code: u16,
+
+    #[attribute(id = 4, access = "get")]
     pub revision: Revision,
+
+    #[attribute(id = 5, access = "get")]
     pub status: u16,
+
+    #[attribute(id = 6, access = "get")]
     pub serial_number: u32,
+
+    #[attribute(id = 7, access = "get")]
     pub product_name: ShortString,
+
+    #[attribute(id = 8, access = "get")]
     pub state: DeviceState,
+
+    #[attribute(id = 9, access = "get")]
+    pub configuration_consistency_value: u16,
+
+    #[attribute(id = 10, access = "set")]
+    pub heartbeat_interval: RwLock<u8>,
+
+    #[attribute(id = 11, access = "set")]
+    pub active_language: RwLock<LanguageCode>,
+
+    #[attribute(id = 12, access = "get")]
+    pub supported_languages: LanguageList,
+
+    #[attribute(id = 13, access = "get")]
+    pub international_product_name: StringI,
+
+    #[attribute(id = 14, access = "set")]
+    pub semaphore: RwLock<Semaphore>,
+
+    #[attribute(id = 15, access = "set")]
+    pub assigned_name: RwLock<StringI>,
+
+    #[attribute(id = 16, access = "set")]
+    pub assigned_description: RwLock<StringI>,
+
+    #[attribute(id = 17, access = "set")]
+    pub geographic_location: RwLock<StringI>,
+
+    #[attribute(id = 19, access = "get")]
+    pub protection_mode: u16,
 }
 
 #[cip_object_impl]
@@ -165,6 +400,16 @@ impl IdentityInstance {
             serial_number: info.serial_number,
             product_name: info.product_name.into(),
             state: DeviceState::Default,
+            configuration_consistency_value: 0,
+            heartbeat_interval: RwLock::new(0),
+            active_language: RwLock::new(LanguageCode::ENGLISH),
+            supported_languages: LanguageList(vec![LanguageCode::ENGLISH]),
+            international_product_name: StringI::new(info.product_name),
+            semaphore: RwLock::new(Semaphore::default()),
+            assigned_name: RwLock::new(StringI::default()),
+            assigned_description: RwLock::new(StringI::default()),
+            geographic_location: RwLock::new(StringI::default()),
+            protection_mode: 0,
         }
     }
 
@@ -205,3 +450,110 @@ impl ToBytes for IdentityInstance {
         Self::BASE_ATTRIBUTES_LEN + self.product_name.encoded_len()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::{Bytes, BytesMut};
+
+    const INFO: IdentityInfo = IdentityInfo {
+        vendor_id: 0x1234,
+        device_type: 0x000C,
+        product_code: 0x0042,
+        revision_major: 1,
+        revision_minor: 2,
+        serial_number: 0xDEADBEEF,
+        product_name: "Adapter",
+    };
+
+    fn attribute_request(attribute_id: u16, data: &[u8]) -> Bytes {
+        let mut req = BytesMut::new();
+        req.put_u16_le(attribute_id);
+        req.put_slice(data);
+        req.freeze()
+    }
+
+    #[test]
+    fn get_attribute_single_returns_required_attributes() {
+        let mut instance = IdentityInstance::new(&INFO);
+
+        let mut resp = BytesMut::new();
+        instance
+            .execute_service(0x0E, &mut attribute_request(6, &[]), &mut resp)
+            .expect("Failed to get serial number");
+        assert_eq!(resp.as_ref(), &0xDEADBEEFu32.to_le_bytes());
+
+        let mut resp = BytesMut::new();
+        instance
+            .execute_service(0x0E, &mut attribute_request(7, &[]), &mut resp)
+            .expect("Failed to get product name");
+        assert_eq!(resp.as_ref(), b"\x07Adapter");
+    }
+
+    #[test]
+    fn set_attribute_single_updates_assigned_name() {
+        let mut instance = IdentityInstance::new(&INFO);
+        let mut value = BytesMut::new();
+        StringI::new("Line 3 Press")
+            .encode(&mut value)
+            .expect("Failed to encode name");
+
+        let mut resp = BytesMut::new();
+        instance
+            .execute_service(0x10, &mut attribute_request(15, &value), &mut resp)
+            .expect("Failed to set assigned name");
+
+        assert_eq!(
+            instance
+                .assigned_name
+                .read()
+                .expect("Failed to lock assigned name")
+                .value(LanguageCode::ENGLISH)
+                .as_deref(),
+            Some("Line 3 Press")
+        );
+
+        let mut resp = BytesMut::new();
+        instance
+            .execute_service(0x0E, &mut attribute_request(15, &[]), &mut resp)
+            .expect("Failed to get assigned name");
+        assert_eq!(resp.as_ref(), value.as_ref());
+    }
+
+    #[test]
+    fn set_attribute_single_on_read_only_attribute_fails() {
+        let mut instance = IdentityInstance::new(&INFO);
+
+        let mut resp = BytesMut::new();
+        let shared =
+            instance.execute_shared(0x10, &mut attribute_request(1, &[0x01, 0x00]), &mut resp);
+        let exclusive =
+            instance.execute_service(0x10, &mut attribute_request(1, &[0x01, 0x00]), &mut resp);
+        let unknown =
+            instance.execute_shared(0x10, &mut attribute_request(18, &[0x01, 0x00]), &mut resp);
+
+        assert!(matches!(shared, Err(CipError::AttributeNotSetable)));
+        assert!(matches!(exclusive, Err(CipError::AttributeNotSetable)));
+        assert!(matches!(unknown, Err(CipError::AttributeNotSupported)));
+        assert_eq!(instance.vendor_id, 0x1234);
+    }
+
+    #[test]
+    fn set_attribute_single_with_invalid_characters_fails() {
+        let mut instance = IdentityInstance::new(&INFO);
+        // One English SHORT_STRING entry holding an invalid UTF-8 byte
+        let value = [0x01, b'e', b'n', b'g', 0xDA, 0x04, 0x00, 0x02, 0x41, 0xFF];
+
+        let mut resp = BytesMut::new();
+        let result = instance.execute_service(0x10, &mut attribute_request(15, &value), &mut resp);
+
+        assert!(matches!(result, Err(CipError::InvalidParameterValue)));
+        assert_eq!(
+            *instance
+                .assigned_name
+                .read()
+                .expect("Failed to lock assigned name"),
+            StringI::default()
+        );
+    }
+}
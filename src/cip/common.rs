@@ -1,3 +1,5 @@
 pub mod cip_service;
 pub mod error;
+pub mod fragment;
 pub mod object;
+pub mod shared;
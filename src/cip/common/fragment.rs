@@ -0,0 +1,116 @@
+use bytes::{BufMut, BytesMut};
+
+use super::{
+    error::CipError,
+    object::{ReplyStatus, ServiceResult},
+};
+
+/// Largest reply data of an unconnected explicit message.
+pub const MAX_UNCONNECTED_REPLY_SIZE: usize = 504;
+
+/// Writes the part of `data` starting at `offset` that fits in `max_len` bytes.
+///
+/// Returns [`ReplyStatus::PartialTransfer`] when data remains, the reply then carries
+/// status 0x06 and the client continues at `offset` plus the fragment length.
+pub fn read_fragment(
+    data: &[u8],
+    offset: u32,
+    max_len: usize,
+    resp: &mut BytesMut,
+) -> ServiceResult {
+    let start = offset as usize;
+    if start > data.len() {
+        return Err(CipError::InvalidParameterValue);
+    }
+
+    let end = data.len().min(start + max_len);
+    resp.put_slice(&data[start..end]);
+
+    if end < data.len() {
+        return Ok(ReplyStatus::PartialTransfer);
+    }
+
+    Ok(ReplyStatus::Success)
+}
+
+/// Collects the fragments of a value written at increasing offsets.
+#[derive(Debug, Default)]
+pub struct FragmentedWrite {
+    data: Vec<u8>,
+}
+
+impl FragmentedWrite {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends a fragment of a value of `total_len` bytes, returning the whole value with
+    /// the last fragment. Fragments must follow each other, a write at offset 0 restarts
+    /// the sequence.
+    pub fn write(
+        &mut self,
+        offset: u32,
+        fragment: &[u8],
+        total_len: usize,
+    ) -> Result<Option<Vec<u8>>, CipError> {
+        if offset == 0 {
+            self.data.clear();
+        } else if offset as usize != self.data.len() {
+            return Err(CipError::ServiceFragmentationSequenceNotInProgress);
+        }
+
+        if self.data.len() + fragment.len() > total_len {
+            self.data.clear();
+            return Err(CipError::TooMuchData);
+        }
+
+        self.data.extend_from_slice(fragment);
+        if self.data.len() < total_len {
+            return Ok(None);
+        }
+
+        Ok(Some(std::mem::take(&mut self.data)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn read_fragment_continues_at_offset_until_complete() {
+        let data: Vec<u8> = (0..=255).cycle().take(1200).collect();
+        let mut read = Vec::new();
+
+        let mut offset = 0;
+        loop {
+            let mut resp = BytesMut::new();
+            let result = read_fragment(&data, offset, MAX_UNCONNECTED_REPLY_SIZE, &mut resp);
+            read.extend_from_slice(&resp);
+            offset += resp.len() as u32;
+
+            match result.expect("Failed to read fragment") {
+                ReplyStatus::PartialTransfer => continue,
+                ReplyStatus::Success => break,
+            }
+        }
+
+        assert_eq!(read, data);
+    }
+
+    #[test]
+    fn fragmented_write_out_of_sequence_returns_error() {
+        let mut write = FragmentedWrite::new();
+
+        assert!(matches!(write.write(0, &[0x01, 0x02], 6), Ok(None)));
+        assert!(matches!(
+            write.write(4, &[0x05, 0x06], 6),
+            Err(CipError::ServiceFragmentationSequenceNotInProgress)
+        ));
+        assert!(matches!(write.write(2, &[0x03, 0x04], 6), Ok(None)));
+        assert_eq!(
+            write.write(4, &[0x05, 0x06], 6).expect("Failed to write"),
+            Some(vec![0x01, 0x02, 0x03, 0x04, 0x05, 0x06])
+        );
+    }
+}
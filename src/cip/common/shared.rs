@@ -0,0 +1,400 @@
+use std::{
+    any::Any,
+    sync::{Arc, RwLock, RwLockReadGuard, RwLockWriteGuard},
+};
+
+use bytes::{Bytes, BytesMut};
+
+use super::{
+    error::CipError,
+    object::{CipFuture, CipInstance, CipObject, CipResult},
+};
+use crate::cip::{
+    ClassCode,
+    events::{AttributeChanged, AttributeNotifier},
+};
+
+/// Instance behind a per-instance `RwLock`, so an instance registered as an
+/// `Arc<dyn CipInstance>` can still be mutated.
+///
+/// Services take the write lock, except Get_Attribute_Single of derived instances which
+/// only takes the read lock. The application reads and writes attributes through
+/// [`read`](Self::read) and [`write`](Self::write).
+///
+/// Async services are forwarded when the instance is borrowed mutably, before it is
+/// shared. Through a shared reference they report `ServiceNotSupported`, since the
+/// per-instance guard cannot be held across an await.
+///
+/// With a notifier, [`write_attribute`](Self::write_attribute) publishes an
+/// [`AttributeChanged`] event. Set_Attribute_Single requests are published by the message
+/// router.
+pub struct SharedInstance<T> {
+    id: u16,
+    class_id: ClassCode,
+    inner: RwLock<T>,
+    notifier: Option<AttributeNotifier>,
+}
+
+impl<T: CipInstance> SharedInstance<T> {
+    pub fn new(instance: T) -> Arc<Self> {
+        Self::create(instance, None)
+    }
+
+    pub fn with_notifier(instance: T, notifier: AttributeNotifier) -> Arc<Self> {
+        Self::create(instance, Some(notifier))
+    }
+
+    fn create(instance: T, notifier: Option<AttributeNotifier>) -> Arc<Self> {
+        Arc::new(Self {
+            id: instance.id(),
+            class_id: instance.class_id(),
+            inner: RwLock::new(instance),
+            notifier,
+        })
+    }
+
+    pub fn read(&self) -> Result<RwLockReadGuard<'_, T>, CipError> {
+        self.inner.read().map_err(|_| {
+            log::error!("Failed to get read guard for instance {}", self.id);
+            CipError::GeneralError
+        })
+    }
+
+    pub fn write(&self) -> Result<RwLockWriteGuard<'_, T>, CipError> {
+        self.inner.write().map_err(|_| {
+            log::error!("Failed to get write guard for instance {}", self.id);
+            CipError::GeneralError
+        })
+    }
+
+    /// Updates an attribute from the application and publishes the new value, encoded
+    /// through Get_Attribute_Single. The update is kept when the attribute cannot be
+    /// read back, but nothing is published and the error is returned.
+    pub fn write_attribute(&self, attribute_id: u16, update: impl FnOnce(&mut T)) -> CipResult {
+        let mut value = BytesMut::new();
+        {
+            let mut instance = self.write()?;
+            update(&mut instance);
+
+            let mut req = Bytes::copy_from_slice(&attribute_id.to_le_bytes());
+            match instance.execute_read_service(0x0E, &mut req, &mut value) {
+                Some(result) => result?,
+                None => instance.execute_service(0x0E, &mut req, &mut value)?,
+            }
+        }
+
+        self.attribute_changed(attribute_id, value.freeze());
+        Ok(())
+    }
+
+    pub fn execute(&self, service_id: u8, req: &mut Bytes, resp: &mut BytesMut) -> CipResult {
+        if let Some(result) = self.read()?.execute_read_service(service_id, req, resp) {
+            return result;
+        }
+
+        self.write()?.execute_service(service_id, req, resp)
+    }
+
+    fn attribute_changed(&self, attribute: u16, new_value_bytes: Bytes) {
+        if let Some(notifier) = &self.notifier {
+            notifier.notify(AttributeChanged {
+                class: self.class_id,
+                instance: self.id,
+                attribute,
+                new_value_bytes,
+                source_peer: None,
+            });
+        }
+    }
+}
+
+impl<T: CipInstance> CipObject for SharedInstance<T> {
+    fn execute_service(
+        &mut self,
+        service_id: u8,
+        req: &mut Bytes,
+        resp: &mut BytesMut,
+    ) -> CipResult {
+        self.execute(service_id, req, resp)
+    }
+
+    fn execute_async_service<'a>(
+        &'a mut self,
+        service_id: u8,
+        req: &'a mut Bytes,
+        resp: &'a mut BytesMut,
+    ) -> Option<CipFuture<'a>> {
+        match self.inner.get_mut() {
+            Ok(instance) => instance.execute_async_service(service_id, req, resp),
+            Err(_) => {
+                log::error!("Failed to get instance {} for async service", self.id);
+                Some(Box::pin(async { Err(CipError::GeneralError) }))
+            }
+        }
+    }
+
+    fn execute_shared(&self, service_id: u8, req: &mut Bytes, resp: &mut BytesMut) -> CipResult {
+        self.execute(service_id, req, resp)
+    }
+
+    fn execute_read_service(
+        &self,
+        service_id: u8,
+        req: &mut Bytes,
+        resp: &mut BytesMut,
+    ) -> Option<CipResult> {
+        match self.read() {
+            Ok(instance) => instance.execute_read_service(service_id, req, resp),
+            Err(error) => Some(Err(error)),
+        }
+    }
+}
+
+impl<T: CipInstance + 'static> CipInstance for SharedInstance<T> {
+    fn id(&self) -> u16 {
+        self.id
+    }
+
+    fn class_id(&self) -> ClassCode {
+        self.class_id
+    }
+
+    fn as_any_arc(self: Arc<Self>) -> Arc<dyn Any + Send + Sync> {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cip::{
+            common::object::CipClass, connection_manager::MessageRequest,
+            data_types::epath::EPathBuilder, dynamic::DynamicClass, message_router::MessageRouter,
+            registry::Registry,
+        },
+        common::binary::{FromBytes, ToBytes},
+    };
+    use bytes::{Buf, BufMut};
+    use cip_macros::{CipInstance, cip_object_impl};
+    use std::{net::SocketAddr, thread};
+
+    #[derive(CipInstance)]
+    struct SetpointInstance {
+        id: u16,
+        class_id: ClassCode,
+
+        #[attribute(id = 1, access = "set")]
+        setpoint: u32,
+    }
+
+    #[derive(CipInstance)]
+    #[cip(custom_services = true)]
+    struct SensorInstance {
+        id: u16,
+        class_id: ClassCode,
+
+        #[attribute(id = 1, access = "get")]
+        reading: u16,
+    }
+
+    #[cip_object_impl]
+    impl SensorInstance {
+        #[service(0x4B, async)]
+        async fn sample(&mut self, _req: &mut Bytes, resp: &mut BytesMut) -> CipResult {
+            tokio::task::yield_now().await;
+            self.reading += 1;
+            resp.put_u16_le(self.reading);
+            Ok(())
+        }
+    }
+
+    fn registry_with_setpoint() -> Arc<Registry> {
+        let class = DynamicClass::builder(0x64).instance(2).build();
+        class
+            .add_instance(SharedInstance::new(SetpointInstance {
+                id: 1,
+                class_id: ClassCode::UserDefined(0x64),
+                setpoint: 0,
+            }))
+            .expect("Failed to add instance");
+
+        let mut registry = Registry::new();
+        registry.register(class).expect("Failed to register class");
+        Arc::new(registry)
+    }
+
+    #[test]
+    fn concurrent_network_and_application_access_is_consistent() {
+        let registry = registry_with_setpoint();
+        let shared = registry
+            .get_instance::<SharedInstance<SetpointInstance>>(ClassCode::UserDefined(0x64), 1)
+            .expect("Failed to get shared instance");
+
+        let handles: Vec<_> = (1..=4u32)
+            .map(|writer| {
+                let shared = shared.clone();
+                thread::spawn(move || {
+                    // Both halves carry the writer number so torn values are detectable
+                    let value = writer << 16 | writer;
+                    for i in 0..500 {
+                        if i % 2 == 0 {
+                            let mut req = BytesMut::new();
+                            req.put_u16_le(1);
+                            req.put_u32_le(value);
+                            shared
+                                .execute(0x10, &mut req.freeze(), &mut BytesMut::new())
+                                .expect("Failed to set setpoint");
+                        } else {
+                            shared.write().expect("Failed to lock").setpoint = value;
+                        }
+
+                        let mut resp = BytesMut::new();
+                        shared
+                            .execute(0x0E, &mut Bytes::from_static(&[0x01, 0x00]), &mut resp)
+                            .expect("Failed to get setpoint");
+                        let read = resp.get_u32_le();
+                        assert_eq!(read >> 16, read & 0xFFFF);
+                    }
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().expect("Access thread panicked");
+        }
+
+        let setpoint = shared.read().expect("Failed to lock").setpoint;
+        assert!((1..=4).any(|writer| setpoint == writer << 16 | writer));
+    }
+
+    #[test]
+    fn set_through_registry_is_visible_to_application() {
+        let registry = registry_with_setpoint();
+        let instance = registry
+            .get(0x64)
+            .expect("Class not registered")
+            .get_instance(1)
+            .expect("Failed to get instance");
+
+        instance
+            .execute_shared(
+                0x10,
+                &mut Bytes::from_static(&[0x01, 0x00, 0x2A, 0x00, 0x00, 0x00]),
+                &mut BytesMut::new(),
+            )
+            .expect("Failed to set setpoint");
+
+        let shared = registry
+            .get_instance::<SharedInstance<SetpointInstance>>(ClassCode::UserDefined(0x64), 1)
+            .expect("Failed to get shared instance");
+        assert_eq!(shared.read().expect("Failed to lock").setpoint, 42);
+    }
+
+    fn setpoint_request(data: &'static [u8]) -> MessageRequest {
+        MessageRequest {
+            service: 0x10,
+            path: EPathBuilder::new()
+                .class(0x64)
+                .instance(1)
+                .attribute(1)
+                .build(),
+            data: Bytes::from_static(data),
+        }
+    }
+
+    #[tokio::test]
+    async fn set_and_application_write_publish_attribute_changes() {
+        let mut registry = Registry::new();
+        let mut changes = registry.notifier().subscribe();
+        let shared = SharedInstance::with_notifier(
+            SetpointInstance {
+                id: 1,
+                class_id: ClassCode::UserDefined(0x64),
+                setpoint: 0,
+            },
+            registry.notifier(),
+        );
+        let class = DynamicClass::builder(0x64).instance(2).build();
+        class
+            .add_instance(shared.clone())
+            .expect("Failed to add instance");
+        registry.register(class).expect("Failed to register class");
+
+        let peer: SocketAddr = "192.168.1.10:44818".parse().expect("Invalid address");
+        let response = MessageRouter::default()
+            .dispatch(
+                &registry,
+                &setpoint_request(&[0x2A, 0x00, 0x00, 0x00]),
+                Some(peer),
+            )
+            .await;
+        assert!(response.is_success());
+        shared
+            .write_attribute(1, |instance| instance.setpoint = 7)
+            .expect("Failed to write setpoint");
+
+        let from_network = changes.try_recv().expect("Missing set event");
+        let from_application = changes.try_recv().expect("Missing write event");
+        assert_eq!(
+            from_network,
+            AttributeChanged {
+                class: ClassCode::UserDefined(0x64),
+                instance: 1,
+                attribute: 1,
+                new_value_bytes: Bytes::from_static(&[0x2A, 0x00, 0x00, 0x00]),
+                source_peer: Some(peer),
+            }
+        );
+        assert_eq!(from_application.source_peer, None);
+        assert_eq!(
+            from_application.new_value_bytes.as_ref(),
+            &[0x07, 0x00, 0x00, 0x00]
+        );
+        assert!(changes.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn failed_set_publishes_nothing() {
+        let registry = registry_with_setpoint();
+        let mut changes = registry.notifier().subscribe();
+
+        let response = MessageRouter::default()
+            .dispatch(&registry, &setpoint_request(&[0x2A]), None)
+            .await;
+
+        assert_eq!(response.general_status, CipError::NotEnoughData as u8);
+        assert!(changes.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn async_service_runs_before_sharing_only() {
+        let mut shared = Arc::into_inner(SharedInstance::new(SensorInstance {
+            id: 1,
+            class_id: ClassCode::UserDefined(0x64),
+            reading: 0,
+        }))
+        .expect("Instance already shared");
+
+        let mut resp = BytesMut::new();
+        MessageRouter::default()
+            .execute(&mut shared, 0x4B, &mut Bytes::new(), &mut resp)
+            .await
+            .expect("Failed to execute async service");
+        assert_eq!(resp.get_u16_le(), 1);
+
+        let shared: Arc<dyn CipInstance> = Arc::new(shared);
+        let result = shared.execute_shared(0x4B, &mut Bytes::new(), &mut BytesMut::new());
+        assert!(matches!(result, Err(CipError::ServiceNotSupported)));
+        assert_eq!(
+            shared
+                .as_any_arc()
+                .downcast::<SharedInstance<SensorInstance>>()
+                .expect("Failed to downcast")
+                .read()
+                .expect("Failed to lock")
+                .reading,
+            1
+        );
+    }
+}
@@ -0,0 +1,703 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, RwLock},
+};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use cip_macros::{CipClass, CipInstance, cip_object_impl};
+
+use super::{
+    ClassCode,
+    common::error::CipError,
+    common::object::{CipClass, CipInstance, CipObject, CipResult, InstanceFactory},
+    data_types::{epath::SizedEPath, string2::String2},
+};
+use crate::common::{
+    binary::{BinaryError, FromBytes, ToBytes},
+    storage::NonVolatileStorage,
+};
+
+const STORAGE_KEY: &str = "connection_configuration";
+
+/// Longest connection name in characters.
+pub const MAX_CONNECTION_NAME_LEN: usize = 64;
+
+/// Instance attribute 5, the timing and network connection parameters of the Forward_Open
+/// sent for the connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NetConnectionParameters {
+    pub timeout_multiplier: u8,
+    pub o_to_t_rpi_us: u32,
+    pub o_to_t_network_parameters: u16,
+    pub t_to_o_rpi_us: u32,
+    pub t_to_o_network_parameters: u16,
+}
+
+impl NetConnectionParameters {
+    const LEN: usize = 13;
+}
+
+impl FromBytes for NetConnectionParameters {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        if buffer.remaining() < Self::LEN {
+            return Err(BinaryError::Truncated {
+                expected: Self::LEN,
+                actual: buffer.remaining(),
+            });
+        }
+
+        Ok(Self {
+            timeout_multiplier: buffer.get_u8(),
+            o_to_t_rpi_us: buffer.get_u32_le(),
+            o_to_t_network_parameters: buffer.get_u16_le(),
+            t_to_o_rpi_us: buffer.get_u32_le(),
+            t_to_o_network_parameters: buffer.get_u16_le(),
+        })
+    }
+}
+
+impl ToBytes for NetConnectionParameters {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < Self::LEN {
+            return Err(BinaryError::BufferTooSmall {
+                expected: Self::LEN,
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        buffer.put_u8(self.timeout_multiplier);
+        buffer.put_u32_le(self.o_to_t_rpi_us);
+        buffer.put_u16_le(self.o_to_t_network_parameters);
+        buffer.put_u32_le(self.t_to_o_rpi_us);
+        buffer.put_u16_le(self.t_to_o_network_parameters);
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        Self::LEN
+    }
+}
+
+/// Configuration of one originated connection. It is encoded in instance attribute order:
+/// net connection parameters (5), connection path (6) and connection name (8). Create
+/// takes it as request data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ConnectionConfig {
+    pub net_parameters: NetConnectionParameters,
+    pub connection_path: SizedEPath,
+    pub connection_name: String2<MAX_CONNECTION_NAME_LEN>,
+}
+
+impl ConnectionConfig {
+    fn validate(&self) -> CipResult {
+        if self.net_parameters.o_to_t_rpi_us == 0 || self.net_parameters.t_to_o_rpi_us == 0 {
+            return Err(CipError::InvalidAttributeValue);
+        }
+
+        Ok(())
+    }
+}
+
+impl FromBytes for ConnectionConfig {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        Ok(Self {
+            net_parameters: NetConnectionParameters::decode(buffer)?,
+            connection_path: SizedEPath::decode(buffer)?,
+            connection_name: String2::decode(buffer)?,
+        })
+    }
+}
+
+impl ToBytes for ConnectionConfig {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < self.encoded_len() {
+            return Err(BinaryError::BufferTooSmall {
+                expected: self.encoded_len(),
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        self.net_parameters.encode(buffer)?;
+        self.connection_path.encode(buffer)?;
+        self.connection_name.encode(buffer)?;
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        self.net_parameters.encoded_len()
+            + self.connection_path.encoded_len()
+            + self.connection_name.encoded_len()
+    }
+}
+
+/// Opens and maintains the connections described by the Connection Configuration object.
+pub trait OriginatorEngine: Send + Sync {
+    /// Called when an instance is created or changed. Returning an error rejects the change.
+    fn configure(&self, instance_id: u16, config: &ConnectionConfig) -> CipResult;
+
+    fn remove(&self, instance_id: u16);
+}
+
+/// Engine for adapters without originator support. Configurations are accepted and kept,
+/// but no connection is opened.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoOriginator;
+
+impl OriginatorEngine for NoOriginator {
+    fn configure(&self, _instance_id: u16, _config: &ConnectionConfig) -> CipResult {
+        Ok(())
+    }
+
+    fn remove(&self, _instance_id: u16) {}
+}
+
+#[derive(CipClass)]
+#[cip(id = ClassCode::ConnectionConfiguration, name = "Connection Configuration", singleton = false, custom_services = true, create = true, max_instance = 32, on_create = instance_created, on_delete = instance_deleted)]
+pub struct ConnectionConfigurationClass {
+    engine: Arc<dyn OriginatorEngine>,
+    storage: Option<Arc<dyn NonVolatileStorage>>,
+    factory: InstanceFactory,
+    pub instances: RwLock<HashMap<u16, Arc<dyn CipInstance>>>,
+}
+
+#[cip_object_impl]
+impl ConnectionConfigurationClass {
+    pub const REVISION: u16 = 1;
+    pub const MAX_INSTANCES: u16 = 32;
+
+    /// Creates the class with the configurations saved in `storage`, if any.
+    pub fn new(
+        engine: Arc<dyn OriginatorEngine>,
+        storage: Option<Arc<dyn NonVolatileStorage>>,
+    ) -> Arc<Self> {
+        let factory_engine = engine.clone();
+        let class = Arc::new(Self {
+            engine,
+            storage,
+            factory: Box::new(move |instance_id, req| {
+                let config = ConnectionConfig::decode(req)?;
+                config.validate()?;
+                Ok(Arc::new(ConnectionConfigurationInstance::new(
+                    instance_id,
+                    config,
+                    factory_engine.clone(),
+                )))
+            }),
+            instances: RwLock::new(HashMap::new()),
+        });
+
+        if class.storage.is_some() && class.restore().is_err() {
+            log::error!("Failed to restore connection configurations");
+        }
+
+        class
+    }
+
+    /// Returns the configurations of all instances, ordered by instance id.
+    pub fn configurations(&self) -> Result<Vec<(u16, ConnectionConfig)>, CipError> {
+        let guard = self.instances.read().map_err(|_| {
+            log::error!("Failed to get read guard for Connection Configuration instances");
+            CipError::GeneralError
+        })?;
+        Ok(Self::configurations_of(&guard))
+    }
+
+    fn configurations_of(
+        instances: &HashMap<u16, Arc<dyn CipInstance>>,
+    ) -> Vec<(u16, ConnectionConfig)> {
+        let mut configurations = instances
+            .values()
+            .filter_map(|instance| {
+                instance
+                    .clone()
+                    .as_any_arc()
+                    .downcast::<ConnectionConfigurationInstance>()
+                    .ok()
+            })
+            .map(|instance| (instance.id, instance.config()))
+            .collect::<Vec<_>>();
+        configurations.sort_by_key(|(id, _)| *id);
+        configurations
+    }
+
+    /// Creates an instance like the Create service and hands it to the originator engine.
+    pub fn create(&self, config: &ConnectionConfig) -> Result<u16, CipError> {
+        let mut data = BytesMut::new();
+        config.encode(&mut data)?;
+        self.create_instance(&mut data.freeze())
+    }
+
+    /// Hands a created instance to the engine once the instances are unlocked. A rejected
+    /// configuration removes the instance again.
+    fn instance_created(&self, instance_id: u16) -> CipResult {
+        let instance = self
+            .get_instance(instance_id)?
+            .as_any_arc()
+            .downcast::<ConnectionConfigurationInstance>()
+            .map_err(|_| CipError::GeneralError)?;
+        self.engine.configure(instance_id, &instance.config())
+    }
+
+    fn instance_deleted(&self, instance_id: u16) {
+        self.engine.remove(instance_id);
+    }
+
+    /// Persists all instances so they can be brought back with [`Self::restore`].
+    pub fn save(&self) -> CipResult {
+        let storage = self
+            .storage
+            .as_ref()
+            .ok_or(CipError::StoreOperationFailure)?;
+
+        let configurations = self.configurations()?;
+        let mut data = BytesMut::new();
+        data.put_u16_le(configurations.len() as u16);
+        for (instance_id, config) in &configurations {
+            data.put_u16_le(*instance_id);
+            config.encode(&mut data)?;
+        }
+
+        storage.store(STORAGE_KEY, &data).map_err(|err| {
+            log::error!("Failed to store connection configurations: {}", err);
+            CipError::StoreOperationFailure
+        })
+    }
+
+    /// Replaces all instances with the last saved configurations. The saved configurations
+    /// are validated before the engine is told about them. When the engine rejects one, it
+    /// gets the current configurations back and the instances are left unchanged.
+    pub fn restore(&self) -> CipResult {
+        let storage = self
+            .storage
+            .as_ref()
+            .ok_or(CipError::StoreOperationFailure)?;
+
+        let data = storage.load(STORAGE_KEY).map_err(|err| {
+            log::error!("Failed to load connection configurations: {}", err);
+            CipError::StoreOperationFailure
+        })?;
+
+        let mut saved: Vec<(u16, ConnectionConfig)> = Vec::new();
+        if let Some(data) = data {
+            let mut data = Bytes::from(data);
+            let count = u16::decode(&mut data)?;
+            for _ in 0..count {
+                let instance_id = u16::decode(&mut data)?;
+                let config = ConnectionConfig::decode(&mut data)?;
+                config.validate()?;
+                if !(1..=Self::MAX_INSTANCES).contains(&instance_id)
+                    || saved.iter().any(|(id, _)| *id == instance_id)
+                {
+                    log::error!("Saved connection configuration {} is invalid", instance_id);
+                    return Err(CipError::StoreOperationFailure);
+                }
+                saved.push((instance_id, config));
+            }
+        }
+
+        let mut guard = self.instances.write().map_err(|_| {
+            log::error!("Failed to get write guard for Connection Configuration instances");
+            CipError::GeneralError
+        })?;
+        let current = Self::configurations_of(&guard);
+
+        for (instance_id, _) in &current {
+            self.engine.remove(*instance_id);
+        }
+        for (index, (instance_id, config)) in saved.iter().enumerate() {
+            if let Err(err) = self.engine.configure(*instance_id, config) {
+                log::error!(
+                    "Originator engine rejected connection configuration {}",
+                    instance_id
+                );
+                for (instance_id, _) in &saved[..index] {
+                    self.engine.remove(*instance_id);
+                }
+                for (instance_id, config) in &current {
+                    if self.engine.configure(*instance_id, config).is_err() {
+                        log::error!(
+                            "Failed to reconfigure connection configuration {}",
+                            instance_id
+                        );
+                    }
+                }
+                return Err(err);
+            }
+        }
+
+        *guard = saved
+            .into_iter()
+            .map(|(instance_id, config)| {
+                let instance =
+                    ConnectionConfigurationInstance::new(instance_id, config, self.engine.clone());
+                (instance_id, Arc::new(instance) as Arc<dyn CipInstance>)
+            })
+            .collect();
+        Ok(())
+    }
+
+    fn instance_count(&self) -> Result<u16, CipError> {
+        let guard = self.instances.read().map_err(|_| {
+            log::error!("Failed to get read guard for Connection Configuration instances");
+            CipError::GeneralError
+        })?;
+        Ok(guard.len() as u16)
+    }
+
+    #[service(0x0E)]
+    pub fn get_attribute_single(&self, req: &mut Bytes, resp: &mut BytesMut) -> CipResult {
+        let attribute_id = u16::decode(req)?;
+
+        match attribute_id {
+            1 => Self::REVISION.encode(resp)?,
+            2 => Self::MAX_INSTANCES.encode(resp)?,
+            3 => self.instance_count()?.encode(resp)?,
+            _ => return Err(CipError::AttributeNotSupported),
+        }
+
+        Ok(())
+    }
+
+    #[service(0x15)]
+    pub fn restore_service(&self, _req: &mut Bytes, _resp: &mut BytesMut) -> CipResult {
+        self.restore()
+    }
+
+    #[service(0x16)]
+    pub fn save_service(&self, _req: &mut Bytes, _resp: &mut BytesMut) -> CipResult {
+        self.save()
+    }
+}
+
+/// Instance holding one [`ConnectionConfig`]. Of the Connection Configuration instance
+/// attributes, net connection parameters (5), connection path (6) and connection name (8)
+/// are supported; connection status, flags, target device id and configuration data are
+/// left to the originator engine.
+#[derive(CipInstance)]
+#[cip(custom_services = true)]
+pub struct ConnectionConfigurationInstance {
+    id: u16,
+    class_id: ClassCode,
+    config: RwLock<ConnectionConfig>,
+    /// Held across a configuration change, from storing it to the engine's answer.
+    update: Mutex<()>,
+    engine: Arc<dyn OriginatorEngine>,
+}
+
+#[cip_object_impl]
+impl ConnectionConfigurationInstance {
+    pub fn new(id: u16, config: ConnectionConfig, engine: Arc<dyn OriginatorEngine>) -> Self {
+        Self {
+            id,
+            class_id: ClassCode::ConnectionConfiguration,
+            config: RwLock::new(config),
+            update: Mutex::new(()),
+            engine,
+        }
+    }
+
+    pub fn config(&self) -> ConnectionConfig {
+        match self.config.read() {
+            Ok(config) => config.clone(),
+            Err(poisoned) => {
+                log::error!("Failed to get read guard for connection configuration");
+                poisoned.into_inner().clone()
+            }
+        }
+    }
+
+    /// Validates and applies the configuration, then hands it to the originator engine. The
+    /// previous configuration is put back when the engine rejects it. Changes are applied
+    /// one at a time, the engine may read the configuration meanwhile.
+    pub fn set_config(&self, config: ConnectionConfig) -> CipResult {
+        config.validate()?;
+
+        let _update = self.update.lock().map_err(|_| {
+            log::error!("Failed to lock connection configuration update");
+            CipError::GeneralError
+        })?;
+        let previous = self.replace_config(config.clone())?;
+        if let Err(err) = self.engine.configure(self.id, &config) {
+            self.replace_config(previous)?;
+            return Err(err);
+        }
+        Ok(())
+    }
+
+    fn replace_config(&self, config: ConnectionConfig) -> Result<ConnectionConfig, CipError> {
+        let mut guard = self.config.write().map_err(|_| {
+            log::error!("Failed to get write guard for connection configuration");
+            CipError::GeneralError
+        })?;
+        Ok(std::mem::replace(&mut *guard, config))
+    }
+
+    #[service(0x0E)]
+    pub fn get_attribute_single(&self, req: &mut Bytes, resp: &mut BytesMut) -> CipResult {
+        let attribute_id = u16::decode(req)?;
+        let config = self.config();
+
+        match attribute_id {
+            5 => config.net_parameters.encode(resp)?,
+            6 => config.connection_path.encode(resp)?,
+            8 => config.connection_name.encode(resp)?,
+            _ => return Err(CipError::AttributeNotSupported),
+        }
+
+        Ok(())
+    }
+
+    #[service(0x10)]
+    pub fn set_attribute_single(&self, req: &mut Bytes, _resp: &mut BytesMut) -> CipResult {
+        let attribute_id = u16::decode(req)?;
+        let mut config = self.config();
+
+        match attribute_id {
+            5 => config.net_parameters = NetConnectionParameters::decode(req)?,
+            6 => config.connection_path = SizedEPath::decode(req)?,
+            8 => config.connection_name = String2::decode(req)?,
+            _ => return Err(CipError::AttributeNotSupported),
+        }
+
+        self.set_config(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cip::data_types::epath::{LogicalSegment, PaddedEPath, Segment};
+    use crate::common::storage::MemoryStorage;
+
+    #[derive(Default)]
+    struct RecordingEngine {
+        configured: Mutex<Vec<u16>>,
+        removed: Mutex<Vec<u16>>,
+        rejected: Mutex<Option<u16>>,
+    }
+
+    impl OriginatorEngine for RecordingEngine {
+        fn configure(&self, instance_id: u16, _config: &ConnectionConfig) -> CipResult {
+            if *self.rejected.lock().unwrap() == Some(instance_id) {
+                return Err(CipError::ResourceUnavailable);
+            }
+
+            self.configured.lock().unwrap().push(instance_id);
+            Ok(())
+        }
+
+        fn remove(&self, instance_id: u16) {
+            self.removed.lock().unwrap().push(instance_id);
+        }
+    }
+
+    fn config() -> ConnectionConfig {
+        ConnectionConfig {
+            net_parameters: NetConnectionParameters {
+                timeout_multiplier: 1,
+                o_to_t_rpi_us: 10_000,
+                o_to_t_network_parameters: 0x4802,
+                t_to_o_rpi_us: 10_000,
+                t_to_o_network_parameters: 0x4802,
+            },
+            connection_path: SizedEPath::new(PaddedEPath::new(vec![
+                Segment::Logical(LogicalSegment::class_id(0x04)),
+                Segment::Logical(LogicalSegment::instance_id(0x64)),
+            ])),
+            connection_name: String2::new("Remote IO"),
+        }
+    }
+
+    #[test]
+    fn create_and_delete_notify_engine() {
+        let engine = Arc::new(RecordingEngine::default());
+        let mut class = ConnectionConfigurationClass::new(engine.clone(), None);
+        let class = Arc::get_mut(&mut class).expect("Failed to get class");
+
+        let mut req = BytesMut::new();
+        config().encode(&mut req).expect("Failed to encode config");
+        let mut resp = BytesMut::new();
+        class
+            .execute_service(0x08, &mut req.freeze(), &mut resp)
+            .expect("Failed to create instance");
+        assert_eq!(resp.as_ref(), &[0x01, 0x00]);
+        assert_eq!(
+            class.configurations().expect("Failed to list"),
+            vec![(1, config())]
+        );
+
+        class.delete_instance(1).expect("Failed to delete instance");
+        assert!(matches!(
+            class.get_instance(1),
+            Err(CipError::ObjectDoesNotExist)
+        ));
+        assert_eq!(*engine.configured.lock().unwrap(), vec![1]);
+        assert_eq!(*engine.removed.lock().unwrap(), vec![1]);
+    }
+
+    #[test]
+    fn create_with_zero_rpi_returns_error() {
+        let engine = Arc::new(RecordingEngine::default());
+        let class = ConnectionConfigurationClass::new(engine.clone(), None);
+        let mut config = config();
+        config.net_parameters.o_to_t_rpi_us = 0;
+
+        let result = class.create(&config);
+
+        assert!(matches!(result, Err(CipError::InvalidAttributeValue)));
+        assert!(engine.configured.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn create_rejected_by_engine_removes_instance() {
+        let engine = Arc::new(RecordingEngine::default());
+        let class = ConnectionConfigurationClass::new(engine.clone(), None);
+        *engine.rejected.lock().unwrap() = Some(1);
+
+        let rejected = class.create(&config());
+        *engine.rejected.lock().unwrap() = None;
+        let created = class.create(&config());
+
+        assert!(matches!(rejected, Err(CipError::ResourceUnavailable)));
+        assert_eq!(created.ok(), Some(1));
+        assert_eq!(
+            class.configurations().expect("Failed to list"),
+            vec![(1, config())]
+        );
+    }
+
+    #[test]
+    fn set_config_rejected_by_engine_keeps_previous_config() {
+        let engine = Arc::new(RecordingEngine::default());
+        let instance = ConnectionConfigurationInstance::new(1, config(), engine.clone());
+        *engine.rejected.lock().unwrap() = Some(1);
+        let mut renamed = config();
+        renamed.connection_name = String2::new("Renamed");
+
+        let result = instance.set_config(renamed);
+
+        assert!(matches!(result, Err(CipError::ResourceUnavailable)));
+        assert_eq!(instance.config(), config());
+    }
+
+    #[test]
+    fn engine_can_read_configurations_while_configuring() {
+        /// Engine reading the instances of its class, which are unlocked by then.
+        #[derive(Default)]
+        struct ListingEngine {
+            class: std::sync::OnceLock<std::sync::Weak<ConnectionConfigurationClass>>,
+            listed: Mutex<Vec<usize>>,
+        }
+
+        impl OriginatorEngine for ListingEngine {
+            fn configure(&self, _instance_id: u16, _config: &ConnectionConfig) -> CipResult {
+                let class = self.class.get().and_then(|class| class.upgrade());
+                if let Some(class) = class {
+                    let count = class.configurations()?.len();
+                    self.listed.lock().unwrap().push(count);
+                }
+                Ok(())
+            }
+
+            fn remove(&self, _instance_id: u16) {}
+        }
+
+        let engine = Arc::new(ListingEngine::default());
+        let class = ConnectionConfigurationClass::new(engine.clone(), None);
+        let _ = engine.class.set(Arc::downgrade(&class));
+
+        class.create(&config()).expect("Failed to create instance");
+        let instance = class
+            .get_instance(1)
+            .expect("Failed to get instance")
+            .as_any_arc()
+            .downcast::<ConnectionConfigurationInstance>()
+            .expect("Failed to downcast");
+        instance
+            .set_config(config())
+            .expect("Failed to set configuration");
+
+        assert_eq!(*engine.listed.lock().unwrap(), vec![1, 1]);
+    }
+
+    #[test]
+    fn instance_attributes_follow_connection_configuration_numbering() {
+        let instance = ConnectionConfigurationInstance::new(1, config(), Arc::new(NoOriginator));
+
+        let mut resp = BytesMut::new();
+        instance
+            .execute_shared(0x0E, &mut Bytes::from_static(&[0x05, 0x00]), &mut resp)
+            .expect("Failed to get net connection parameters");
+        assert_eq!(
+            resp.as_ref(),
+            &[
+                0x01, // Connection timeout multiplier
+                0x10, 0x27, 0x00, 0x00, 0x02, 0x48, // O->T RPI and parameters
+                0x10, 0x27, 0x00, 0x00, 0x02, 0x48, // T->O RPI and parameters
+            ]
+        );
+
+        let mut req = BytesMut::new();
+        req.put_u16_le(8);
+        String2::<MAX_CONNECTION_NAME_LEN>::new("Drive")
+            .encode(&mut req)
+            .expect("Failed to encode name");
+        instance
+            .execute_shared(0x10, &mut req.freeze(), &mut BytesMut::new())
+            .expect("Failed to set connection name");
+        assert_eq!(instance.config().connection_name.value(), "Drive");
+        assert!(matches!(
+            instance.execute_shared(0x0E, &mut Bytes::from_static(&[0x01, 0x00]), &mut resp),
+            Err(CipError::AttributeNotSupported)
+        ));
+    }
+
+    #[test]
+    fn save_and_restore_round_trip_success() {
+        let storage = Arc::new(MemoryStorage::new());
+        let class =
+            ConnectionConfigurationClass::new(Arc::new(NoOriginator), Some(storage.clone()));
+        class.create(&config()).expect("Failed to create instance");
+        class.save().expect("Failed to save");
+
+        class.create(&config()).expect("Failed to create instance");
+        class.restore().expect("Failed to restore");
+        assert_eq!(
+            class.configurations().expect("Failed to list"),
+            vec![(1, config())]
+        );
+
+        let restored = ConnectionConfigurationClass::new(Arc::new(NoOriginator), Some(storage));
+        assert_eq!(
+            restored.configurations().expect("Failed to list"),
+            vec![(1, config())]
+        );
+    }
+
+    #[test]
+    fn restore_rejected_by_engine_keeps_instances() {
+        let storage = Arc::new(MemoryStorage::new());
+        let engine = Arc::new(RecordingEngine::default());
+        let class = ConnectionConfigurationClass::new(engine.clone(), Some(storage));
+        class.create(&config()).expect("Failed to create instance");
+        class.create(&config()).expect("Failed to create instance");
+        class.save().expect("Failed to save");
+        class.delete_instance(2).expect("Failed to delete instance");
+        engine.configured.lock().unwrap().clear();
+        engine.removed.lock().unwrap().clear();
+        *engine.rejected.lock().unwrap() = Some(2);
+
+        let result = class.restore();
+
+        assert!(matches!(result, Err(CipError::ResourceUnavailable)));
+        assert_eq!(
+            class.configurations().expect("Failed to list"),
+            vec![(1, config())]
+        );
+        // Instance 1 is handed back to the engine after the saved instance 1 is withdrawn
+        assert_eq!(*engine.configured.lock().unwrap(), vec![1, 1]);
+        assert_eq!(*engine.removed.lock().unwrap(), vec![1, 1]);
+    }
+}
@@ -0,0 +1,482 @@
+mod connection_parameters;
+mod forward_open;
+mod unconnected_send;
+
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{
+        Arc, RwLock,
+        atomic::{AtomicU16, AtomicU32, Ordering},
+    },
+    time::{Duration, Instant},
+};
+
+use bytes::{Buf, BufMut};
+use cip_macros::{CipClass, CipInstance, cip_object_impl};
+
+use super::{
+    ClassCode,
+    common::error::CipError,
+    common::object::{CipClass, CipInstance, CipObject, CipResult},
+    data_types::epath::{LogicalSegment, NetworkSegment, PaddedEPath, PortSegment, Segment},
+};
+use crate::common::binary::{BinaryError, ToBytes};
+pub use connection_parameters::{
+    MAX_CONNECTION_SIZE, MAX_LARGE_CONNECTION_SIZE, NetworkConnectionParameters,
+};
+pub use forward_open::{
+    CONNECTION_IN_USE, CONNECTION_NOT_FOUND, ConnectionError, ForwardCloseRequest,
+    ForwardOpenReply, ForwardOpenRequest, INVALID_CONNECTION_SIZE,
+    INVALID_SEGMENT_IN_CONNECTION_PATH, TRANSPORT_CLASS_NOT_SUPPORTED,
+};
+pub use unconnected_send::{
+    BACKPLANE_PORT, BackplaneRouter, MessageRequest, NoBackplane, PORT_NOT_AVAILABLE, RoutingError,
+    UNCONNECTED_REQUEST_TIMED_OUT, UnconnectedSendRequest,
+};
+
+/// UINT event counter that can be incremented through a shared reference.
+/// Wraps around on overflow as required for the Connection Manager statistics.
+#[derive(Debug, Default)]
+pub struct EventCounter(AtomicU16);
+
+impl EventCounter {
+    pub fn increment(&self) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+
+    pub fn value(&self) -> u16 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+impl ToBytes for EventCounter {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        self.value().encode(buffer)
+    }
+
+    fn encoded_len(&self) -> usize {
+        2
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OpenRejectReason {
+    Format,
+    Resource,
+    Other,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CloseRejectReason {
+    Format,
+    Other,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ConnectionKey {
+    pub connection_serial_number: u16,
+    pub originator_vendor_id: u16,
+    pub originator_serial_number: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConnectionInfo {
+    pub key: ConnectionKey,
+    pub o_to_t_connection_id: u32,
+    pub t_to_o_connection_id: u32,
+    pub o_to_t_rpi_us: u32,
+    pub t_to_o_rpi_us: u32,
+    pub o_to_t_api_us: u32,
+    pub t_to_o_api_us: u32,
+    pub transport_class: u8,
+    /// Production inhibit time from the connection path, in microseconds.
+    pub production_inhibit_us: Option<u32>,
+    /// Configuration data of the connection path, for the configuration assembly.
+    pub configuration_data: Option<Vec<u8>>,
+    /// Time without data from the originator after which the connection times out.
+    pub connection_timeout: Duration,
+    pub peer: SocketAddr,
+}
+
+/// Open connection with the time it last received data.
+#[derive(Debug)]
+struct ActiveConnection {
+    info: ConnectionInfo,
+    last_received: Instant,
+}
+
+/// Connection path of an open request split into the route to the target, the application
+/// path and the connection parameters carried by network and data segments.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConnectionPath {
+    pub route: Vec<PortSegment>,
+    pub application_path: Vec<LogicalSegment>,
+    /// Production inhibit time in microseconds; millisecond segments are converted.
+    pub production_inhibit_us: Option<u32>,
+    /// Simple data segment content, written to the configuration assembly.
+    pub configuration_data: Option<Vec<u8>>,
+}
+
+impl TryFrom<&PaddedEPath> for ConnectionPath {
+    type Error = CipError;
+
+    /// Port segments must precede the application path, production inhibit time and
+    /// configuration data may appear once. Safety segments are rejected.
+    fn try_from(path: &PaddedEPath) -> Result<Self, Self::Error> {
+        let mut connection_path = Self::default();
+
+        for segment in path.segments() {
+            match segment {
+                Segment::Port(port) if connection_path.application_path.is_empty() => {
+                    connection_path.route.push(*port);
+                }
+                Segment::Port(_) => return Err(CipError::PathSegmentError),
+                Segment::Logical(logical) => connection_path.application_path.push(*logical),
+                Segment::Network(NetworkSegment::Safety(_)) => {
+                    return Err(CipError::PathSegmentError);
+                }
+                Segment::Network(network) => match network.production_inhibit_us() {
+                    Some(_) if connection_path.production_inhibit_us.is_some() => {
+                        return Err(CipError::PathSegmentError);
+                    }
+                    Some(us) => connection_path.production_inhibit_us = Some(us),
+                    None => log::debug!("Ignoring network segment: {:?}", network),
+                },
+                Segment::Data(_) if connection_path.configuration_data.is_some() => {
+                    return Err(CipError::PathSegmentError);
+                }
+                Segment::Data(data) => {
+                    connection_path.configuration_data = Some(data.data().to_vec());
+                }
+            }
+        }
+
+        if connection_path.application_path.is_empty() {
+            return Err(CipError::PathSegmentError);
+        }
+
+        Ok(connection_path)
+    }
+}
+
+#[derive(CipClass)]
+#[cip(id = ClassCode::ConnectionManager, name = "Connection Manager", singleton = true, custom_services = true)]
+pub struct ConnectionManagerClass {
+    pub instance: RwLock<Arc<ConnectionManagerInstance>>,
+}
+
+#[cip_object_impl]
+impl ConnectionManagerClass {
+    pub fn new(instance: Arc<ConnectionManagerInstance>) -> Arc<Self> {
+        Arc::new(Self {
+            instance: RwLock::new(instance),
+        })
+    }
+
+    pub fn with_default_instance() -> Arc<Self> {
+        Self::new(Arc::new(ConnectionManagerInstance::new()))
+    }
+}
+
+#[derive(CipInstance)]
+#[cip(custom_services = true)]
+pub struct ConnectionManagerInstance {
+    id: u16,
+    class_id: ClassCode,
+
+    #[attribute(id = 1, access = "get")]
+    open_requests: EventCounter,
+
+    #[attribute(id = 2, access = "get")]
+    open_format_rejects: EventCounter,
+
+    #[attribute(id = 3, access = "get")]
+    open_resource_rejects: EventCounter,
+
+    #[attribute(id = 4, access = "get")]
+    open_other_rejects: EventCounter,
+
+    #[attribute(id = 5, access = "get")]
+    close_requests: EventCounter,
+
+    #[attribute(id = 6, access = "get")]
+    close_format_requests: EventCounter,
+
+    #[attribute(id = 7, access = "get")]
+    close_other_requests: EventCounter,
+
+    #[attribute(id = 8, access = "get")]
+    connection_timeouts: EventCounter,
+
+    connections: RwLock<HashMap<ConnectionKey, ActiveConnection>>,
+    next_connection_id: AtomicU32,
+    router: Arc<dyn BackplaneRouter>,
+}
+
+/// The backplane router is left out, routers do not have to implement Debug.
+impl std::fmt::Debug for ConnectionManagerInstance {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ConnectionManagerInstance")
+            .field("id", &self.id)
+            .field("class_id", &self.class_id)
+            .field("open_requests", &self.open_requests)
+            .field("open_format_rejects", &self.open_format_rejects)
+            .field("open_resource_rejects", &self.open_resource_rejects)
+            .field("open_other_rejects", &self.open_other_rejects)
+            .field("close_requests", &self.close_requests)
+            .field("close_format_requests", &self.close_format_requests)
+            .field("close_other_requests", &self.close_other_requests)
+            .field("connection_timeouts", &self.connection_timeouts)
+            .field("connections", &self.connections)
+            .field("next_connection_id", &self.next_connection_id)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cip_object_impl]
+impl ConnectionManagerInstance {
+    pub fn new() -> Self {
+        Self::with_router(Arc::new(NoBackplane))
+    }
+
+    /// Routes Unconnected_Send requests leaving the device through `router`.
+    pub fn with_router(router: Arc<dyn BackplaneRouter>) -> Self {
+        Self {
+            id: 1,
+            class_id: ClassCode::ConnectionManager,
+            open_requests: EventCounter::default(),
+            open_format_rejects: EventCounter::default(),
+            open_resource_rejects: EventCounter::default(),
+            open_other_rejects: EventCounter::default(),
+            close_requests: EventCounter::default(),
+            close_format_requests: EventCounter::default(),
+            close_other_requests: EventCounter::default(),
+            connection_timeouts: EventCounter::default(),
+            connections: RwLock::new(HashMap::new()),
+            next_connection_id: AtomicU32::new(0x1000_0001),
+            router,
+        }
+    }
+
+    /// Parses the connection path of an open request, recording a format reject when it is
+    /// invalid.
+    pub fn parse_connection_path(&self, path: &PaddedEPath) -> Result<ConnectionPath, CipError> {
+        ConnectionPath::try_from(path).inspect_err(|error| {
+            log::warn!("Invalid connection path {}: {:?}", path, error);
+            self.open_rejected(OpenRejectReason::Format);
+        })
+    }
+
+    /// Records an accepted open request and adds the connection to the active list.
+    pub fn connection_opened(&self, info: ConnectionInfo) {
+        self.open_requests.increment();
+        log::info!("Connection opened: {:?}", info);
+
+        match self.connections.write() {
+            Ok(mut connections) => {
+                connections.insert(info.key, ActiveConnection::new(info));
+            }
+            Err(_) => log::error!("Failed to get write guard for active connections"),
+        }
+    }
+
+    /// Records a rejected open request.
+    pub fn open_rejected(&self, reason: OpenRejectReason) {
+        self.open_requests.increment();
+        match reason {
+            OpenRejectReason::Format => self.open_format_rejects.increment(),
+            OpenRejectReason::Resource => self.open_resource_rejects.increment(),
+            OpenRejectReason::Other => self.open_other_rejects.increment(),
+        }
+    }
+
+    /// Records a close request and removes the connection, returning it if it was active.
+    pub fn connection_closed(&self, key: &ConnectionKey) -> Option<ConnectionInfo> {
+        self.close_requests.increment();
+        self.remove_connection(key)
+    }
+
+    /// Records a rejected close request.
+    pub fn close_rejected(&self, reason: CloseRejectReason) {
+        self.close_requests.increment();
+        match reason {
+            CloseRejectReason::Format => self.close_format_requests.increment(),
+            CloseRejectReason::Other => self.close_other_requests.increment(),
+        }
+    }
+
+    /// Records an inactivity timeout and removes the connection, returning it if it was active.
+    pub fn connection_timed_out(&self, key: &ConnectionKey) -> Option<ConnectionInfo> {
+        self.connection_timeouts.increment();
+        self.remove_connection(key)
+    }
+
+    pub fn active_connections(&self) -> Vec<ConnectionInfo> {
+        match self.connections.read() {
+            Ok(connections) => connections
+                .values()
+                .map(|connection| connection.info.clone())
+                .collect(),
+            Err(_) => {
+                log::error!("Failed to get read guard for active connections");
+                Vec::new()
+            }
+        }
+    }
+
+    pub fn statistics(&self) -> ConnectionStatistics {
+        ConnectionStatistics {
+            open_requests: self.open_requests.value(),
+            open_format_rejects: self.open_format_rejects.value(),
+            open_resource_rejects: self.open_resource_rejects.value(),
+            open_other_rejects: self.open_other_rejects.value(),
+            close_requests: self.close_requests.value(),
+            close_format_requests: self.close_format_requests.value(),
+            close_other_requests: self.close_other_requests.value(),
+            connection_timeouts: self.connection_timeouts.value(),
+        }
+    }
+
+    fn remove_connection(&self, key: &ConnectionKey) -> Option<ConnectionInfo> {
+        match self.connections.write() {
+            Ok(mut connections) => connections.remove(key).map(|connection| connection.info),
+            Err(_) => {
+                log::error!("Failed to get write guard for active connections");
+                None
+            }
+        }
+    }
+}
+
+impl Default for ConnectionManagerInstance {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ConnectionStatistics {
+    pub open_requests: u16,
+    pub open_format_rejects: u16,
+    pub open_resource_rejects: u16,
+    pub open_other_rejects: u16,
+    pub close_requests: u16,
+    pub close_format_requests: u16,
+    pub close_other_requests: u16,
+    pub connection_timeouts: u16,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cip::data_types::epath::EPathBuilder;
+    use bytes::{Bytes, BytesMut};
+    use std::net::{Ipv4Addr, SocketAddrV4};
+
+    fn connection(serial: u16) -> ConnectionInfo {
+        ConnectionInfo {
+            key: ConnectionKey {
+                connection_serial_number: serial,
+                originator_vendor_id: 0x0001,
+                originator_serial_number: 0x12345678,
+            },
+            o_to_t_connection_id: 0x1000 + serial as u32,
+            t_to_o_connection_id: 0x2000 + serial as u32,
+            o_to_t_rpi_us: 10_000,
+            t_to_o_rpi_us: 10_000,
+            o_to_t_api_us: 10_000,
+            t_to_o_api_us: 10_000,
+            transport_class: 1,
+            production_inhibit_us: None,
+            configuration_data: None,
+            connection_timeout: Duration::from_millis(40),
+            peer: SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 2222)),
+        }
+    }
+
+    #[test]
+    fn connection_events_update_statistics_and_active_list() {
+        let instance = ConnectionManagerInstance::new();
+
+        instance.connection_opened(connection(1));
+        instance.connection_opened(connection(2));
+        instance.open_rejected(OpenRejectReason::Resource);
+        instance.close_rejected(CloseRejectReason::Format);
+        let closed = instance.connection_closed(&connection(1).key);
+        let timed_out = instance.connection_timed_out(&connection(2).key);
+
+        assert_eq!(closed, Some(connection(1)));
+        assert_eq!(timed_out, Some(connection(2)));
+        assert!(instance.active_connections().is_empty());
+        assert_eq!(
+            instance.statistics(),
+            ConnectionStatistics {
+                open_requests: 3,
+                open_resource_rejects: 1,
+                close_requests: 2,
+                close_format_requests: 1,
+                connection_timeouts: 1,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn get_attribute_single_returns_counter_value() {
+        let mut instance = ConnectionManagerInstance::new();
+        instance.connection_opened(connection(7));
+
+        let mut req = Bytes::from_static(&[0x01, 0x00]);
+        let mut resp = BytesMut::new();
+        instance
+            .execute_service(0x0E, &mut req, &mut resp)
+            .expect("Failed to get open requests");
+
+        assert_eq!(resp.as_ref(), &[0x01, 0x00]);
+        assert_eq!(instance.active_connections(), vec![connection(7)]);
+    }
+
+    #[test]
+    fn parse_connection_path_extracts_inhibit_time_and_configuration_data() {
+        let instance = ConnectionManagerInstance::new();
+        let path = EPathBuilder::new()
+            .port(1, 0)
+            .production_inhibit_ms(5)
+            .class(0x04)
+            .instance(1)
+            .connection_point(100)
+            .connection_point(150)
+            .simple_data(vec![0x01, 0x02, 0x03, 0x04])
+            .build();
+
+        let connection_path = instance
+            .parse_connection_path(&path)
+            .expect("Failed to parse connection path");
+
+        assert_eq!(connection_path.route.len(), 1);
+        assert_eq!(connection_path.application_path.len(), 4);
+        assert_eq!(connection_path.production_inhibit_us, Some(5_000));
+        assert_eq!(
+            connection_path.configuration_data,
+            Some(vec![0x01, 0x02, 0x03, 0x04])
+        );
+        assert_eq!(instance.statistics(), ConnectionStatistics::default());
+    }
+
+    #[test]
+    fn parse_connection_path_with_two_inhibit_times_records_format_reject() {
+        let instance = ConnectionManagerInstance::new();
+        let path = EPathBuilder::new()
+            .production_inhibit_ms(5)
+            .production_inhibit_us(2_500)
+            .class(0x04)
+            .instance(1)
+            .build();
+
+        let result = instance.parse_connection_path(&path);
+
+        assert!(matches!(result, Err(CipError::PathSegmentError)));
+        assert_eq!(instance.statistics().open_format_rejects, 1);
+    }
+}
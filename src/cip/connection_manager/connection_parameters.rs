@@ -0,0 +1,107 @@
+use super::{ConnectionManagerInstance, OpenRejectReason};
+use crate::cip::common::error::CipError;
+
+/// Largest connection size of a Forward_Open, limited by its 9-bit size field.
+pub const MAX_CONNECTION_SIZE: u16 = 511;
+
+/// Largest connection size accepted through Large_Forward_Open.
+pub const MAX_LARGE_CONNECTION_SIZE: u16 = 4000;
+
+/// Network connection parameters of one direction of a connection. Forward_Open packs them
+/// in a WORD, Large_Forward_Open in a DWORD with a 16-bit connection size.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NetworkConnectionParameters {
+    /// Connection size in bytes.
+    pub connection_size: u16,
+    pub variable_size: bool,
+    pub priority: u8,
+    pub connection_type: u8,
+    pub redundant_owner: bool,
+    pub large: bool,
+}
+
+impl NetworkConnectionParameters {
+    pub fn from_forward_open(word: u16) -> Self {
+        Self {
+            connection_size: word & 0x01FF,
+            variable_size: word & (1 << 9) != 0,
+            priority: ((word >> 10) & 0b11) as u8,
+            connection_type: ((word >> 13) & 0b11) as u8,
+            redundant_owner: word & (1 << 15) != 0,
+            large: false,
+        }
+    }
+
+    pub fn from_large_forward_open(dword: u32) -> Self {
+        Self {
+            connection_size: (dword & 0xFFFF) as u16,
+            variable_size: dword & (1 << 25) != 0,
+            priority: ((dword >> 26) & 0b11) as u8,
+            connection_type: ((dword >> 29) & 0b11) as u8,
+            redundant_owner: dword & (1 << 31) != 0,
+            large: true,
+        }
+    }
+
+    pub fn max_connection_size(&self) -> u16 {
+        if self.large {
+            MAX_LARGE_CONNECTION_SIZE
+        } else {
+            MAX_CONNECTION_SIZE
+        }
+    }
+}
+
+impl ConnectionManagerInstance {
+    /// Checks the connection size requested in an open request, recording a resource reject
+    /// when it is larger than the adapter supports.
+    pub fn check_connection_size(
+        &self,
+        parameters: &NetworkConnectionParameters,
+    ) -> Result<(), CipError> {
+        if parameters.connection_size > parameters.max_connection_size() {
+            log::warn!(
+                "Connection size {} exceeds the maximum of {}",
+                parameters.connection_size,
+                parameters.max_connection_size()
+            );
+            self.open_rejected(OpenRejectReason::Resource);
+            return Err(CipError::ResourceUnavailable);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn large_forward_open_accepts_connection_size_up_to_4000() {
+        let instance = ConnectionManagerInstance::new();
+        // Point to point, high priority, variable size, 4000 bytes
+        let large = NetworkConnectionParameters::from_large_forward_open(0x4600_0FA0);
+        let too_large = NetworkConnectionParameters::from_large_forward_open(0x4600_0FA1);
+
+        assert_eq!(large.connection_size, 4000);
+        assert_eq!(large.connection_type, 2);
+        assert!(large.variable_size);
+        assert!(instance.check_connection_size(&large).is_ok());
+        assert!(matches!(
+            instance.check_connection_size(&too_large),
+            Err(CipError::ResourceUnavailable)
+        ));
+        assert_eq!(instance.statistics().open_resource_rejects, 1);
+    }
+
+    #[test]
+    fn forward_open_connection_size_uses_nine_bits() {
+        let parameters = NetworkConnectionParameters::from_forward_open(0x43F4);
+
+        assert_eq!(parameters.connection_size, 500);
+        assert_eq!(parameters.connection_type, 2);
+        assert!(!parameters.large);
+        assert_eq!(parameters.max_connection_size(), MAX_CONNECTION_SIZE);
+    }
+}
@@ -0,0 +1,815 @@
+use std::{
+    net::SocketAddr,
+    sync::atomic::Ordering,
+    time::{Duration, Instant},
+};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+use super::{
+    ActiveConnection, CloseRejectReason, ConnectionInfo, ConnectionKey, ConnectionManagerInstance,
+    NetworkConnectionParameters, OpenRejectReason, PORT_NOT_AVAILABLE,
+    unconnected_send::is_local_hop,
+};
+use crate::{
+    cip::{
+        common::error::CipError, data_types::epath::PaddedEPath, message_router::MessageResponse,
+    },
+    common::binary::{BinaryError, FromBytes, ToBytes},
+};
+
+/// Extended status of an open request for a connection that is already open.
+pub const CONNECTION_IN_USE: u16 = 0x0100;
+
+/// Extended status of an open request with a transport class the adapter does not serve.
+pub const TRANSPORT_CLASS_NOT_SUPPORTED: u16 = 0x0103;
+
+/// Extended status of a close request for a connection that is not open.
+pub const CONNECTION_NOT_FOUND: u16 = 0x0107;
+
+/// Extended status of an open request with a connection size the adapter does not support.
+pub const INVALID_CONNECTION_SIZE: u16 = 0x0109;
+
+/// Extended status of an open request with a segment the connection path does not allow.
+pub const INVALID_SEGMENT_IN_CONNECTION_PATH: u16 = 0x0315;
+
+impl FromBytes for ConnectionKey {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        if buffer.remaining() < 8 {
+            return Err(BinaryError::Truncated {
+                expected: 8,
+                actual: buffer.remaining(),
+            });
+        }
+
+        Ok(Self {
+            connection_serial_number: buffer.get_u16_le(),
+            originator_vendor_id: buffer.get_u16_le(),
+            originator_serial_number: buffer.get_u32_le(),
+        })
+    }
+}
+
+impl ToBytes for ConnectionKey {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < self.encoded_len() {
+            return Err(BinaryError::BufferTooSmall {
+                expected: self.encoded_len(),
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        buffer.put_u16_le(self.connection_serial_number);
+        buffer.put_u16_le(self.originator_vendor_id);
+        buffer.put_u32_le(self.originator_serial_number);
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        8
+    }
+}
+
+/// Request data of the Forward_Open (0x54) and Large_Forward_Open (0x5B) services.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForwardOpenRequest {
+    pub priority_time_tick: u8,
+    pub timeout_ticks: u8,
+    pub o_to_t_connection_id: u32,
+    pub t_to_o_connection_id: u32,
+    pub key: ConnectionKey,
+    pub timeout_multiplier: u8,
+    pub o_to_t_rpi_us: u32,
+    pub o_to_t_parameters: NetworkConnectionParameters,
+    pub t_to_o_rpi_us: u32,
+    pub t_to_o_parameters: NetworkConnectionParameters,
+    pub transport_type_trigger: u8,
+    pub connection_path: PaddedEPath,
+}
+
+impl ForwardOpenRequest {
+    /// Time without data from the originator after which the connection times out, the
+    /// O->T RPI times 4, 8, 16, ... 512 for multipliers 0 to 7.
+    pub fn connection_timeout(&self) -> Duration {
+        let multiplier = 4u64 << self.timeout_multiplier.min(7);
+        Duration::from_micros(self.o_to_t_rpi_us as u64 * multiplier)
+    }
+
+    /// Decodes a Large_Forward_Open request, its network connection parameters are DWORDs.
+    pub fn decode_large<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        Self::decode_with(buffer, true)
+    }
+
+    fn decode_with<T: Buf>(buffer: &mut T, large: bool) -> Result<Self, BinaryError> {
+        // Fixed fields up to and including the connection path size
+        let fixed_len = if large { 40 } else { 36 };
+        if buffer.remaining() < fixed_len {
+            return Err(BinaryError::Truncated {
+                expected: fixed_len,
+                actual: buffer.remaining(),
+            });
+        }
+        let parameters = |buffer: &mut T| {
+            if large {
+                NetworkConnectionParameters::from_large_forward_open(buffer.get_u32_le())
+            } else {
+                NetworkConnectionParameters::from_forward_open(buffer.get_u16_le())
+            }
+        };
+
+        let priority_time_tick = buffer.get_u8();
+        let timeout_ticks = buffer.get_u8();
+        let o_to_t_connection_id = buffer.get_u32_le();
+        let t_to_o_connection_id = buffer.get_u32_le();
+        let key = ConnectionKey::decode(buffer)?;
+        let timeout_multiplier = buffer.get_u8();
+        buffer.advance(3);
+        let o_to_t_rpi_us = buffer.get_u32_le();
+        let o_to_t_parameters = parameters(buffer);
+        let t_to_o_rpi_us = buffer.get_u32_le();
+        let t_to_o_parameters = parameters(buffer);
+        let transport_type_trigger = buffer.get_u8();
+        let path_len = buffer.get_u8() as usize * 2;
+        let connection_path = decode_path(buffer, path_len)?;
+
+        Ok(Self {
+            priority_time_tick,
+            timeout_ticks,
+            o_to_t_connection_id,
+            t_to_o_connection_id,
+            key,
+            timeout_multiplier,
+            o_to_t_rpi_us,
+            o_to_t_parameters,
+            t_to_o_rpi_us,
+            t_to_o_parameters,
+            transport_type_trigger,
+            connection_path,
+        })
+    }
+}
+
+impl FromBytes for ForwardOpenRequest {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        Self::decode_with(buffer, false)
+    }
+}
+
+/// Request data of the Forward_Close service (0x4E).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForwardCloseRequest {
+    pub priority_time_tick: u8,
+    pub timeout_ticks: u8,
+    pub key: ConnectionKey,
+    pub connection_path: PaddedEPath,
+}
+
+impl FromBytes for ForwardCloseRequest {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        if buffer.remaining() < 12 {
+            return Err(BinaryError::Truncated {
+                expected: 12,
+                actual: buffer.remaining(),
+            });
+        }
+
+        let priority_time_tick = buffer.get_u8();
+        let timeout_ticks = buffer.get_u8();
+        let key = ConnectionKey::decode(buffer)?;
+        let path_len = buffer.get_u8() as usize * 2;
+        buffer.advance(1);
+        let connection_path = decode_path(buffer, path_len)?;
+
+        Ok(Self {
+            priority_time_tick,
+            timeout_ticks,
+            key,
+            connection_path,
+        })
+    }
+}
+
+/// Successful Forward_Open reply, without application reply data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ForwardOpenReply {
+    pub o_to_t_connection_id: u32,
+    pub t_to_o_connection_id: u32,
+    pub key: ConnectionKey,
+    pub o_to_t_api_us: u32,
+    pub t_to_o_api_us: u32,
+}
+
+impl ToBytes for ForwardOpenReply {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < self.encoded_len() {
+            return Err(BinaryError::BufferTooSmall {
+                expected: self.encoded_len(),
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        buffer.put_u32_le(self.o_to_t_connection_id);
+        buffer.put_u32_le(self.t_to_o_connection_id);
+        self.key.encode(buffer)?;
+        buffer.put_u32_le(self.o_to_t_api_us);
+        buffer.put_u32_le(self.t_to_o_api_us);
+        // Application reply size and reserved byte
+        buffer.put_u8(0);
+        buffer.put_u8(0);
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        26
+    }
+}
+
+/// Connection Failure reply to an open or close request.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ConnectionError {
+    pub extended_status: u16,
+}
+
+impl ConnectionError {
+    /// Error reply to `service`, identifying the connection by `key`.
+    pub fn into_response(self, service: u8, key: &ConnectionKey) -> MessageResponse {
+        let mut data = BytesMut::with_capacity(10);
+        // Encoding into a growable buffer cannot fail
+        let _ = key.encode(&mut data);
+        // Remaining path size and reserved byte
+        data.put_u8(0);
+        data.put_u8(0);
+
+        MessageResponse {
+            extended_status: vec![self.extended_status],
+            data: data.freeze(),
+            ..MessageResponse::error(service, CipError::ConnectionFailure)
+        }
+    }
+}
+
+impl ConnectionManagerInstance {
+    /// Opens the connection of a Forward_Open request for `peer`. The target chooses the
+    /// O->T connection id, the T->O connection id of the originator is kept and the actual
+    /// packet intervals are the requested ones. Connection paths routed past this device
+    /// are rejected, connections are not forwarded. Only class 3 connections are served,
+    /// their data arrives through SendUnitData, the adapter has no I/O data path.
+    pub fn forward_open(
+        &self,
+        request: &ForwardOpenRequest,
+        peer: SocketAddr,
+    ) -> Result<ForwardOpenReply, ConnectionError> {
+        let transport_class = request.transport_type_trigger & 0x0F;
+        if transport_class != 3 {
+            log::warn!(
+                "Connection {:?} uses unsupported transport class {}",
+                request.key,
+                transport_class
+            );
+            self.open_rejected(OpenRejectReason::Other);
+            return Err(ConnectionError {
+                extended_status: TRANSPORT_CLASS_NOT_SUPPORTED,
+            });
+        }
+
+        self.check_connection_size(&request.o_to_t_parameters)
+            .and_then(|()| self.check_connection_size(&request.t_to_o_parameters))
+            .map_err(|_| ConnectionError {
+                extended_status: INVALID_CONNECTION_SIZE,
+            })?;
+
+        let connection_path = self
+            .parse_connection_path(&request.connection_path)
+            .map_err(|_| ConnectionError {
+                extended_status: INVALID_SEGMENT_IN_CONNECTION_PATH,
+            })?;
+        let slot = self.router.slot();
+        if !connection_path
+            .route
+            .iter()
+            .all(|hop| is_local_hop(hop, slot))
+        {
+            log::warn!("Connection {:?} is routed past this device", request.key);
+            self.open_rejected(OpenRejectReason::Other);
+            return Err(ConnectionError {
+                extended_status: PORT_NOT_AVAILABLE,
+            });
+        }
+
+        if self.is_open(&request.key) {
+            log::warn!("Connection {:?} is already open", request.key);
+            self.open_rejected(OpenRejectReason::Other);
+            return Err(ConnectionError {
+                extended_status: CONNECTION_IN_USE,
+            });
+        }
+
+        let info = ConnectionInfo {
+            key: request.key,
+            o_to_t_connection_id: self.next_connection_id.fetch_add(1, Ordering::Relaxed),
+            t_to_o_connection_id: request.t_to_o_connection_id,
+            o_to_t_rpi_us: request.o_to_t_rpi_us,
+            t_to_o_rpi_us: request.t_to_o_rpi_us,
+            o_to_t_api_us: request.o_to_t_rpi_us,
+            t_to_o_api_us: request.t_to_o_rpi_us,
+            transport_class,
+            production_inhibit_us: connection_path.production_inhibit_us,
+            configuration_data: connection_path.configuration_data,
+            connection_timeout: request.connection_timeout(),
+            peer,
+        };
+        let reply = ForwardOpenReply {
+            o_to_t_connection_id: info.o_to_t_connection_id,
+            t_to_o_connection_id: info.t_to_o_connection_id,
+            key: info.key,
+            o_to_t_api_us: info.o_to_t_api_us,
+            t_to_o_api_us: info.t_to_o_api_us,
+        };
+        self.connection_opened(info);
+        Ok(reply)
+    }
+
+    /// Closes the connection of a Forward_Close request, returning its key for the reply.
+    pub fn forward_close(
+        &self,
+        request: &ForwardCloseRequest,
+    ) -> Result<ConnectionKey, ConnectionError> {
+        if !self.is_open(&request.key) {
+            log::warn!("Connection {:?} to close is not open", request.key);
+            self.close_rejected(CloseRejectReason::Other);
+            return Err(ConnectionError {
+                extended_status: CONNECTION_NOT_FOUND,
+            });
+        }
+
+        self.connection_closed(&request.key);
+        Ok(request.key)
+    }
+
+    /// Executes a Forward_Open, Large_Forward_Open or Forward_Close request from `peer`,
+    /// `None` for other services.
+    pub fn execute_connection_service(
+        &self,
+        service: u8,
+        data: &Bytes,
+        peer: Option<SocketAddr>,
+    ) -> Option<MessageResponse> {
+        let response = match service {
+            0x54 | 0x5B => {
+                let decoded = if service == 0x5B {
+                    ForwardOpenRequest::decode_large(&mut data.clone())
+                } else {
+                    ForwardOpenRequest::decode(&mut data.clone())
+                };
+                let request = match decoded {
+                    Ok(request) => request,
+                    Err(error) => return Some(self.open_format_error(service, error)),
+                };
+                let Some(peer) = peer else {
+                    log::warn!("Forward_Open without an originator address");
+                    self.open_rejected(OpenRejectReason::Other);
+                    return Some(MessageResponse::error(service, CipError::ConnectionFailure));
+                };
+
+                self.forward_open(&request, peer)
+                    .map(|reply| encode_reply(service, &reply))
+                    .unwrap_or_else(|error| error.into_response(service, &request.key))
+            }
+            0x4E => {
+                let request = match ForwardCloseRequest::decode(&mut data.clone()) {
+                    Ok(request) => request,
+                    Err(error) => {
+                        log::warn!("Invalid Forward_Close request: {}", error);
+                        self.close_rejected(CloseRejectReason::Format);
+                        return Some(MessageResponse::error(service, error.into()));
+                    }
+                };
+
+                self.forward_close(&request)
+                    .map(|key| encode_reply(service, &ForwardCloseReply(key)))
+                    .unwrap_or_else(|error| error.into_response(service, &request.key))
+            }
+            _ => return None,
+        };
+        Some(response)
+    }
+
+    /// Resets the inactivity timer of the connection consuming `o_to_t_connection_id`,
+    /// called for every packet received on it. Returns the connection, `None` if it is
+    /// not open.
+    pub fn connection_received(&self, o_to_t_connection_id: u32) -> Option<ConnectionInfo> {
+        match self.connections.write() {
+            Ok(mut connections) => connections
+                .values_mut()
+                .find(|connection| connection.info.o_to_t_connection_id == o_to_t_connection_id)
+                .map(|connection| {
+                    connection.last_received = Instant::now();
+                    connection.info.clone()
+                }),
+            Err(_) => {
+                log::error!("Failed to get write guard for active connections");
+                None
+            }
+        }
+    }
+
+    /// Closes the connections opened by `peer` when its session ends, returning them.
+    /// They are neither timeouts nor close requests, no counter is updated.
+    pub fn close_connections_of(&self, peer: SocketAddr) -> Vec<ConnectionInfo> {
+        match self.connections.write() {
+            Ok(mut connections) => {
+                let keys = connections
+                    .values()
+                    .filter(|connection| connection.info.peer == peer)
+                    .map(|connection| connection.info.key)
+                    .collect::<Vec<_>>();
+                keys.iter()
+                    .filter_map(|key| connections.remove(key))
+                    .map(|connection| connection.info)
+                    .collect()
+            }
+            Err(_) => {
+                log::error!("Failed to get write guard for active connections");
+                Vec::new()
+            }
+        }
+    }
+
+    /// Times out the connections that received nothing within their connection timeout
+    /// before `now`, returning them.
+    pub fn expire_connections(&self, now: Instant) -> Vec<ConnectionInfo> {
+        let expired = match self.connections.read() {
+            Ok(connections) => connections
+                .values()
+                .filter(|connection| {
+                    now.saturating_duration_since(connection.last_received)
+                        > connection.info.connection_timeout
+                })
+                .map(|connection| connection.info.key)
+                .collect::<Vec<_>>(),
+            Err(_) => {
+                log::error!("Failed to get read guard for active connections");
+                Vec::new()
+            }
+        };
+
+        expired
+            .iter()
+            .filter_map(|key| {
+                log::warn!("Connection {:?} timed out", key);
+                self.connection_timed_out(key)
+            })
+            .collect()
+    }
+
+    fn is_open(&self, key: &ConnectionKey) -> bool {
+        match self.connections.read() {
+            Ok(connections) => connections.contains_key(key),
+            Err(_) => {
+                log::error!("Failed to get read guard for active connections");
+                false
+            }
+        }
+    }
+
+    fn open_format_error(&self, service: u8, error: BinaryError) -> MessageResponse {
+        log::warn!("Invalid Forward_Open request: {}", error);
+        self.open_rejected(OpenRejectReason::Format);
+        MessageResponse::error(service, error.into())
+    }
+}
+
+impl ActiveConnection {
+    pub(super) fn new(info: ConnectionInfo) -> Self {
+        Self {
+            info,
+            last_received: Instant::now(),
+        }
+    }
+}
+
+/// Successful Forward_Close reply, without application reply data.
+struct ForwardCloseReply(ConnectionKey);
+
+impl ToBytes for ForwardCloseReply {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        self.0.encode(buffer)?;
+        // Application reply size and reserved byte
+        buffer.put_u8(0);
+        buffer.put_u8(0);
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        10
+    }
+}
+
+fn encode_reply(service: u8, reply: &impl ToBytes) -> MessageResponse {
+    let mut data = BytesMut::with_capacity(reply.encoded_len());
+    match reply.encode(&mut data) {
+        Ok(()) => MessageResponse::success(service, data.freeze()),
+        Err(error) => MessageResponse::error(service, error.into()),
+    }
+}
+
+fn decode_path<T: Buf>(buffer: &mut T, path_len: usize) -> Result<PaddedEPath, BinaryError> {
+    if buffer.remaining() < path_len {
+        return Err(BinaryError::Truncated {
+            expected: path_len,
+            actual: buffer.remaining(),
+        });
+    }
+
+    match path_len {
+        0 => Ok(PaddedEPath::new(Vec::new())),
+        _ => PaddedEPath::decode(&mut buffer.copy_to_bytes(path_len)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cip::{connection_manager::ConnectionStatistics, data_types::epath::EPathBuilder};
+    use std::net::{Ipv4Addr, SocketAddrV4};
+
+    const PEER: SocketAddr = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 44818));
+
+    fn key(serial: u16) -> ConnectionKey {
+        ConnectionKey {
+            connection_serial_number: serial,
+            originator_vendor_id: 0x0001,
+            originator_serial_number: 0x12345678,
+        }
+    }
+
+    /// Class 3 Forward_Open with a 10 ms O->T RPI and timeout multiplier 1.
+    fn forward_open_data(serial: u16) -> Bytes {
+        forward_open_with_path(serial, EPathBuilder::new().class(0x02).instance(1).build())
+    }
+
+    fn forward_open_with_path(serial: u16, path: PaddedEPath) -> Bytes {
+        let mut data = BytesMut::new();
+        data.put_u8(0x0A);
+        data.put_u8(0x0E);
+        data.put_u32_le(0);
+        data.put_u32_le(0x8000_0001);
+        key(serial).encode(&mut data).expect("Failed to encode key");
+        data.put_u8(1);
+        data.put_slice(&[0x00; 3]);
+        data.put_u32_le(10_000);
+        data.put_u16_le(0x43F4);
+        data.put_u32_le(20_000);
+        data.put_u16_le(0x43F4);
+        data.put_u8(0xA3);
+        data.put_u8((path.encoded_len() / 2) as u8);
+        path.encode(&mut data).expect("Failed to encode path");
+        data.freeze()
+    }
+
+    /// Class 3 Large_Forward_Open with `size` bytes in both directions.
+    fn large_forward_open_data(serial: u16, size: u16) -> Bytes {
+        let path = EPathBuilder::new().class(0x02).instance(1).build();
+        let mut data = BytesMut::new();
+        data.put_u8(0x0A);
+        data.put_u8(0x0E);
+        data.put_u32_le(0);
+        data.put_u32_le(0x8000_0001);
+        key(serial).encode(&mut data).expect("Failed to encode key");
+        data.put_u8(1);
+        data.put_slice(&[0x00; 3]);
+        data.put_u32_le(10_000);
+        data.put_u32_le(0x4200_0000 | size as u32);
+        data.put_u32_le(20_000);
+        data.put_u32_le(0x4200_0000 | size as u32);
+        data.put_u8(0xA3);
+        data.put_u8((path.encoded_len() / 2) as u8);
+        path.encode(&mut data).expect("Failed to encode path");
+        data.freeze()
+    }
+
+    fn forward_close_data(serial: u16) -> Bytes {
+        let mut data = BytesMut::new();
+        data.put_u8(0x0A);
+        data.put_u8(0x0E);
+        key(serial).encode(&mut data).expect("Failed to encode key");
+        data.put_u8(0);
+        data.put_u8(0);
+        data.freeze()
+    }
+
+    #[test]
+    fn forward_open_and_close_update_statistics() {
+        let instance = ConnectionManagerInstance::new();
+
+        let opened = instance
+            .execute_connection_service(0x54, &forward_open_data(1), Some(PEER))
+            .expect("Forward_Open is a connection service");
+        let duplicate = instance
+            .execute_connection_service(0x54, &forward_open_data(1), Some(PEER))
+            .expect("Forward_Open is a connection service");
+        let truncated = instance
+            .execute_connection_service(0x54, &forward_open_data(2).slice(..20), Some(PEER))
+            .expect("Forward_Open is a connection service");
+        let connections = instance.active_connections();
+        let closed = instance
+            .execute_connection_service(0x4E, &forward_close_data(1), None)
+            .expect("Forward_Close is a connection service");
+        let not_open = instance
+            .execute_connection_service(0x4E, &forward_close_data(1), None)
+            .expect("Forward_Close is a connection service");
+
+        let reply = ForwardOpenReply {
+            o_to_t_connection_id: connections[0].o_to_t_connection_id,
+            t_to_o_connection_id: 0x8000_0001,
+            key: key(1),
+            o_to_t_api_us: 10_000,
+            t_to_o_api_us: 20_000,
+        };
+        assert_eq!(opened, encode_reply(0x54, &reply));
+        assert_eq!(duplicate.extended_status, vec![CONNECTION_IN_USE]);
+        assert_eq!(truncated.general_status, CipError::NotEnoughData as u8);
+        assert_eq!(connections.len(), 1);
+        assert_eq!(connections[0].transport_class, 3);
+        assert_eq!(connections[0].connection_timeout, Duration::from_millis(80));
+        assert_eq!(connections[0].peer, PEER);
+        assert_eq!(
+            closed,
+            MessageResponse::success(
+                0x4E,
+                Bytes::from_static(&[0x01, 0x00, 0x01, 0x00, 0x78, 0x56, 0x34, 0x12, 0x00, 0x00])
+            )
+        );
+        assert_eq!(not_open.extended_status, vec![CONNECTION_NOT_FOUND]);
+        assert!(
+            instance
+                .execute_connection_service(0x0E, &Bytes::new(), None)
+                .is_none()
+        );
+        assert_eq!(
+            instance.statistics(),
+            ConnectionStatistics {
+                open_requests: 3,
+                open_format_rejects: 1,
+                open_other_rejects: 1,
+                close_requests: 2,
+                close_other_requests: 1,
+                ..Default::default()
+            }
+        );
+    }
+
+    #[test]
+    fn large_forward_open_rejects_connection_size_past_maximum() {
+        let instance = ConnectionManagerInstance::new();
+
+        let opened = instance
+            .execute_connection_service(0x5B, &large_forward_open_data(1, 4000), Some(PEER))
+            .expect("Large_Forward_Open is a connection service");
+        let too_large = instance
+            .execute_connection_service(0x5B, &large_forward_open_data(2, 4001), Some(PEER))
+            .expect("Large_Forward_Open is a connection service");
+        let not_large = instance
+            .execute_connection_service(
+                0x54,
+                &forward_open_with_path(3, EPathBuilder::new().class(0x02).instance(1).build()),
+                Some(PEER),
+            )
+            .expect("Forward_Open is a connection service");
+
+        assert!(opened.is_success());
+        assert_eq!(opened.service, 0xDB);
+        assert_eq!(too_large.service, 0xDB);
+        assert_eq!(too_large.general_status, CipError::ConnectionFailure as u8);
+        assert_eq!(too_large.extended_status, vec![INVALID_CONNECTION_SIZE]);
+        assert!(not_large.is_success());
+        assert_eq!(instance.active_connections().len(), 2);
+        assert_eq!(instance.statistics().open_resource_rejects, 1);
+    }
+
+    #[test]
+    fn forward_open_keeps_inhibit_time_and_configuration_data_of_path() {
+        let instance = ConnectionManagerInstance::new();
+        let path = EPathBuilder::new()
+            .port(1, 0)
+            .production_inhibit_ms(5)
+            .class(0x04)
+            .instance(1)
+            .connection_point(100)
+            .connection_point(150)
+            .simple_data(vec![0x01, 0x02])
+            .build();
+        let invalid = EPathBuilder::new()
+            .production_inhibit_ms(5)
+            .production_inhibit_us(2_500)
+            .class(0x04)
+            .instance(1)
+            .build();
+        let routed = EPathBuilder::new()
+            .port(1, 3)
+            .class(0x04)
+            .instance(1)
+            .build();
+
+        let opened =
+            instance.execute_connection_service(0x54, &forward_open_with_path(1, path), Some(PEER));
+        let rejected = instance.execute_connection_service(
+            0x54,
+            &forward_open_with_path(2, invalid),
+            Some(PEER),
+        );
+        let not_local = instance.execute_connection_service(
+            0x54,
+            &forward_open_with_path(3, routed),
+            Some(PEER),
+        );
+
+        assert!(opened.is_some_and(|response| response.is_success()));
+        let connections = instance.active_connections();
+        assert_eq!(connections.len(), 1);
+        assert_eq!(connections[0].production_inhibit_us, Some(5_000));
+        assert_eq!(connections[0].configuration_data, Some(vec![0x01, 0x02]));
+        assert_eq!(
+            rejected.map(|response| response.extended_status),
+            Some(vec![INVALID_SEGMENT_IN_CONNECTION_PATH])
+        );
+        assert_eq!(
+            not_local.map(|response| response.extended_status),
+            Some(vec![PORT_NOT_AVAILABLE])
+        );
+        assert_eq!(instance.statistics().open_format_rejects, 1);
+        assert_eq!(instance.statistics().open_other_rejects, 1);
+    }
+
+    #[test]
+    fn connection_without_data_within_timeout_expires() {
+        let instance = ConnectionManagerInstance::new();
+        instance
+            .execute_connection_service(0x54, &forward_open_data(1), Some(PEER))
+            .expect("Forward_Open is a connection service");
+        instance
+            .execute_connection_service(0x54, &forward_open_data(2), Some(PEER))
+            .expect("Forward_Open is a connection service");
+        let received = instance
+            .active_connections()
+            .into_iter()
+            .find(|connection| connection.key == key(2))
+            .expect("Missing connection");
+
+        let later = Instant::now() + Duration::from_millis(50);
+        assert!(instance.expire_connections(later).is_empty());
+        std::thread::sleep(Duration::from_millis(40));
+        assert_eq!(
+            instance.connection_received(received.o_to_t_connection_id),
+            Some(received.clone())
+        );
+        let expired = instance.expire_connections(later + Duration::from_millis(50));
+
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].key, key(1));
+        assert_eq!(instance.active_connections(), vec![received]);
+        assert_eq!(instance.statistics().connection_timeouts, 1);
+    }
+
+    #[test]
+    fn forward_open_rejects_io_transport_classes() {
+        let instance = ConnectionManagerInstance::new();
+        let mut class_1 = BytesMut::from(forward_open_data(1).as_ref());
+        // Transport type/trigger byte follows the 34 fixed bytes before it
+        class_1[34] = 0x01;
+
+        let rejected = instance
+            .execute_connection_service(0x54, &class_1.freeze(), Some(PEER))
+            .expect("Forward_Open is a connection service");
+
+        assert_eq!(rejected.general_status, CipError::ConnectionFailure as u8);
+        assert_eq!(
+            rejected.extended_status,
+            vec![TRANSPORT_CLASS_NOT_SUPPORTED]
+        );
+        assert!(instance.active_connections().is_empty());
+        assert_eq!(instance.statistics().open_other_rejects, 1);
+    }
+
+    #[test]
+    fn closing_connections_of_peer_keeps_others_without_counting() {
+        let other = SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 50000));
+        let instance = ConnectionManagerInstance::new();
+        instance.execute_connection_service(0x54, &forward_open_data(1), Some(PEER));
+        instance.execute_connection_service(0x54, &forward_open_data(2), Some(other));
+
+        let closed = instance.close_connections_of(PEER);
+
+        assert_eq!(closed.len(), 1);
+        assert_eq!(closed[0].key, key(1));
+        let remaining = instance.active_connections();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].peer, other);
+        assert_eq!(
+            instance.connection_received(closed[0].o_to_t_connection_id),
+            None
+        );
+        assert_eq!(instance.statistics().close_requests, 0);
+        assert_eq!(instance.statistics().connection_timeouts, 0);
+    }
+}
@@ -0,0 +1,371 @@
+use std::time::Duration;
+
+use bytes::{Buf, BufMut, Bytes};
+
+use super::ConnectionManagerInstance;
+use crate::{
+    cip::{
+        common::error::CipError,
+        data_types::epath::{LinkAddress, PaddedEPath, PortIdentifier, PortSegment, Segment},
+        message_router::MessageResponse,
+    },
+    common::binary::{BinaryError, FromBytes, ToBytes},
+};
+
+/// Port of the backplane in route paths.
+pub const BACKPLANE_PORT: u8 = 1;
+
+/// Extended status of a request that did not complete before its timeout ticks elapsed.
+pub const UNCONNECTED_REQUEST_TIMED_OUT: u16 = 0x0204;
+
+/// Extended status of a route through a port the device does not have.
+pub const PORT_NOT_AVAILABLE: u16 = 0x0311;
+
+/// Message router request: service, padded path and request data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageRequest {
+    pub service: u8,
+    pub path: PaddedEPath,
+    pub data: Bytes,
+}
+
+impl FromBytes for MessageRequest {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        if buffer.remaining() < 2 {
+            return Err(BinaryError::Truncated {
+                expected: 2,
+                actual: buffer.remaining(),
+            });
+        }
+
+        let service = buffer.get_u8();
+        let path_len = buffer.get_u8() as usize * 2;
+        if buffer.remaining() < path_len {
+            return Err(BinaryError::Truncated {
+                expected: path_len,
+                actual: buffer.remaining(),
+            });
+        }
+
+        let path = match path_len {
+            0 => PaddedEPath::new(Vec::new()),
+            _ => PaddedEPath::decode(&mut buffer.copy_to_bytes(path_len))?,
+        };
+        let data = buffer.copy_to_bytes(buffer.remaining());
+
+        Ok(Self {
+            service,
+            path,
+            data,
+        })
+    }
+}
+
+impl ToBytes for MessageRequest {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < self.encoded_len() {
+            return Err(BinaryError::BufferTooSmall {
+                expected: self.encoded_len(),
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        buffer.put_u8(self.service);
+        buffer.put_u8((self.path.encoded_len() / 2) as u8);
+        self.path.encode(buffer)?;
+        buffer.put_slice(&self.data);
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        2 + self.path.encoded_len() + self.data.len()
+    }
+}
+
+/// Request data of the Unconnected_Send service (0x52).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnconnectedSendRequest {
+    /// Tick time in the low nibble, the tick lasts 2^n milliseconds.
+    pub priority_time_tick: u8,
+    pub timeout_ticks: u8,
+    pub message: MessageRequest,
+    pub route: Vec<PortSegment>,
+}
+
+impl UnconnectedSendRequest {
+    pub fn timeout(&self) -> Duration {
+        let tick_ms = 1u64 << (self.priority_time_tick & 0x0F);
+        Duration::from_millis(tick_ms * self.timeout_ticks as u64)
+    }
+}
+
+impl FromBytes for UnconnectedSendRequest {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        if buffer.remaining() < 4 {
+            return Err(BinaryError::Truncated {
+                expected: 4,
+                actual: buffer.remaining(),
+            });
+        }
+
+        let priority_time_tick = buffer.get_u8();
+        let timeout_ticks = buffer.get_u8();
+        let message_len = buffer.get_u16_le() as usize;
+        // Message request, its pad byte, route path size and reserved byte
+        let padded_len = message_len + message_len % 2 + 2;
+        if buffer.remaining() < padded_len {
+            return Err(BinaryError::Truncated {
+                expected: padded_len,
+                actual: buffer.remaining(),
+            });
+        }
+
+        let message = MessageRequest::decode(&mut buffer.copy_to_bytes(message_len))?;
+        if !message_len.is_multiple_of(2) {
+            buffer.advance(1);
+        }
+
+        let route_len = buffer.get_u8() as usize * 2;
+        buffer.advance(1);
+        if buffer.remaining() < route_len {
+            return Err(BinaryError::Truncated {
+                expected: route_len,
+                actual: buffer.remaining(),
+            });
+        }
+
+        let route = match route_len {
+            0 => Vec::new(),
+            _ => PaddedEPath::decode(&mut buffer.copy_to_bytes(route_len))?
+                .segments()
+                .iter()
+                .map(|segment| match segment {
+                    Segment::Port(port) => Ok(*port),
+                    _ => Err(BinaryError::InvalidData {
+                        message: "Invalid route path segment".to_string(),
+                        expected: "Port segment".to_string(),
+                        actual: segment.to_string(),
+                    }),
+                })
+                .collect::<Result<_, _>>()?,
+        };
+
+        Ok(Self {
+            priority_time_tick,
+            timeout_ticks,
+            message,
+            route,
+        })
+    }
+}
+
+/// Error reply of a routed request: general status with its optional extended status.
+#[derive(Debug)]
+pub struct RoutingError {
+    pub status: CipError,
+    pub extended_status: Option<u16>,
+}
+
+impl RoutingError {
+    pub fn timed_out() -> Self {
+        Self {
+            status: CipError::ConnectionFailure,
+            extended_status: Some(UNCONNECTED_REQUEST_TIMED_OUT),
+        }
+    }
+
+    pub fn port_not_available() -> Self {
+        Self {
+            status: CipError::ConnectionFailure,
+            extended_status: Some(PORT_NOT_AVAILABLE),
+        }
+    }
+
+    /// Error reply of the Unconnected_Send service.
+    pub fn into_response(self) -> MessageResponse {
+        MessageResponse {
+            extended_status: self.extended_status.into_iter().collect(),
+            ..MessageResponse::error(0x52, self.status)
+        }
+    }
+}
+
+impl From<CipError> for RoutingError {
+    fn from(status: CipError) -> Self {
+        Self {
+            status,
+            extended_status: None,
+        }
+    }
+}
+
+/// Forwards Unconnected_Send requests to other ports, implemented by devices with a
+/// backplane or more than one network.
+pub trait BackplaneRouter: Send + Sync {
+    /// Slot of this device on the backplane, routes to it are executed locally.
+    fn slot(&self) -> u8 {
+        0
+    }
+
+    /// Sends `message` along `route` and returns the reply data, or fails with
+    /// [`RoutingError::timed_out`] when no reply arrives within `timeout`.
+    fn route(
+        &self,
+        route: &[PortSegment],
+        message: &MessageRequest,
+        timeout: Duration,
+    ) -> Result<Bytes, RoutingError>;
+}
+
+/// Router of a device without other ports, every route outside the device is rejected.
+pub struct NoBackplane;
+
+impl BackplaneRouter for NoBackplane {
+    fn route(
+        &self,
+        _route: &[PortSegment],
+        _message: &MessageRequest,
+        _timeout: Duration,
+    ) -> Result<Bytes, RoutingError> {
+        Err(RoutingError::port_not_available())
+    }
+}
+
+impl ConnectionManagerInstance {
+    /// Returns the route of an Unconnected_Send request past this device, empty when the
+    /// embedded request is for this device. Hops to its own backplane slot are local.
+    pub fn remote_route<'a>(&self, request: &'a UnconnectedSendRequest) -> &'a [PortSegment] {
+        let slot = self.router.slot();
+        request
+            .route
+            .iter()
+            .position(|hop| !is_local_hop(hop, slot))
+            .map_or(&[][..], |first_remote| &request.route[first_remote..])
+    }
+
+    /// Sends `message` along `route` through the backplane router. The router runs on a
+    /// blocking task and the request fails with [`RoutingError::timed_out`] when it has not
+    /// replied within `timeout`.
+    pub async fn route(
+        &self,
+        route: &[PortSegment],
+        message: &MessageRequest,
+        timeout: Duration,
+    ) -> Result<Bytes, RoutingError> {
+        log::debug!("Routing unconnected request through {:?}", route);
+        let router = self.router.clone();
+        let route = route.to_vec();
+        let message = message.clone();
+        let routed = tokio::task::spawn_blocking(move || router.route(&route, &message, timeout));
+
+        match tokio::time::timeout(timeout, routed).await {
+            Ok(Ok(reply)) => reply,
+            Ok(Err(error)) => {
+                log::error!("Backplane router failed: {}", error);
+                Err(RoutingError::from(CipError::GeneralError))
+            }
+            Err(_) => {
+                log::warn!("Unconnected request timed out after {:?}", timeout);
+                Err(RoutingError::timed_out())
+            }
+        }
+    }
+}
+
+pub(super) fn is_local_hop(hop: &PortSegment, slot: u8) -> bool {
+    hop.port == PortIdentifier::Default(BACKPLANE_PORT)
+        && hop.link_address == LinkAddress::Default(slot)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cip::data_types::epath::EPathBuilder;
+    use bytes::BytesMut;
+    use std::sync::Arc;
+
+    fn request(route: &[u8], timeout_ticks: u8) -> UnconnectedSendRequest {
+        let message = MessageRequest {
+            service: 0x0E,
+            path: EPathBuilder::new()
+                .class(0x01)
+                .instance(1)
+                .attribute(7)
+                .build(),
+            data: Bytes::new(),
+        };
+        let mut raw = BytesMut::new();
+        raw.put_u8(0x0A); // 1024 ms ticks
+        raw.put_u8(timeout_ticks);
+        raw.put_u16_le(message.encoded_len() as u16);
+        message.encode(&mut raw).expect("Failed to encode");
+        raw.put_u8((route.len() / 2) as u8);
+        raw.put_u8(0x00);
+        raw.put_slice(route);
+
+        UnconnectedSendRequest::decode(&mut raw.freeze()).expect("Failed to decode")
+    }
+
+    struct SlowBackplane;
+
+    impl BackplaneRouter for SlowBackplane {
+        fn slot(&self) -> u8 {
+            2
+        }
+
+        fn route(
+            &self,
+            _route: &[PortSegment],
+            _message: &MessageRequest,
+            _timeout: Duration,
+        ) -> Result<Bytes, RoutingError> {
+            std::thread::sleep(Duration::from_millis(200));
+            Ok(Bytes::new())
+        }
+    }
+
+    #[test]
+    fn route_to_own_slot_stays_local() {
+        let instance = ConnectionManagerInstance::new();
+        let request = request(&[0x01, 0x00], 5);
+
+        assert!(instance.remote_route(&request).is_empty());
+        assert_eq!(request.message.path.to_string(), "@1/1/7");
+        assert_eq!(request.timeout(), Duration::from_millis(5 * 1024));
+    }
+
+    #[tokio::test]
+    async fn route_to_other_slot_without_backplane_returns_port_not_available() {
+        let instance = ConnectionManagerInstance::new();
+        let request = request(&[0x01, 0x03], 5);
+
+        let route = instance.remote_route(&request);
+        let result = instance
+            .route(route, &request.message, request.timeout())
+            .await;
+
+        assert_eq!(route.len(), 1);
+        let error = result.expect_err("Expected routing error");
+        assert!(matches!(error.status, CipError::ConnectionFailure));
+        assert_eq!(error.extended_status, Some(PORT_NOT_AVAILABLE));
+    }
+
+    #[tokio::test]
+    async fn backplane_router_slower_than_timeout_returns_timed_out() {
+        let instance = ConnectionManagerInstance::with_router(Arc::new(SlowBackplane));
+        let request = request(&[0x01, 0x02, 0x01, 0x05], 5);
+
+        let route = instance.remote_route(&request);
+        let result = instance
+            .route(route, &request.message, Duration::from_millis(20))
+            .await;
+
+        assert_eq!(route.len(), 1);
+        let response = result.expect_err("Expected timeout").into_response();
+        assert_eq!(response.service, 0xD2);
+        assert_eq!(
+            response.extended_status,
+            vec![UNCONNECTED_REQUEST_TIMED_OUT]
+        );
+    }
+}
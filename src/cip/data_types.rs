@@ -3,14 +3,47 @@ use bytes::{Buf, BufMut};
 use crate::common::binary::{BinaryError, FromBytes, ToBytes};
 
 mod ascii;
+pub mod boolean;
+pub mod data_type;
 pub mod epath;
+pub mod real;
 pub mod short_string;
 pub mod string;
+pub mod string_i;
+pub mod string_n;
+pub mod string2;
+pub mod time;
+pub mod value;
 #[macro_use]
 mod primitive;
 
+pub use boolean::Bool;
+pub use data_type::{CipDataType, TypeDescriptor};
+pub use real::{LReal, Real};
 pub use short_string::ShortString;
 pub use string::CipString;
+pub use string_i::StringI;
+pub use string_n::StringN;
+pub use string2::String2;
+pub use time::{Date, DateAndTime, FTime, ITime, LTime, NTime, STime, Time, TimeOfDay};
+pub use value::CipValue;
+
+/// Elementary data type with its CIP data type code.
+pub trait ElementaryType: FromBytes + ToBytes {
+    const DATA_TYPE: CipDataType;
+    const TYPE_CODE: u8 = Self::DATA_TYPE as u8;
+}
+
+/// The type code comes from the [`CipDataType`] variant of the same name.
+macro_rules! impl_elementary_type {
+    ($($name:ident),* $(,)?) => {
+        $(
+            impl ElementaryType for $name {
+                const DATA_TYPE: CipDataType = CipDataType::$name;
+            }
+        )*
+    };
+}
 
 impl_cip_primitive!(Byte, u8);
 impl_cip_primitive!(Word, u16);
@@ -19,3 +52,35 @@ impl_cip_primitive!(USInt, u8);
 impl_cip_primitive!(UInt, u16);
 impl_cip_primitive!(UDInt, u32);
 impl_cip_primitive!(ULInt, u64);
+impl_cip_primitive!(LWord, u64);
+impl_cip_primitive!(SInt, i8);
+impl_cip_primitive!(Int, i16);
+impl_cip_primitive!(DInt, i32);
+impl_cip_primitive!(LInt, i64);
+
+impl_elementary_type!(
+    Bool,
+    SInt,
+    Int,
+    DInt,
+    LInt,
+    USInt,
+    UInt,
+    UDInt,
+    ULInt,
+    Real,
+    LReal,
+    STime,
+    Date,
+    TimeOfDay,
+    DateAndTime,
+    Byte,
+    Word,
+    DWord,
+    LWord,
+    FTime,
+    LTime,
+    ITime,
+    Time,
+    NTime,
+);
@@ -116,6 +116,37 @@ impl ToBytes for u16 {
     }
 }
 
+impl FromBytes for u32 {
+    fn decode<T: bytes::Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        if buffer.remaining() < 4 {
+            return Err(BinaryError::Truncated {
+                expected: 4,
+                actual: buffer.remaining(),
+            });
+        }
+
+        Ok(buffer.get_u32_le())
+    }
+}
+
+impl ToBytes for u32 {
+    fn encode<T: bytes::BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < 4 {
+            return Err(BinaryError::BufferTooSmall {
+                expected: 4,
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        buffer.put_u32_le(*self);
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        4
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct AsciiString<L: StringLen, const N: usize> {
     len: L,
@@ -156,12 +187,14 @@ impl<L: StringLen, const N: usize> AsciiString<L, N> {
         self.len.into()
     }
 
+    /// Returns the characters up to the first invalid UTF-8 sequence, which only strings
+    /// built with [`from_bytes`](Self::from_bytes) can contain.
     pub fn value(&self) -> &str {
-        if self.len() == 0 {
-            return "";
+        let bytes = &self.characters[..self.len()];
+        match std::str::from_utf8(bytes) {
+            Ok(value) => value,
+            Err(error) => std::str::from_utf8(&bytes[..error.valid_up_to()]).unwrap_or_default(),
         }
-
-        unsafe { std::str::from_utf8_unchecked(&self.characters[..self.len()]) }
     }
 }
 
@@ -192,6 +225,14 @@ impl<L: StringLen, const N: usize> FromBytes for AsciiString<L, N> {
 
         let mut characters = [0u8; N];
         buffer.copy_to_slice(&mut characters[..len.into()]);
+        if let Err(error) = std::str::from_utf8(&characters[..len.into()]) {
+            return Err(BinaryError::InvalidData {
+                message: "Invalid ASCII string characters".to_string(),
+                expected: "UTF-8".to_string(),
+                actual: error.to_string(),
+            });
+        }
+
         Ok(Self { len, characters })
     }
 }
@@ -215,6 +256,9 @@ impl<L: StringLen, const N: usize> ToBytes for AsciiString<L, N> {
     }
 }
 
+#[cfg(feature = "serde")]
+crate::common::serde_text::impl_serde_string!([L: StringLen, const N: usize] AsciiString<L, N>);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -330,6 +374,15 @@ mod tests {
         }
     }
 
+    #[test]
+    fn ascii_string_invalid_utf8_returns_error() {
+        let mut cursor = Bytes::from_static(&[0x02, b'A', 0xFF]);
+
+        let result = AsciiString::<u8, 4>::decode(&mut cursor);
+
+        assert!(matches!(result, Err(BinaryError::InvalidData { .. })));
+    }
+
     #[test]
     fn ascii_string_buffer_too_small_returns_error() {
         let s: AsciiString<u8, 10> = AsciiString::new("Hello");
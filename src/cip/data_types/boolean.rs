@@ -0,0 +1,78 @@
+use bytes::{Buf, BufMut};
+
+use crate::common::binary::{BinaryError, FromBytes, ToBytes};
+
+/// BOOL, encoded as one byte. Any non-zero byte decodes as `true`.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct Bool(bool);
+
+impl Bool {
+    pub const LEN: usize = 1;
+
+    pub fn new(value: bool) -> Self {
+        Self(value)
+    }
+
+    pub fn value(&self) -> bool {
+        self.0
+    }
+}
+
+impl From<bool> for Bool {
+    fn from(value: bool) -> Self {
+        Self(value)
+    }
+}
+
+impl FromBytes for Bool {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        if buffer.remaining() < Self::LEN {
+            return Err(BinaryError::Truncated {
+                expected: Self::LEN,
+                actual: buffer.remaining(),
+            });
+        }
+
+        Ok(Self(buffer.get_u8() != 0))
+    }
+}
+
+impl ToBytes for Bool {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < Self::LEN {
+            return Err(BinaryError::BufferTooSmall {
+                expected: Self::LEN,
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        buffer.put_u8(self.0 as u8);
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        Self::LEN
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::Bytes;
+
+    use super::*;
+
+    #[test]
+    fn bool_decode_non_zero_is_true() {
+        let mut buffer = Bytes::from_static(&[0x00, 0x01, 0xFF]);
+
+        assert!(!Bool::decode(&mut buffer).expect("Failed to decode").value());
+        assert!(Bool::decode(&mut buffer).expect("Failed to decode").value());
+        assert!(Bool::decode(&mut buffer).expect("Failed to decode").value());
+    }
+}
@@ -0,0 +1,386 @@
+use bytes::{Buf, BufMut};
+
+use crate::common::binary::{BinaryError, FromBytes, ToBytes};
+
+/// Declares [`CipDataType`] with its code and name lookups from a single table.
+macro_rules! cip_data_types {
+    ($($variant:ident = $code:literal => $name:literal),* $(,)?) => {
+        /// Elementary data type codes. EPATH (0xDC) and ENGUNIT (0xDD) are not supported.
+        #[repr(u8)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum CipDataType {
+            $( $variant = $code, )*
+        }
+
+        impl CipDataType {
+            pub fn code(&self) -> u8 {
+                *self as u8
+            }
+
+            pub fn name(&self) -> &'static str {
+                match self {
+                    $( Self::$variant => $name, )*
+                }
+            }
+        }
+
+        impl TryFrom<u8> for CipDataType {
+            type Error = BinaryError;
+
+            fn try_from(value: u8) -> Result<Self, Self::Error> {
+                match value {
+                    $( $code => Ok(Self::$variant), )*
+                    _ => Err(BinaryError::InvalidData {
+                        message: "Unknown data type code".to_string(),
+                        expected: "0xC1..=0xDF".to_string(),
+                        actual: format!("{:#04X}", value),
+                    }),
+                }
+            }
+        }
+    };
+}
+
+cip_data_types!(
+    Bool = 0xC1 => "BOOL",
+    SInt = 0xC2 => "SINT",
+    Int = 0xC3 => "INT",
+    DInt = 0xC4 => "DINT",
+    LInt = 0xC5 => "LINT",
+    USInt = 0xC6 => "USINT",
+    UInt = 0xC7 => "UINT",
+    UDInt = 0xC8 => "UDINT",
+    ULInt = 0xC9 => "ULINT",
+    Real = 0xCA => "REAL",
+    LReal = 0xCB => "LREAL",
+    STime = 0xCC => "STIME",
+    Date = 0xCD => "DATE",
+    TimeOfDay = 0xCE => "TIME_OF_DAY",
+    DateAndTime = 0xCF => "DATE_AND_TIME",
+    String = 0xD0 => "STRING",
+    Byte = 0xD1 => "BYTE",
+    Word = 0xD2 => "WORD",
+    DWord = 0xD3 => "DWORD",
+    LWord = 0xD4 => "LWORD",
+    String2 = 0xD5 => "STRING2",
+    FTime = 0xD6 => "FTIME",
+    LTime = 0xD7 => "LTIME",
+    ITime = 0xD8 => "ITIME",
+    StringN = 0xD9 => "STRINGN",
+    ShortString = 0xDA => "SHORT_STRING",
+    Time = 0xDB => "TIME",
+    StringI = 0xDE => "STRINGI",
+    NTime = 0xDF => "NTIME",
+);
+
+impl std::fmt::Display for CipDataType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl std::str::FromStr for CipDataType {
+    type Err = BinaryError;
+
+    /// Parses the type name, ex: "UINT".
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        (0xC1..=0xDF)
+            .filter_map(|code| Self::try_from(code).ok())
+            .find(|data_type| data_type.name().eq_ignore_ascii_case(name))
+            .ok_or_else(|| BinaryError::InvalidData {
+                message: "Unknown data type name".to_string(),
+                expected: "BOOL..NTIME".to_string(),
+                actual: name.to_string(),
+            })
+    }
+}
+
+#[cfg(feature = "serde")]
+crate::common::serde_text::impl_serde_text!(CipDataType);
+
+/// Type of a value, as carried by the type encodings of Appendix C.
+///
+/// Elementary types are encoded by their type code alone (abbreviated encoding);
+/// structures and arrays use the formal constructed encodings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum TypeDescriptor {
+    Elementary(CipDataType),
+    /// 0xA2, length, then the member types in order.
+    Struct(Vec<TypeDescriptor>),
+    /// 0xA3, length, lower and upper bound as typed USINT/UINT/UDINT values, element type.
+    Array {
+        lower_bound: u32,
+        upper_bound: u32,
+        element: Box<TypeDescriptor>,
+    },
+}
+
+impl TypeDescriptor {
+    pub const STRUCT: u8 = 0xA2;
+    pub const ARRAY: u8 = 0xA3;
+
+    /// Array indexed from 0 with `count` elements; `count` must not be 0.
+    pub fn array(element: TypeDescriptor, count: u32) -> Self {
+        Self::Array {
+            lower_bound: 0,
+            upper_bound: count.saturating_sub(1),
+            element: Box::new(element),
+        }
+    }
+
+    /// Number of elements of an array, `None` for other types.
+    pub fn element_count(&self) -> Option<usize> {
+        match self {
+            Self::Array {
+                lower_bound,
+                upper_bound,
+                ..
+            } => Some(upper_bound.saturating_sub(*lower_bound) as usize + 1),
+            _ => None,
+        }
+    }
+
+    fn body_len(&self) -> usize {
+        match self {
+            Self::Elementary(_) => 0,
+            Self::Struct(members) => members.iter().map(ToBytes::encoded_len).sum(),
+            Self::Array {
+                lower_bound,
+                upper_bound,
+                element,
+            } => bound_len(*lower_bound) + bound_len(*upper_bound) + element.encoded_len(),
+        }
+    }
+}
+
+impl From<CipDataType> for TypeDescriptor {
+    fn from(data_type: CipDataType) -> Self {
+        Self::Elementary(data_type)
+    }
+}
+
+/// Bounds use the narrowest unsigned type holding the value.
+fn bound_len(value: u32) -> usize {
+    match value {
+        0..=0xFF => 2,
+        0x100..=0xFFFF => 3,
+        _ => 5,
+    }
+}
+
+fn encode_bound<T: BufMut>(value: u32, buffer: &mut T) {
+    match value {
+        0..=0xFF => {
+            buffer.put_u8(CipDataType::USInt.code());
+            buffer.put_u8(value as u8);
+        }
+        0x100..=0xFFFF => {
+            buffer.put_u8(CipDataType::UInt.code());
+            buffer.put_u16_le(value as u16);
+        }
+        _ => {
+            buffer.put_u8(CipDataType::UDInt.code());
+            buffer.put_u32_le(value);
+        }
+    }
+}
+
+fn decode_bound<T: Buf>(buffer: &mut T) -> Result<u32, BinaryError> {
+    let code = u8::decode(buffer)?;
+    match CipDataType::try_from(code)? {
+        CipDataType::USInt => Ok(u8::decode(buffer)? as u32),
+        CipDataType::UInt => Ok(u16::decode(buffer)? as u32),
+        CipDataType::UDInt => u32::decode(buffer),
+        data_type => Err(BinaryError::InvalidData {
+            message: "Invalid array bound type".to_string(),
+            expected: "USINT, UINT or UDINT".to_string(),
+            actual: data_type.to_string(),
+        }),
+    }
+}
+
+impl FromBytes for TypeDescriptor {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        let code = u8::decode(buffer)?;
+        if code != Self::STRUCT && code != Self::ARRAY {
+            return Ok(Self::Elementary(CipDataType::try_from(code)?));
+        }
+
+        let len = u8::decode(buffer)? as usize;
+        if buffer.remaining() < len {
+            return Err(BinaryError::Truncated {
+                expected: len,
+                actual: buffer.remaining(),
+            });
+        }
+        let mut body = buffer.copy_to_bytes(len);
+
+        let descriptor = if code == Self::STRUCT {
+            let mut members = Vec::new();
+            while body.has_remaining() {
+                members.push(Self::decode(&mut body)?);
+            }
+            Self::Struct(members)
+        } else {
+            let lower_bound = decode_bound(&mut body)?;
+            let upper_bound = decode_bound(&mut body)?;
+            if upper_bound < lower_bound {
+                return Err(BinaryError::InvalidData {
+                    message: "Array upper bound below lower bound".to_string(),
+                    expected: format!(">= {}", lower_bound),
+                    actual: upper_bound.to_string(),
+                });
+            }
+            let element = Box::new(Self::decode(&mut body)?);
+            Self::Array {
+                lower_bound,
+                upper_bound,
+                element,
+            }
+        };
+
+        if body.has_remaining() {
+            return Err(BinaryError::InvalidData {
+                message: "Trailing bytes in constructed type".to_string(),
+                expected: "0".to_string(),
+                actual: body.remaining().to_string(),
+            });
+        }
+
+        Ok(descriptor)
+    }
+}
+
+impl ToBytes for TypeDescriptor {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < self.encoded_len() {
+            return Err(BinaryError::BufferTooSmall {
+                expected: self.encoded_len(),
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        let body_len = self.body_len();
+        if body_len > u8::MAX as usize {
+            return Err(BinaryError::InvalidData {
+                message: "Constructed type encoding too long".to_string(),
+                expected: format!("<= {}", u8::MAX),
+                actual: body_len.to_string(),
+            });
+        }
+
+        match self {
+            Self::Elementary(data_type) => buffer.put_u8(data_type.code()),
+            Self::Struct(members) => {
+                buffer.put_u8(Self::STRUCT);
+                buffer.put_u8(body_len as u8);
+                for member in members {
+                    member.encode(buffer)?;
+                }
+            }
+            Self::Array {
+                lower_bound,
+                upper_bound,
+                element,
+            } => {
+                buffer.put_u8(Self::ARRAY);
+                buffer.put_u8(body_len as u8);
+                encode_bound(*lower_bound, buffer);
+                encode_bound(*upper_bound, buffer);
+                element.encode(buffer)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        match self {
+            Self::Elementary(_) => 1,
+            _ => 2 + self.body_len(),
+        }
+    }
+}
+
+impl std::fmt::Display for TypeDescriptor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Elementary(data_type) => write!(f, "{}", data_type),
+            Self::Struct(members) => {
+                write!(f, "STRUCT {{ ")?;
+                for (i, member) in members.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", member)?;
+                }
+                write!(f, " }}")
+            }
+            Self::Array {
+                lower_bound,
+                upper_bound,
+                element,
+            } => write!(f, "ARRAY [{}..{}] OF {}", lower_bound, upper_bound, element),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::{Bytes, BytesMut};
+
+    use super::*;
+
+    #[test]
+    fn formal_struct_round_trip_success() {
+        let raw_bytes = [
+            0xA2, 0x08, // Struct, 8 bytes
+            0xC7, // UINT
+            0xA3, 0x05, // Array, 5 bytes
+            0xC6, 0x00, // Lower bound 0
+            0xC6, 0x03, // Upper bound 3
+            0xC1, // BOOL
+        ];
+
+        let descriptor = TypeDescriptor::decode(&mut Bytes::copy_from_slice(&raw_bytes))
+            .expect("Failed to decode");
+        assert_eq!(
+            descriptor,
+            TypeDescriptor::Struct(vec![
+                CipDataType::UInt.into(),
+                TypeDescriptor::array(CipDataType::Bool.into(), 4),
+            ])
+        );
+        assert_eq!(
+            descriptor.to_string(),
+            "STRUCT { UINT, ARRAY [0..3] OF BOOL }"
+        );
+
+        let mut buffer = BytesMut::new();
+        descriptor.encode(&mut buffer).expect("Failed to encode");
+        assert_eq!(buffer.as_ref(), &raw_bytes);
+    }
+
+    #[test]
+    fn elementary_type_code_matches_data_type() {
+        use crate::cip::data_types::{ElementaryType, LReal, UDInt};
+
+        assert_eq!(UDInt::TYPE_CODE, 0xC8);
+        assert_eq!(
+            CipDataType::try_from(LReal::TYPE_CODE).ok(),
+            Some(CipDataType::LReal)
+        );
+    }
+
+    #[test]
+    fn unknown_type_code_returns_error() {
+        let result = TypeDescriptor::decode(&mut Bytes::from_static(&[0xDC]));
+
+        assert!(matches!(result, Err(BinaryError::InvalidData { .. })));
+    }
+}
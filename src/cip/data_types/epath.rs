@@ -1,13 +1,27 @@
+mod builder;
+mod data_segment;
+mod logical_segment;
+mod network_segment;
 mod port_segment;
+mod text;
 
-use bytes::Buf;
-pub use port_segment::PortSegment;
+use bytes::{Buf, BufMut};
+pub use builder::EPathBuilder;
+pub use data_segment::DataSegment;
+pub use logical_segment::{LogicalSegment, LogicalType};
+pub use network_segment::NetworkSegment;
+pub use port_segment::{LinkAddress, PortIdentifier, PortSegment};
 
 use crate::common::binary::{BinaryError, FromBytes, ToBytes};
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+// Port segments carry an inline link address buffer, kept unboxed like the other segments
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub enum Segment {
     Port(PortSegment),
+    Logical(LogicalSegment),
+    Network(NetworkSegment),
+    Data(DataSegment),
 }
 
 impl Segment {
@@ -23,15 +37,26 @@ impl FromBytes for Segment {
             });
         }
 
-        let mut first_byte = [0u8; 1];
-        buffer.copy_to_slice(&mut first_byte);
-        let segment_type = SegmentType::from(first_byte[0]);
+        // The segment decoders consume the segment type byte themselves
+        let segment_type = SegmentType::from(buffer.chunk()[0] >> 5);
 
         match segment_type {
             SegmentType::PortSegment => {
                 let port_segment = PortSegment::decode(buffer)?;
                 Ok(Segment::Port(port_segment))
             }
+            SegmentType::LogicalSegment => {
+                let logical_segment = LogicalSegment::decode(buffer)?;
+                Ok(Segment::Logical(logical_segment))
+            }
+            SegmentType::NetworkSegment => {
+                let network_segment = NetworkSegment::decode(buffer)?;
+                Ok(Segment::Network(network_segment))
+            }
+            SegmentType::DataSegment => {
+                let data_segment = DataSegment::decode(buffer)?;
+                Ok(Segment::Data(data_segment))
+            }
             _ => Err(BinaryError::InvalidData {
                 message: "Invalid EPATH segment data".to_string(),
                 expected: "Valid segment type".to_string(),
@@ -52,6 +77,9 @@ impl ToBytes for Segment {
 
         match self {
             Segment::Port(port_segment) => port_segment.encode(buffer)?,
+            Segment::Logical(logical_segment) => logical_segment.encode(buffer)?,
+            Segment::Network(network_segment) => network_segment.encode(buffer)?,
+            Segment::Data(data_segment) => data_segment.encode(buffer)?,
         }
 
         Ok(())
@@ -60,6 +88,9 @@ impl ToBytes for Segment {
     fn encoded_len(&self) -> usize {
         match self {
             Segment::Port(port_segment) => port_segment.encoded_len(),
+            Segment::Logical(logical_segment) => logical_segment.encoded_len(),
+            Segment::Network(network_segment) => network_segment.encoded_len(),
+            Segment::Data(data_segment) => data_segment.encoded_len(),
         }
     }
 }
@@ -73,6 +104,10 @@ impl PaddedEPath {
     pub fn new(segments: Vec<Segment>) -> Self {
         Self { segments }
     }
+
+    pub fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
 }
 
 impl FromBytes for PaddedEPath {
@@ -118,6 +153,99 @@ impl ToBytes for PaddedEPath {
     }
 }
 
+/// Padded EPATH preceded by its size in 16-bit words (UINT).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SizedEPath {
+    path_size: u16,
+    path: PaddedEPath,
+}
+
+impl SizedEPath {
+    pub const MIN_LEN: usize = 2;
+
+    pub fn new(path: PaddedEPath) -> Self {
+        Self {
+            path_size: (path.encoded_len() / 2) as u16,
+            path,
+        }
+    }
+
+    pub fn path(&self) -> &PaddedEPath {
+        &self.path
+    }
+}
+
+impl FromBytes for SizedEPath {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        if buffer.remaining() < Self::MIN_LEN {
+            return Err(BinaryError::Truncated {
+                expected: Self::MIN_LEN,
+                actual: buffer.remaining(),
+            });
+        }
+
+        let path_size = buffer.get_u16_le();
+        let path_size_bytes = path_size as usize * 2;
+
+        if buffer.remaining() < path_size_bytes {
+            return Err(BinaryError::Truncated {
+                expected: path_size_bytes,
+                actual: buffer.remaining(),
+            });
+        }
+
+        let path = PaddedEPath::decode(&mut buffer.copy_to_bytes(path_size_bytes))?;
+        Ok(Self { path_size, path })
+    }
+}
+
+impl ToBytes for SizedEPath {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < self.encoded_len() {
+            return Err(BinaryError::BufferTooSmall {
+                expected: self.encoded_len(),
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        buffer.put_u16_le(self.path_size);
+        self.path.encode(buffer)?;
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        self.path.encoded_len() + 2
+    }
+}
+
+#[cfg(feature = "serde")]
+crate::common::serde_text::impl_serde_hex!(
+    PortSegment,
+    LogicalSegment,
+    NetworkSegment,
+    DataSegment,
+    Segment
+);
+
+// Serialized in the text notation, ex: "1,10.0.0.5/@4/100/3"
+#[cfg(feature = "serde")]
+crate::common::serde_text::impl_serde_text!(PaddedEPath);
+
+/// Serialized as the path alone, the size is implied.
+#[cfg(feature = "serde")]
+impl serde::Serialize for SizedEPath {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.path.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for SizedEPath {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        PaddedEPath::deserialize(deserializer).map(Self::new)
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SegmentType {
     PortSegment = 0b000,
@@ -157,3 +285,31 @@ impl std::fmt::Display for SegmentType {
         write!(f, "{:?}", self)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::{Bytes, BytesMut};
+
+    #[test]
+    fn padded_epath_mixed_segments_round_trip_success() {
+        let raw_bytes: [u8; 6] = [
+            0x01, 0x00, // Port 1, link address 0
+            0x20, 0x04, // Class 0x04
+            0x24, 0x64, // Instance 100
+        ];
+
+        let mut cursor = Bytes::copy_from_slice(&raw_bytes);
+        let decoded = PaddedEPath::decode(&mut cursor).expect("Failed to decode");
+
+        assert_eq!(decoded.segments().len(), 3);
+        assert_eq!(
+            decoded.segments()[2],
+            Segment::Logical(LogicalSegment::instance_id(100))
+        );
+
+        let mut buffer = BytesMut::with_capacity(decoded.encoded_len());
+        decoded.encode(&mut buffer).expect("Failed to encode");
+        assert_eq!(buffer.as_ref(), &raw_bytes);
+    }
+}
@@ -0,0 +1,113 @@
+use crate::cip::data_types::{
+    epath::{
+        DataSegment, LinkAddress, LogicalSegment, NetworkSegment, PaddedEPath, PortIdentifier,
+        PortSegment, Segment, SizedEPath,
+    },
+    short_string::ShortString,
+};
+
+impl From<u16> for PortIdentifier {
+    /// Ports 0-14 fit the segment type byte, larger ports use the extended identifier.
+    fn from(port: u16) -> Self {
+        if port < 15 {
+            Self::Default(port as u8)
+        } else {
+            Self::Extended(port)
+        }
+    }
+}
+
+impl From<u8> for LinkAddress {
+    fn from(link: u8) -> Self {
+        Self::Default(link)
+    }
+}
+
+impl From<&str> for LinkAddress {
+    /// Extended link address, ex: an IP address or a host name.
+    fn from(link: &str) -> Self {
+        Self::Extended(ShortString::new(link))
+    }
+}
+
+/// Builds a padded EPATH segment by segment.
+///
+/// ### Example
+/// ```rust,ignore
+/// let path = EPathBuilder::new()
+///     .port(1, "10.0.0.5")
+///     .class(0x04)
+///     .instance(100)
+///     .attribute(3)
+///     .build();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct EPathBuilder {
+    segments: Vec<Segment>,
+}
+
+impl EPathBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn port(mut self, port: u16, link: impl Into<LinkAddress>) -> Self {
+        self.segments.push(Segment::Port(PortSegment {
+            port: port.into(),
+            link_address: link.into(),
+        }));
+        self
+    }
+
+    pub fn class(self, class_id: u16) -> Self {
+        self.segment(Segment::Logical(LogicalSegment::class_id(class_id)))
+    }
+
+    pub fn instance(self, instance_id: u32) -> Self {
+        self.segment(Segment::Logical(LogicalSegment::instance_id(instance_id)))
+    }
+
+    pub fn attribute(self, attribute_id: u16) -> Self {
+        self.segment(Segment::Logical(LogicalSegment::attribute_id(attribute_id)))
+    }
+
+    pub fn member(self, member_id: u32) -> Self {
+        self.segment(Segment::Logical(LogicalSegment::member_id(member_id)))
+    }
+
+    pub fn connection_point(self, connection_point: u32) -> Self {
+        self.segment(Segment::Logical(LogicalSegment::connection_point(
+            connection_point,
+        )))
+    }
+
+    pub fn production_inhibit_ms(self, milliseconds: u8) -> Self {
+        self.segment(Segment::Network(NetworkSegment::ProductionInhibitTime(
+            milliseconds,
+        )))
+    }
+
+    pub fn production_inhibit_us(self, microseconds: u32) -> Self {
+        self.segment(Segment::Network(
+            NetworkSegment::ProductionInhibitTimeMicros(microseconds),
+        ))
+    }
+
+    /// Simple data segment, ex: configuration data for the configuration assembly.
+    pub fn simple_data(self, data: impl Into<Vec<u8>>) -> Self {
+        self.segment(Segment::Data(DataSegment::new(data.into())))
+    }
+
+    pub fn segment(mut self, segment: Segment) -> Self {
+        self.segments.push(segment);
+        self
+    }
+
+    pub fn build(self) -> PaddedEPath {
+        PaddedEPath::new(self.segments)
+    }
+
+    pub fn build_sized(self) -> SizedEPath {
+        SizedEPath::new(self.build())
+    }
+}
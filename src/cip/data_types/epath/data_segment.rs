@@ -0,0 +1,142 @@
+use bytes::{Buf, BufMut};
+
+use crate::{
+    cip::data_types::{Byte, epath::SegmentType},
+    common::binary::{BinaryError, FromBytes, ToBytes},
+};
+
+const SIMPLE_DATA: u8 = 0x00;
+
+/// Simple data segment, ex: the configuration data of a Forward_Open for the configuration
+/// assembly. The data is padded to a whole number of words when encoded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataSegment {
+    data: Vec<u8>,
+}
+
+impl DataSegment {
+    pub const MIN_LEN: usize = 2;
+
+    pub fn new(data: Vec<u8>) -> Self {
+        Self { data }
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    pub fn into_data(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+impl FromBytes for DataSegment {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        if buffer.remaining() < Self::MIN_LEN {
+            return Err(BinaryError::Truncated {
+                expected: Self::MIN_LEN,
+                actual: buffer.remaining(),
+            });
+        }
+
+        let first_byte = Byte::decode(buffer)?;
+        let segment_type = SegmentType::from(first_byte.get_bits(5, 7));
+
+        if segment_type != SegmentType::DataSegment {
+            return Err(BinaryError::InvalidData {
+                message: "Invalid segment type".to_string(),
+                expected: SegmentType::DataSegment.to_string(),
+                actual: segment_type.to_string(),
+            });
+        }
+
+        let subtype = first_byte.get_bits(0, 4);
+        if subtype != SIMPLE_DATA {
+            return Err(BinaryError::InvalidData {
+                message: "Unsupported data segment subtype".to_string(),
+                expected: "Simple data segment".to_string(),
+                actual: format!("{:#04X}", first_byte.value()),
+            });
+        }
+
+        let data_len = buffer.get_u8() as usize * 2;
+        if buffer.remaining() < data_len {
+            return Err(BinaryError::Truncated {
+                expected: data_len,
+                actual: buffer.remaining(),
+            });
+        }
+
+        Ok(Self {
+            data: buffer.copy_to_bytes(data_len).to_vec(),
+        })
+    }
+}
+
+impl ToBytes for DataSegment {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < self.encoded_len() {
+            return Err(BinaryError::BufferTooSmall {
+                expected: self.encoded_len(),
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        let words = self.data.len().div_ceil(2);
+        if words > u8::MAX as usize {
+            return Err(BinaryError::InvalidData {
+                message: "Simple data segment too long".to_string(),
+                expected: format!("At most {} words", u8::MAX),
+                actual: format!("{} words", words),
+            });
+        }
+
+        let mut first_byte = Byte::new(0);
+        first_byte.set_bits(5, 7, SegmentType::DataSegment as u8);
+        first_byte.set_bits(0, 4, SIMPLE_DATA);
+        buffer.put_u8(first_byte.value());
+        buffer.put_u8(words as u8);
+        buffer.put_slice(&self.data);
+        if !self.data.len().is_multiple_of(2) {
+            buffer.put_u8(0);
+        }
+
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        2 + self.data.len().div_ceil(2) * 2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::{Bytes, BytesMut};
+
+    #[test]
+    fn simple_data_segment_round_trip_success() {
+        let raw_bytes: [u8; 6] = [
+            0x80, // Simple data segment
+            0x02, // 2 words
+            0x01, 0x02, 0x03, 0x04,
+        ];
+
+        let mut cursor = Bytes::copy_from_slice(&raw_bytes);
+        let decoded = DataSegment::decode(&mut cursor).expect("Failed to decode");
+        assert_eq!(decoded.data(), &[0x01, 0x02, 0x03, 0x04]);
+
+        let mut buffer = BytesMut::with_capacity(decoded.encoded_len());
+        decoded.encode(&mut buffer).expect("Failed to encode");
+        assert_eq!(buffer.as_ref(), &raw_bytes);
+    }
+
+    #[test]
+    fn decode_ansi_symbol_returns_error() {
+        let mut cursor = Bytes::from_static(&[0x91, 0x02, 0x41, 0x42]);
+
+        let result = DataSegment::decode(&mut cursor);
+
+        assert!(matches!(result, Err(BinaryError::InvalidData { .. })));
+    }
+}
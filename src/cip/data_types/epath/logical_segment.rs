@@ -0,0 +1,269 @@
+use bytes::{Buf, BufMut};
+
+use crate::{
+    cip::data_types::{Byte, epath::SegmentType},
+    common::binary::{BinaryError, FromBytes, ToBytes},
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogicalType {
+    ClassId = 0b000,
+    InstanceId = 0b001,
+    MemberId = 0b010,
+    ConnectionPoint = 0b011,
+    AttributeId = 0b100,
+    Special = 0b101,
+    ServiceId = 0b110,
+    Reserved = 0b111,
+}
+
+impl From<u8> for LogicalType {
+    fn from(value: u8) -> Self {
+        match value & 0b111 {
+            0b000 => Self::ClassId,
+            0b001 => Self::InstanceId,
+            0b010 => Self::MemberId,
+            0b011 => Self::ConnectionPoint,
+            0b100 => Self::AttributeId,
+            0b101 => Self::Special,
+            0b110 => Self::ServiceId,
+            _ => Self::Reserved,
+        }
+    }
+}
+
+impl std::fmt::Display for LogicalType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LogicalFormat {
+    Bits8 = 0b00,
+    Bits16 = 0b01,
+    Bits32 = 0b10,
+}
+
+impl LogicalFormat {
+    fn for_value(value: u32) -> Self {
+        if value <= u8::MAX as u32 {
+            Self::Bits8
+        } else if value <= u16::MAX as u32 {
+            Self::Bits16
+        } else {
+            Self::Bits32
+        }
+    }
+
+    /// Bytes following the segment type byte, including the pad byte of the padded EPATH.
+    fn value_len(self) -> usize {
+        match self {
+            Self::Bits8 => 1,
+            Self::Bits16 => 3,
+            Self::Bits32 => 5,
+        }
+    }
+}
+
+/// Logical segment addressing a class, instance, attribute, member or connection point.
+/// Values are encoded with the smallest format that fits, using the padded EPATH layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogicalSegment {
+    pub logical_type: LogicalType,
+    pub value: u32,
+}
+
+impl LogicalSegment {
+    pub const MIN_LEN: usize = 2;
+
+    pub fn class_id(class_id: u16) -> Self {
+        Self {
+            logical_type: LogicalType::ClassId,
+            value: class_id as u32,
+        }
+    }
+
+    pub fn instance_id(instance_id: u32) -> Self {
+        Self {
+            logical_type: LogicalType::InstanceId,
+            value: instance_id,
+        }
+    }
+
+    pub fn attribute_id(attribute_id: u16) -> Self {
+        Self {
+            logical_type: LogicalType::AttributeId,
+            value: attribute_id as u32,
+        }
+    }
+
+    pub fn member_id(member_id: u32) -> Self {
+        Self {
+            logical_type: LogicalType::MemberId,
+            value: member_id,
+        }
+    }
+
+    pub fn connection_point(connection_point: u32) -> Self {
+        Self {
+            logical_type: LogicalType::ConnectionPoint,
+            value: connection_point,
+        }
+    }
+}
+
+impl FromBytes for LogicalSegment {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        if buffer.remaining() < Self::MIN_LEN {
+            return Err(BinaryError::Truncated {
+                expected: Self::MIN_LEN,
+                actual: buffer.remaining(),
+            });
+        }
+
+        let first_byte = Byte::decode(buffer)?;
+        let segment_type = SegmentType::from(first_byte.get_bits(5, 7));
+
+        if segment_type != SegmentType::LogicalSegment {
+            return Err(BinaryError::InvalidData {
+                message: "Invalid segment type".to_string(),
+                expected: SegmentType::LogicalSegment.to_string(),
+                actual: segment_type.to_string(),
+            });
+        }
+
+        let logical_type = LogicalType::from(first_byte.get_bits(2, 4));
+        if matches!(
+            logical_type,
+            LogicalType::Special | LogicalType::ServiceId | LogicalType::Reserved
+        ) {
+            return Err(BinaryError::InvalidData {
+                message: "Unsupported logical segment type".to_string(),
+                expected: "Class, instance, member, connection point or attribute".to_string(),
+                actual: logical_type.to_string(),
+            });
+        }
+
+        let format = match first_byte.get_bits(0, 1) {
+            0b00 => LogicalFormat::Bits8,
+            0b01 => LogicalFormat::Bits16,
+            0b10 => LogicalFormat::Bits32,
+            format => {
+                return Err(BinaryError::InvalidData {
+                    message: "Reserved logical segment format".to_string(),
+                    expected: "8, 16 or 32 bit format".to_string(),
+                    actual: format!("{:#04b}", format),
+                });
+            }
+        };
+
+        if buffer.remaining() < format.value_len() {
+            return Err(BinaryError::Truncated {
+                expected: format.value_len(),
+                actual: buffer.remaining(),
+            });
+        }
+
+        let value = match format {
+            LogicalFormat::Bits8 => buffer.get_u8() as u32,
+            LogicalFormat::Bits16 => {
+                buffer.advance(1);
+                buffer.get_u16_le() as u32
+            }
+            LogicalFormat::Bits32 => {
+                buffer.advance(1);
+                buffer.get_u32_le()
+            }
+        };
+
+        Ok(Self {
+            logical_type,
+            value,
+        })
+    }
+}
+
+impl ToBytes for LogicalSegment {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < self.encoded_len() {
+            return Err(BinaryError::BufferTooSmall {
+                expected: self.encoded_len(),
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        let format = LogicalFormat::for_value(self.value);
+        let mut first_byte = Byte::new(0);
+        first_byte.set_bits(5, 7, SegmentType::LogicalSegment as u8);
+        first_byte.set_bits(2, 4, self.logical_type as u8);
+        first_byte.set_bits(0, 1, format as u8);
+        buffer.put_u8(first_byte.value());
+
+        match format {
+            LogicalFormat::Bits8 => buffer.put_u8(self.value as u8),
+            LogicalFormat::Bits16 => {
+                buffer.put_u8(0);
+                buffer.put_u16_le(self.value as u16);
+            }
+            LogicalFormat::Bits32 => {
+                buffer.put_u8(0);
+                buffer.put_u32_le(self.value);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        1 + LogicalFormat::for_value(self.value).value_len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::{Bytes, BytesMut};
+
+    #[test]
+    fn decode_and_encode_8_bit_class_symmetry() {
+        let raw_bytes: [u8; 2] = [
+            0x20, // Logical | Class ID | 8-bit
+            0xF5, // Class 0xF5
+        ];
+
+        let mut cursor = Bytes::copy_from_slice(&raw_bytes);
+        let decoded = LogicalSegment::decode(&mut cursor).expect("Failed to decode");
+        assert_eq!(decoded, LogicalSegment::class_id(0xF5));
+
+        let mut buffer = BytesMut::with_capacity(decoded.encoded_len());
+        decoded.encode(&mut buffer).expect("Failed to encode");
+        assert_eq!(buffer.as_ref(), &raw_bytes);
+    }
+
+    #[test]
+    fn decode_and_encode_16_bit_instance_symmetry() {
+        let raw_bytes: [u8; 4] = [
+            0x25, // Logical | Instance ID | 16-bit
+            0x00, // Pad
+            0x34, 0x12, // Instance 0x1234
+        ];
+
+        let mut cursor = Bytes::copy_from_slice(&raw_bytes);
+        let decoded = LogicalSegment::decode(&mut cursor).expect("Failed to decode");
+        assert_eq!(decoded, LogicalSegment::instance_id(0x1234));
+
+        let mut buffer = BytesMut::with_capacity(decoded.encoded_len());
+        decoded.encode(&mut buffer).expect("Failed to encode");
+        assert_eq!(buffer.as_ref(), &raw_bytes);
+    }
+
+    #[test]
+    fn decode_reserved_format_returns_error() {
+        let mut cursor = Bytes::from_static(&[0x33, 0x00]);
+
+        let result = LogicalSegment::decode(&mut cursor);
+
+        assert!(matches!(result, Err(BinaryError::InvalidData { .. })));
+    }
+}
@@ -0,0 +1,197 @@
+use bytes::{Buf, BufMut};
+
+use crate::{
+    cip::data_types::{Byte, epath::SegmentType},
+    common::binary::{BinaryError, FromBytes, ToBytes},
+};
+
+const SCHEDULE: u8 = 0x01;
+const FIXED_TAG: u8 = 0x02;
+const PRODUCTION_INHIBIT_TIME_MS: u8 = 0x03;
+const SAFETY: u8 = 0x10;
+const PRODUCTION_INHIBIT_TIME_US: u8 = 0x11;
+
+/// Subtypes with this bit set are followed by their data size in 16-bit words.
+const SIZED_SUBTYPE: u8 = 0x10;
+
+/// Network segment of a connection path, carrying network specific connection parameters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetworkSegment {
+    Schedule(u8),
+    FixedTag(u8),
+    /// Production inhibit time in milliseconds.
+    ProductionInhibitTime(u8),
+    /// Production inhibit time in microseconds.
+    ProductionInhibitTimeMicros(u32),
+    /// Safety segment data, padded to a whole number of words when encoded.
+    Safety(Vec<u8>),
+}
+
+impl NetworkSegment {
+    pub const MIN_LEN: usize = 2;
+
+    /// Production inhibit time in microseconds, for either time segment.
+    pub fn production_inhibit_us(&self) -> Option<u32> {
+        match self {
+            Self::ProductionInhibitTime(ms) => Some(*ms as u32 * 1000),
+            Self::ProductionInhibitTimeMicros(us) => Some(*us),
+            _ => None,
+        }
+    }
+
+    fn subtype(&self) -> u8 {
+        match self {
+            Self::Schedule(_) => SCHEDULE,
+            Self::FixedTag(_) => FIXED_TAG,
+            Self::ProductionInhibitTime(_) => PRODUCTION_INHIBIT_TIME_MS,
+            Self::ProductionInhibitTimeMicros(_) => PRODUCTION_INHIBIT_TIME_US,
+            Self::Safety(_) => SAFETY,
+        }
+    }
+
+    /// Data words following the size byte of the sized subtypes.
+    fn data_words(&self) -> usize {
+        match self {
+            Self::ProductionInhibitTimeMicros(_) => 2,
+            Self::Safety(data) => data.len().div_ceil(2),
+            _ => 0,
+        }
+    }
+}
+
+impl FromBytes for NetworkSegment {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        if buffer.remaining() < Self::MIN_LEN {
+            return Err(BinaryError::Truncated {
+                expected: Self::MIN_LEN,
+                actual: buffer.remaining(),
+            });
+        }
+
+        let first_byte = Byte::decode(buffer)?;
+        let segment_type = SegmentType::from(first_byte.get_bits(5, 7));
+
+        if segment_type != SegmentType::NetworkSegment {
+            return Err(BinaryError::InvalidData {
+                message: "Invalid segment type".to_string(),
+                expected: SegmentType::NetworkSegment.to_string(),
+                actual: segment_type.to_string(),
+            });
+        }
+
+        let subtype = first_byte.get_bits(0, 4);
+        if subtype & SIZED_SUBTYPE == 0 {
+            let value = buffer.get_u8();
+            return match subtype {
+                SCHEDULE => Ok(Self::Schedule(value)),
+                FIXED_TAG => Ok(Self::FixedTag(value)),
+                PRODUCTION_INHIBIT_TIME_MS => Ok(Self::ProductionInhibitTime(value)),
+                _ => Err(unsupported_subtype(subtype)),
+            };
+        }
+
+        let data_len = buffer.get_u8() as usize * 2;
+        if buffer.remaining() < data_len {
+            return Err(BinaryError::Truncated {
+                expected: data_len,
+                actual: buffer.remaining(),
+            });
+        }
+
+        match subtype {
+            PRODUCTION_INHIBIT_TIME_US if data_len == 4 => {
+                Ok(Self::ProductionInhibitTimeMicros(buffer.get_u32_le()))
+            }
+            PRODUCTION_INHIBIT_TIME_US => Err(BinaryError::InvalidData {
+                message: "Invalid production inhibit time size".to_string(),
+                expected: "2 words".to_string(),
+                actual: format!("{} words", data_len / 2),
+            }),
+            SAFETY => Ok(Self::Safety(buffer.copy_to_bytes(data_len).to_vec())),
+            _ => Err(unsupported_subtype(subtype)),
+        }
+    }
+}
+
+fn unsupported_subtype(subtype: u8) -> BinaryError {
+    BinaryError::InvalidData {
+        message: "Unsupported network segment subtype".to_string(),
+        expected: "Schedule, fixed tag, production inhibit time or safety".to_string(),
+        actual: format!("{:#04X}", subtype),
+    }
+}
+
+impl ToBytes for NetworkSegment {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < self.encoded_len() {
+            return Err(BinaryError::BufferTooSmall {
+                expected: self.encoded_len(),
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        let mut first_byte = Byte::new(0);
+        first_byte.set_bits(5, 7, SegmentType::NetworkSegment as u8);
+        first_byte.set_bits(0, 4, self.subtype());
+        buffer.put_u8(first_byte.value());
+
+        match self {
+            Self::Schedule(value) | Self::FixedTag(value) | Self::ProductionInhibitTime(value) => {
+                buffer.put_u8(*value)
+            }
+            Self::ProductionInhibitTimeMicros(us) => {
+                buffer.put_u8(self.data_words() as u8);
+                buffer.put_u32_le(*us);
+            }
+            Self::Safety(data) => {
+                buffer.put_u8(self.data_words() as u8);
+                buffer.put_slice(data);
+                if !data.len().is_multiple_of(2) {
+                    buffer.put_u8(0);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        2 + self.data_words() * 2
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::{Bytes, BytesMut};
+
+    #[test]
+    fn production_inhibit_time_segments_round_trip_success() {
+        let raw_bytes: [u8; 8] = [
+            0x43, 0x0A, // Production inhibit time 10 ms
+            0x51, 0x02, // Production inhibit time in microseconds, 2 words
+            0xA0, 0x86, 0x01, 0x00, // 100000 us
+        ];
+
+        let mut cursor = Bytes::copy_from_slice(&raw_bytes);
+        let milliseconds = NetworkSegment::decode(&mut cursor).expect("Failed to decode");
+        let microseconds = NetworkSegment::decode(&mut cursor).expect("Failed to decode");
+
+        assert_eq!(milliseconds, NetworkSegment::ProductionInhibitTime(10));
+        assert_eq!(microseconds.production_inhibit_us(), Some(100_000));
+
+        let mut buffer = BytesMut::with_capacity(raw_bytes.len());
+        milliseconds.encode(&mut buffer).expect("Failed to encode");
+        microseconds.encode(&mut buffer).expect("Failed to encode");
+        assert_eq!(buffer.as_ref(), &raw_bytes);
+    }
+
+    #[test]
+    fn decode_truncated_safety_segment_returns_error() {
+        let mut cursor = Bytes::from_static(&[0x50, 0x03, 0x01, 0x02]);
+
+        let result = NetworkSegment::decode(&mut cursor);
+
+        assert!(matches!(result, Err(BinaryError::Truncated { .. })));
+    }
+}
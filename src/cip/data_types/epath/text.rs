@@ -0,0 +1,282 @@
+//! Textual EPATH notation: `/` separated elements where `port,link` is a port segment and
+//! `@class` starts a logical path followed by the instance, attribute and member numbers,
+//! ex: "1,10.0.0.5/@4/100/3". Paths are also accepted as hex bytes, ex: "20 04 24 64 30 03".
+
+use std::{fmt, str::FromStr};
+
+use bytes::{Bytes, BytesMut};
+
+use crate::{
+    cip::data_types::{
+        epath::{
+            LinkAddress, LogicalSegment, LogicalType, PaddedEPath, PortIdentifier, PortSegment,
+            Segment, SizedEPath,
+        },
+        short_string::ShortString,
+    },
+    common::binary::{BinaryError, FromBytes, ToBytes},
+};
+
+fn invalid_path(message: &str, actual: &str) -> BinaryError {
+    BinaryError::InvalidData {
+        message: message.to_string(),
+        expected: "EPATH text, ex: 1,10.0.0.5/@4/100/3".to_string(),
+        actual: actual.to_string(),
+    }
+}
+
+/// Logical type written as a bare number after `previous`.
+fn next_in_sequence(previous: LogicalType) -> Option<LogicalType> {
+    match previous {
+        LogicalType::ClassId => Some(LogicalType::InstanceId),
+        LogicalType::InstanceId => Some(LogicalType::AttributeId),
+        LogicalType::AttributeId => Some(LogicalType::MemberId),
+        _ => None,
+    }
+}
+
+fn parse_number(text: &str) -> Option<u32> {
+    match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => u32::from_str_radix(hex, 16).ok(),
+        None => text.parse().ok(),
+    }
+}
+
+fn write_hex(f: &mut fmt::Formatter<'_>, encoded: &[u8]) -> fmt::Result {
+    for (i, byte) in encoded.iter().enumerate() {
+        if i > 0 {
+            write!(f, " ")?;
+        }
+        write!(f, "{:02X}", byte)?;
+    }
+    Ok(())
+}
+
+fn write_encoded<T: ToBytes>(f: &mut fmt::Formatter<'_>, value: &T) -> fmt::Result {
+    let mut buffer = BytesMut::with_capacity(value.encoded_len());
+    value.encode(&mut buffer).map_err(|_| fmt::Error)?;
+    write_hex(f, &buffer)
+}
+
+impl fmt::Display for PortSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.port {
+            PortIdentifier::Default(port) => write!(f, "{},", port)?,
+            PortIdentifier::Extended(port) => write!(f, "{},", port)?,
+        }
+
+        match &self.link_address {
+            LinkAddress::Default(link) => write!(f, "{}", link),
+            LinkAddress::Extended(link) => write!(f, "{}", link.value()),
+        }
+    }
+}
+
+impl fmt::Display for LogicalSegment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.logical_type {
+            LogicalType::ClassId => write!(f, "@{}", self.value),
+            _ => write_encoded(f, self),
+        }
+    }
+}
+
+impl fmt::Display for Segment {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Segment::Port(port_segment) => write!(f, "{}", port_segment),
+            Segment::Logical(logical_segment) => write!(f, "{}", logical_segment),
+            Segment::Network(network_segment) => write_encoded(f, network_segment),
+            Segment::Data(data_segment) => write_encoded(f, data_segment),
+        }
+    }
+}
+
+/// Written in the text notation when possible, as hex bytes otherwise.
+impl fmt::Display for PaddedEPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut elements = Vec::with_capacity(self.segments().len());
+        let mut expected = None;
+
+        for segment in self.segments() {
+            match segment {
+                Segment::Port(port_segment) => {
+                    elements.push(port_segment.to_string());
+                    expected = None;
+                }
+                Segment::Logical(logical) if logical.logical_type == LogicalType::ClassId => {
+                    elements.push(format!("@{}", logical.value));
+                    expected = next_in_sequence(LogicalType::ClassId);
+                }
+                Segment::Logical(logical) if Some(logical.logical_type) == expected => {
+                    elements.push(logical.value.to_string());
+                    expected = next_in_sequence(logical.logical_type);
+                }
+                Segment::Logical(_) | Segment::Network(_) | Segment::Data(_) => {
+                    return write_encoded(f, self);
+                }
+            }
+        }
+
+        write!(f, "{}", elements.join("/"))
+    }
+}
+
+impl fmt::Display for SizedEPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.path())
+    }
+}
+
+fn parse_port(element: &str) -> Result<Segment, BinaryError> {
+    let (port, link) = element.split_once(',').unwrap_or_default();
+    let port = parse_number(port.trim())
+        .and_then(|port| u16::try_from(port).ok())
+        .ok_or_else(|| invalid_path("Invalid port number", element))?;
+
+    let link = link.trim();
+    let link_address = match parse_number(link) {
+        Some(number) => u8::try_from(number)
+            .map(LinkAddress::Default)
+            .map_err(|_| invalid_path("Invalid link address", element))?,
+        None if !link.is_empty() && link.is_ascii() && link.len() <= ShortString::MAX_LEN => {
+            LinkAddress::from(link)
+        }
+        None => return Err(invalid_path("Invalid link address", element)),
+    };
+
+    Ok(Segment::Port(PortSegment {
+        port: port.into(),
+        link_address,
+    }))
+}
+
+fn parse_text(text: &str) -> Result<PaddedEPath, BinaryError> {
+    let mut segments = Vec::new();
+    let mut expected = None;
+
+    for element in text.split('/').map(str::trim) {
+        if element.contains(',') {
+            segments.push(parse_port(element)?);
+            expected = None;
+            continue;
+        }
+
+        let (logical_type, value) = match element.strip_prefix('@') {
+            Some(class) => (LogicalType::ClassId, class),
+            None => (
+                expected.ok_or_else(|| invalid_path("Value without a class", element))?,
+                element,
+            ),
+        };
+        let value = parse_number(value).ok_or_else(|| invalid_path("Invalid number", element))?;
+
+        segments.push(Segment::Logical(LogicalSegment {
+            logical_type,
+            value,
+        }));
+        expected = next_in_sequence(logical_type);
+    }
+
+    Ok(PaddedEPath::new(segments))
+}
+
+fn parse_hex(text: &str) -> Result<PaddedEPath, BinaryError> {
+    let bytes = text
+        .split_whitespace()
+        .map(|byte| match byte.len() {
+            2 => u8::from_str_radix(byte, 16).ok(),
+            _ => None,
+        })
+        .collect::<Option<Vec<u8>>>()
+        .ok_or_else(|| invalid_path("Invalid hex bytes", text))?;
+
+    let mut buffer = Bytes::from(bytes);
+    PaddedEPath::decode(&mut buffer)
+}
+
+impl FromStr for PaddedEPath {
+    type Err = BinaryError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let text = text.trim();
+        if text.is_empty() {
+            return Ok(Self::new(Vec::new()));
+        }
+
+        if text.contains(['/', ',', '@']) {
+            parse_text(text)
+        } else {
+            parse_hex(text)
+        }
+    }
+}
+
+impl FromStr for SizedEPath {
+    type Err = BinaryError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        text.parse().map(Self::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cip::data_types::epath::EPathBuilder;
+
+    #[test]
+    fn text_and_builder_paths_match() {
+        let built = EPathBuilder::new()
+            .port(1, "10.0.0.5")
+            .class(0x04)
+            .instance(100)
+            .attribute(3)
+            .build();
+
+        let parsed: PaddedEPath = "1,10.0.0.5/@4/100/3".parse().expect("Failed to parse");
+
+        assert_eq!(parsed, built);
+        assert_eq!(built.to_string(), "1,10.0.0.5/@4/100/3");
+    }
+
+    #[test]
+    fn hex_path_parses_and_displays_as_text() {
+        let parsed: PaddedEPath = "20 04 24 64 30 03".parse().expect("Failed to parse");
+
+        assert_eq!(
+            parsed,
+            EPathBuilder::new()
+                .class(4)
+                .instance(100)
+                .attribute(3)
+                .build()
+        );
+        assert_eq!(parsed.to_string(), "@4/100/3");
+        assert_eq!(
+            "1,0/@0x06/1"
+                .parse::<PaddedEPath>()
+                .expect("Failed to parse"),
+            EPathBuilder::new().port(1, 0).class(6).instance(1).build()
+        );
+    }
+
+    #[test]
+    fn path_without_text_form_displays_as_hex() {
+        let path = EPathBuilder::new()
+            .class(4)
+            .instance(1)
+            .connection_point(100)
+            .build();
+
+        assert_eq!(path.to_string(), "20 04 24 01 2C 64");
+        assert_eq!(path.to_string().parse::<PaddedEPath>(), Ok(path));
+    }
+
+    #[test]
+    fn value_without_class_returns_error() {
+        let result = "1,0/100".parse::<PaddedEPath>();
+
+        assert!(matches!(result, Err(BinaryError::InvalidData { .. })));
+    }
+}
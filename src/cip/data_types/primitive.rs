@@ -9,7 +9,12 @@ macro_rules! impl_cip_primitive {
 
     (@gen $name:ident, $type:ty, $get_fn:ident, $put_fn:ident) => {
         #[repr(transparent)]
-        #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+        #[cfg_attr(
+            feature = "serde",
+            derive(serde::Serialize, serde::Deserialize),
+            serde(transparent)
+        )]
         #[allow(dead_code)]
         pub struct $name($type);
 
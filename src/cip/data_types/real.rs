@@ -0,0 +1,103 @@
+use bytes::{Buf, BufMut};
+
+use crate::common::binary::{BinaryError, FromBytes, ToBytes};
+
+macro_rules! impl_cip_real {
+    ($name:ident, $type:ty, $get_fn:ident, $put_fn:ident) => {
+        /// IEEE-754 floating point value, encoded little endian.
+        #[repr(transparent)]
+        #[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Default)]
+        #[cfg_attr(
+            feature = "serde",
+            derive(serde::Serialize, serde::Deserialize),
+            serde(transparent)
+        )]
+        pub struct $name($type);
+
+        impl $name {
+            pub const LEN: usize = std::mem::size_of::<$type>();
+
+            pub fn new(value: $type) -> Self {
+                Self(value)
+            }
+
+            pub fn value(&self) -> $type {
+                self.0
+            }
+        }
+
+        impl From<$type> for $name {
+            fn from(value: $type) -> Self {
+                Self(value)
+            }
+        }
+
+        impl FromBytes for $name {
+            fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+                if buffer.remaining() < Self::LEN {
+                    return Err(BinaryError::Truncated {
+                        expected: Self::LEN,
+                        actual: buffer.remaining(),
+                    });
+                }
+
+                Ok(Self(buffer.$get_fn()))
+            }
+        }
+
+        impl ToBytes for $name {
+            fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+                if buffer.remaining_mut() < Self::LEN {
+                    return Err(BinaryError::BufferTooSmall {
+                        expected: Self::LEN,
+                        actual: buffer.remaining_mut(),
+                    });
+                }
+
+                buffer.$put_fn(self.0);
+                Ok(())
+            }
+
+            fn encoded_len(&self) -> usize {
+                Self::LEN
+            }
+        }
+    };
+}
+
+impl_cip_real!(Real, f32, get_f32_le, put_f32_le);
+impl_cip_real!(LReal, f64, get_f64_le, put_f64_le);
+
+#[cfg(test)]
+mod tests {
+    use bytes::{Bytes, BytesMut};
+
+    use super::*;
+
+    #[test]
+    fn real_round_trip_success() {
+        let mut buffer = BytesMut::new();
+        Real::new(1.5)
+            .encode(&mut buffer)
+            .expect("Failed to encode");
+        assert_eq!(buffer.as_ref(), &[0x00, 0x00, 0xC0, 0x3F]);
+
+        let decoded = Real::decode(&mut buffer.freeze()).expect("Failed to decode");
+        assert_eq!(decoded.value(), 1.5);
+    }
+
+    #[test]
+    fn lreal_decode_truncated_returns_error() {
+        let mut buffer = Bytes::from_static(&[0x00, 0x00, 0x00, 0x00]);
+
+        let result = LReal::decode(&mut buffer);
+
+        assert!(matches!(
+            result,
+            Err(BinaryError::Truncated {
+                expected: 8,
+                actual: 4
+            })
+        ));
+    }
+}
@@ -41,12 +41,14 @@ impl ShortString {
         self.0[0] as usize
     }
 
+    /// Returns the characters up to the first invalid UTF-8 sequence, which only strings
+    /// built with [`from_bytes`](Self::from_bytes) can contain.
     pub fn value(&self) -> &str {
-        if self.len() == 0 {
-            return "";
+        let bytes = &self.0[1..self.len() + 1];
+        match std::str::from_utf8(bytes) {
+            Ok(value) => value,
+            Err(error) => std::str::from_utf8(&bytes[..error.valid_up_to()]).unwrap_or_default(),
         }
-
-        unsafe { std::str::from_utf8_unchecked(&self.0[1..self.len() + 1]) }
     }
 }
 
@@ -77,6 +79,14 @@ impl FromBytes for ShortString {
         value_bytes[0] = len as u8;
         buffer.copy_to_slice(&mut value_bytes[1..len + 1]);
 
+        if let Err(error) = std::str::from_utf8(&value_bytes[1..len + 1]) {
+            return Err(BinaryError::InvalidData {
+                message: "Invalid SHORT_STRING characters".to_string(),
+                expected: "UTF-8".to_string(),
+                actual: error.to_string(),
+            });
+        }
+
         Ok(Self(value_bytes))
     }
 }
@@ -97,3 +107,29 @@ impl ToBytes for ShortString {
         self.0[0] as usize + 1
     }
 }
+
+#[cfg(feature = "serde")]
+crate::common::serde_text::impl_serde_string!([] ShortString);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::Bytes;
+
+    #[test]
+    fn decode_invalid_utf8_returns_error() {
+        let mut buffer = Bytes::from_static(&[0x03, b'a', 0xFF, b'b']);
+
+        let result = ShortString::decode(&mut buffer);
+
+        assert!(matches!(result, Err(BinaryError::InvalidData { .. })));
+    }
+
+    #[test]
+    fn value_of_invalid_raw_bytes_stops_at_invalid_sequence() {
+        let string = ShortString::from_bytes(&[b'o', b'k', 0xC3]);
+
+        assert_eq!(string.value(), "ok");
+        assert_eq!(string.len(), 3);
+    }
+}
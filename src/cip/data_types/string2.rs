@@ -0,0 +1,165 @@
+use bytes::{Buf, BufMut};
+
+use crate::{
+    cip::data_types::ascii::StringLen,
+    common::binary::{BinaryError, FromBytes, ToBytes},
+};
+
+/// UTF-16 (UCS-2) string preceded by its length in characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Utf16String<L: StringLen, const N: usize> {
+    len: L,
+    characters: [u16; N],
+}
+
+/// STRING2: UINT length followed by 16-bit characters.
+pub type String2<const N: usize> = Utf16String<u16, N>;
+
+impl<L: StringLen, const N: usize> Utf16String<L, N> {
+    const LEN_SIZE: usize = std::mem::size_of::<L>();
+    const CHAR_SIZE: usize = 2;
+    pub const MAX_LEN: usize = N;
+
+    /// Characters outside the Basic Multilingual Plane take two code units.
+    /// Input past `MAX_LEN` code units is truncated.
+    pub fn new(value: &str) -> Self {
+        let mut characters = [0u16; N];
+        let mut len = L::new();
+
+        for (i, c) in value.encode_utf16().enumerate() {
+            if i >= Self::MAX_LEN {
+                break;
+            }
+            characters[i] = c;
+            len = len.plus_one();
+        }
+
+        Self { len, characters }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len.into()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn characters(&self) -> &[u16] {
+        &self.characters[..self.len()]
+    }
+
+    /// Decodes the characters, replacing unpaired surrogates.
+    pub fn value(&self) -> String {
+        String::from_utf16_lossy(self.characters())
+    }
+}
+
+impl<L: StringLen, const N: usize> Default for Utf16String<L, N> {
+    fn default() -> Self {
+        Self {
+            len: L::new(),
+            characters: [0u16; N],
+        }
+    }
+}
+
+impl<L: StringLen, const N: usize> From<&str> for Utf16String<L, N> {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<L: StringLen, const N: usize> FromBytes for Utf16String<L, N> {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        if buffer.remaining() < Self::LEN_SIZE {
+            return Err(BinaryError::Truncated {
+                expected: Self::LEN_SIZE,
+                actual: buffer.remaining(),
+            });
+        }
+
+        let len = L::decode(buffer)?;
+        if len.into() > N {
+            return Err(BinaryError::InvalidData {
+                message: "UTF-16 string length exceeds max".to_string(),
+                expected: N.to_string(),
+                actual: len.into().to_string(),
+            });
+        }
+
+        let bytes_len = len.into() * Self::CHAR_SIZE;
+        if buffer.remaining() < bytes_len {
+            return Err(BinaryError::Truncated {
+                expected: bytes_len,
+                actual: buffer.remaining(),
+            });
+        }
+
+        let mut characters = [0u16; N];
+        for character in characters.iter_mut().take(len.into()) {
+            *character = buffer.get_u16_le();
+        }
+
+        Ok(Self { len, characters })
+    }
+}
+
+impl<L: StringLen, const N: usize> ToBytes for Utf16String<L, N> {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < self.encoded_len() {
+            return Err(BinaryError::BufferTooSmall {
+                expected: self.encoded_len(),
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        L::encode(&self.len, buffer)?;
+        for character in self.characters() {
+            buffer.put_u16_le(*character);
+        }
+
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        Self::LEN_SIZE + self.len() * Self::CHAR_SIZE
+    }
+}
+
+#[cfg(feature = "serde")]
+crate::common::serde_text::impl_serde_string!([L: StringLen, const N: usize] Utf16String<L, N>);
+
+#[cfg(test)]
+mod tests {
+    use bytes::{Bytes, BytesMut};
+
+    use super::*;
+
+    #[test]
+    fn string2_round_trip_success() {
+        let raw_bytes = [
+            0x02, 0x00, // Length
+            0x4F, 0x00, // 'O'
+            0xB4, 0x03, // 'δ'
+        ];
+
+        let decoded = String2::<8>::decode(&mut Bytes::copy_from_slice(&raw_bytes))
+            .expect("Failed to decode");
+        assert_eq!(decoded.value(), "Oδ");
+        assert_eq!(decoded, String2::<8>::new("Oδ"));
+
+        let mut buffer = BytesMut::new();
+        decoded.encode(&mut buffer).expect("Failed to encode");
+        assert_eq!(buffer.as_ref(), &raw_bytes);
+    }
+
+    #[test]
+    fn string2_length_exceeds_max_returns_error() {
+        let mut buffer = Bytes::from_static(&[0x03, 0x00, 0x41, 0x00, 0x42, 0x00, 0x43, 0x00]);
+
+        let result = String2::<2>::decode(&mut buffer);
+
+        assert!(matches!(result, Err(BinaryError::InvalidData { .. })));
+    }
+}
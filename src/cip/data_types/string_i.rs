@@ -0,0 +1,481 @@
+use bytes::{Buf, BufMut};
+
+use crate::{
+    cip::data_types::{
+        short_string::ShortString, string::CipString, string_n::StringN, string2::String2,
+    },
+    common::binary::{BinaryError, FromBytes, ToBytes},
+};
+
+/// ISO 639-2/T language code, three lowercase letters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LanguageCode([u8; Self::LEN]);
+
+impl LanguageCode {
+    pub const LEN: usize = 3;
+    pub const ENGLISH: Self = Self(*b"eng");
+
+    pub fn new(code: &str) -> Self {
+        let mut bytes = [b' '; Self::LEN];
+        for (i, c) in code.bytes().take(Self::LEN).enumerate() {
+            bytes[i] = c.to_ascii_lowercase();
+        }
+
+        Self(bytes)
+    }
+
+    /// Returns `None` unless `code` is exactly three ASCII letters.
+    pub fn parse(code: &str) -> Option<Self> {
+        let bytes: [u8; Self::LEN] = code.as_bytes().try_into().ok()?;
+        Self::from_letters(bytes)
+    }
+
+    fn from_letters(bytes: [u8; Self::LEN]) -> Option<Self> {
+        bytes
+            .iter()
+            .all(u8::is_ascii_alphabetic)
+            .then(|| Self(bytes.map(|c| c.to_ascii_lowercase())))
+    }
+
+    pub fn as_str(&self) -> &str {
+        std::str::from_utf8(&self.0).unwrap_or("")
+    }
+}
+
+impl Default for LanguageCode {
+    fn default() -> Self {
+        Self::ENGLISH
+    }
+}
+
+impl std::fmt::Display for LanguageCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl std::str::FromStr for LanguageCode {
+    type Err = BinaryError;
+
+    fn from_str(code: &str) -> Result<Self, Self::Err> {
+        Self::parse(code).ok_or_else(|| BinaryError::InvalidData {
+            message: "Invalid language code".to_string(),
+            expected: "three ASCII letters".to_string(),
+            actual: code.to_string(),
+        })
+    }
+}
+
+impl FromBytes for LanguageCode {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        if buffer.remaining() < Self::LEN {
+            return Err(BinaryError::Truncated {
+                expected: Self::LEN,
+                actual: buffer.remaining(),
+            });
+        }
+
+        let mut code = [0u8; Self::LEN];
+        buffer.copy_to_slice(&mut code);
+        Self::from_letters(code).ok_or_else(|| BinaryError::InvalidData {
+            message: "Invalid language code".to_string(),
+            expected: "three ASCII letters".to_string(),
+            actual: format!("{:02x?}", code),
+        })
+    }
+}
+
+impl ToBytes for LanguageCode {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < Self::LEN {
+            return Err(BinaryError::BufferTooSmall {
+                expected: Self::LEN,
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        buffer.put_slice(&self.0);
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        Self::LEN
+    }
+}
+
+/// Maximum length in characters of the STRING, STRING2 and STRINGN values of a STRINGI.
+pub const STRINGI_MAX_LEN: usize = 255;
+
+/// Character string held by a STRINGI entry.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StringIValue {
+    String(Box<CipString<STRINGI_MAX_LEN>>),
+    String2(Box<String2<STRINGI_MAX_LEN>>),
+    StringN(Box<StringN<{ STRINGI_MAX_LEN * 4 }>>),
+    ShortString(Box<ShortString>),
+}
+
+impl StringIValue {
+    pub const STRING_TYPE: u8 = 0xD0;
+    pub const STRING2_TYPE: u8 = 0xD5;
+    pub const STRINGN_TYPE: u8 = 0xD9;
+    pub const SHORT_STRING_TYPE: u8 = 0xDA;
+
+    /// Uses SHORT_STRING for ASCII text, STRING2 for the Basic Multilingual Plane and
+    /// STRINGN with 4-byte characters otherwise.
+    pub fn new(value: &str) -> Self {
+        if value.is_ascii() && value.len() <= ShortString::MAX_LEN {
+            Self::ShortString(Box::new(ShortString::new(value)))
+        } else if value.chars().all(|c| u32::from(c) <= 0xFFFF) {
+            Self::String2(Box::new(String2::new(value)))
+        } else {
+            Self::StringN(Box::new(StringN::new(value)))
+        }
+    }
+
+    pub fn type_code(&self) -> u8 {
+        match self {
+            Self::String(_) => Self::STRING_TYPE,
+            Self::String2(_) => Self::STRING2_TYPE,
+            Self::StringN(_) => Self::STRINGN_TYPE,
+            Self::ShortString(_) => Self::SHORT_STRING_TYPE,
+        }
+    }
+
+    /// Character set (IANA MIBenum) matching the encoding of the value.
+    pub fn default_char_set(&self) -> u16 {
+        match self {
+            Self::String(_) | Self::ShortString(_) => StringI::CHARSET_ISO_8859_1,
+            Self::String2(_) => StringI::CHARSET_UCS_2,
+            Self::StringN(value) => match value.char_size() {
+                1 => StringI::CHARSET_ISO_8859_1,
+                2 => StringI::CHARSET_UCS_2,
+                _ => StringI::CHARSET_UCS_4,
+            },
+        }
+    }
+
+    pub fn value(&self) -> String {
+        match self {
+            Self::String(value) => value.value().to_string(),
+            Self::String2(value) => value.value(),
+            Self::StringN(value) => value.value(),
+            Self::ShortString(value) => value.value().to_string(),
+        }
+    }
+
+    fn decode<T: Buf>(string_type: u8, buffer: &mut T) -> Result<Self, BinaryError> {
+        match string_type {
+            Self::STRING_TYPE => Ok(Self::String(Box::new(CipString::decode(buffer)?))),
+            Self::STRING2_TYPE => Ok(Self::String2(Box::new(String2::decode(buffer)?))),
+            Self::STRINGN_TYPE => Ok(Self::StringN(Box::new(StringN::decode(buffer)?))),
+            Self::SHORT_STRING_TYPE => {
+                Ok(Self::ShortString(Box::new(ShortString::decode(buffer)?)))
+            }
+            _ => Err(BinaryError::InvalidData {
+                message: "Unsupported STRINGI character string type".to_string(),
+                expected: "0xd0, 0xd5, 0xd9 or 0xda".to_string(),
+                actual: format!("{:#04x}", string_type),
+            }),
+        }
+    }
+
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        match self {
+            Self::String(value) => value.encode(buffer),
+            Self::String2(value) => value.encode(buffer),
+            Self::StringN(value) => value.encode(buffer),
+            Self::ShortString(value) => value.encode(buffer),
+        }
+    }
+
+    fn encoded_len(&self) -> usize {
+        match self {
+            Self::String(value) => value.encoded_len(),
+            Self::String2(value) => value.encoded_len(),
+            Self::StringN(value) => value.encoded_len(),
+            Self::ShortString(value) => value.encoded_len(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StringIEntry {
+    pub language: LanguageCode,
+    pub char_set: u16,
+    pub value: StringIValue,
+}
+
+impl StringIEntry {
+    const HEADER_LEN: usize = 6;
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StringI {
+    entries: Vec<StringIEntry>,
+}
+
+impl StringI {
+    pub const CHARSET_ISO_8859_1: u16 = 4;
+    pub const CHARSET_UCS_2: u16 = 1000;
+    pub const CHARSET_UCS_4: u16 = 1001;
+
+    pub fn new(value: &str) -> Self {
+        Self::with_language(LanguageCode::ENGLISH, value)
+    }
+
+    /// Picks the string type from the characters of `value`, see [`StringIValue::new`].
+    pub fn with_language(language: LanguageCode, value: &str) -> Self {
+        Self::default().with_entry(language, StringIValue::new(value))
+    }
+
+    /// Adds a translation of the string.
+    pub fn with_entry(mut self, language: LanguageCode, value: StringIValue) -> Self {
+        self.entries.push(StringIEntry {
+            language,
+            char_set: value.default_char_set(),
+            value,
+        });
+        self
+    }
+
+    pub fn entries(&self) -> &[StringIEntry] {
+        &self.entries
+    }
+
+    pub fn value(&self, language: LanguageCode) -> Option<String> {
+        self.entries
+            .iter()
+            .find(|entry| entry.language == language)
+            .map(|entry| entry.value.value())
+    }
+}
+
+impl From<&str> for StringI {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl FromBytes for StringI {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        if buffer.remaining() < 1 {
+            return Err(BinaryError::Truncated {
+                expected: 1,
+                actual: buffer.remaining(),
+            });
+        }
+
+        let count = buffer.get_u8() as usize;
+        let mut entries = Vec::with_capacity(count);
+
+        for _ in 0..count {
+            if buffer.remaining() < StringIEntry::HEADER_LEN {
+                return Err(BinaryError::Truncated {
+                    expected: StringIEntry::HEADER_LEN,
+                    actual: buffer.remaining(),
+                });
+            }
+
+            let language = LanguageCode::decode(buffer)?;
+            let string_type = buffer.get_u8();
+            let char_set = buffer.get_u16_le();
+            let value = StringIValue::decode(string_type, buffer)?;
+            entries.push(StringIEntry {
+                language,
+                char_set,
+                value,
+            });
+        }
+
+        Ok(Self { entries })
+    }
+}
+
+impl ToBytes for StringI {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < self.encoded_len() {
+            return Err(BinaryError::BufferTooSmall {
+                expected: self.encoded_len(),
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        buffer.put_u8(self.entries.len() as u8);
+        for entry in &self.entries {
+            entry.language.encode(buffer)?;
+            buffer.put_u8(entry.value.type_code());
+            buffer.put_u16_le(entry.char_set);
+            entry.value.encode(buffer)?;
+        }
+
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        1 + self
+            .entries
+            .iter()
+            .map(|entry| StringIEntry::HEADER_LEN + entry.value.encoded_len())
+            .sum::<usize>()
+    }
+}
+
+#[cfg(feature = "serde")]
+crate::common::serde_text::impl_serde_text!(LanguageCode);
+
+#[cfg(feature = "serde")]
+crate::common::serde_text::impl_serde_string!([] StringIValue);
+
+/// Serialized as a map from language code to text, ex: `{"eng": "Valve", "deu": "Ventil"}`.
+#[cfg(feature = "serde")]
+impl serde::Serialize for StringI {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let mut map = serializer.serialize_map(Some(self.entries.len()))?;
+        for entry in &self.entries {
+            map.serialize_entry(&entry.language, &entry.value)?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for StringI {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct StringIVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for StringIVisitor {
+            type Value = StringI;
+
+            fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+                write!(f, "a map from language code to text")
+            }
+
+            fn visit_map<A: serde::de::MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<Self::Value, A::Error> {
+                let mut string = StringI::default();
+                while let Some((language, value)) = map.next_entry()? {
+                    string = string.with_entry(language, value);
+                }
+                Ok(string)
+            }
+        }
+
+        deserializer.deserialize_map(StringIVisitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::{Bytes, BytesMut};
+
+    #[test]
+    fn string_i_single_entry_round_trip_success() {
+        let raw_bytes: [u8; 12] = [
+            0x01, // Number of strings
+            b'e', b'n', b'g', // Language
+            0xDA, // SHORT_STRING
+            0x04, 0x00, // Character set (ISO 8859-1)
+            0x04, b'T', b'e', b's', b't', // Value
+        ];
+
+        let mut cursor = Bytes::copy_from_slice(&raw_bytes);
+        let decoded = StringI::decode(&mut cursor).expect("Failed to decode");
+
+        assert_eq!(decoded, StringI::new("Test"));
+        assert_eq!(
+            decoded.value(LanguageCode::ENGLISH).as_deref(),
+            Some("Test")
+        );
+
+        let mut buffer = BytesMut::with_capacity(decoded.encoded_len());
+        decoded.encode(&mut buffer).expect("Failed to encode");
+        assert_eq!(buffer.as_ref(), &raw_bytes);
+    }
+
+    #[test]
+    fn string_i_empty_round_trip_success() {
+        let empty = StringI::default();
+
+        let mut buffer = BytesMut::new();
+        empty.encode(&mut buffer).expect("Failed to encode");
+
+        assert_eq!(buffer.as_ref(), &[0x00]);
+        let decoded = StringI::decode(&mut buffer.freeze()).expect("Failed to decode");
+        assert!(decoded.entries().is_empty());
+    }
+
+    #[test]
+    fn string_i_string2_entry_round_trip_success() {
+        let raw_bytes = [
+            0x01, // Number of strings
+            b'd', b'e', b'u', // Language
+            0xD5, // STRING2
+            0xE8, 0x03, // Character set (UCS-2)
+            0x02, 0x00, 0xDC, 0x00, 0x62, 0x00, // Value
+        ];
+
+        let decoded =
+            StringI::decode(&mut Bytes::copy_from_slice(&raw_bytes)).expect("Failed to decode");
+
+        let german = LanguageCode::parse("deu").expect("Failed to parse language");
+        assert_eq!(
+            decoded,
+            StringI::default().with_entry(german, StringIValue::new("Üb"))
+        );
+        assert_eq!(decoded.value(german).as_deref(), Some("Üb"));
+
+        let mut buffer = BytesMut::new();
+        decoded.encode(&mut buffer).expect("Failed to encode");
+        assert_eq!(buffer.as_ref(), &raw_bytes);
+    }
+
+    #[test]
+    fn string_i_invalid_language_returns_error() {
+        let raw_bytes = [0x01, b'e', b'n', b'1', 0xDA, 0x04, 0x00, 0x00];
+        let mut cursor = Bytes::copy_from_slice(&raw_bytes);
+
+        let result = StringI::decode(&mut cursor);
+
+        assert!(matches!(result, Err(BinaryError::InvalidData { .. })));
+        assert_eq!(LanguageCode::parse("EN"), None);
+        assert_eq!(LanguageCode::parse("ENG"), Some(LanguageCode::ENGLISH));
+    }
+
+    #[test]
+    fn string_i_unsupported_string_type_returns_error() {
+        let raw_bytes = [0x01, b'e', b'n', b'g', 0xD1, 0x04, 0x00, 0x00];
+        let mut cursor = Bytes::copy_from_slice(&raw_bytes);
+
+        let result = StringI::decode(&mut cursor);
+
+        assert!(matches!(result, Err(BinaryError::InvalidData { .. })));
+    }
+
+    #[test]
+    fn string_i_string_entry_with_invalid_utf8_returns_error() {
+        let raw_bytes = [
+            0x01, b'e', b'n', b'g', 0xD0, 0x04, 0x00, 0x02, 0x00, b'A', 0xFF,
+        ];
+        let mut cursor = Bytes::copy_from_slice(&raw_bytes);
+
+        let result = StringI::decode(&mut cursor);
+
+        assert!(matches!(result, Err(BinaryError::InvalidData { .. })));
+    }
+
+    #[test]
+    fn string_i_truncated_entry_returns_error() {
+        let raw_bytes = [0x01, b'e', b'n'];
+        let mut cursor = Bytes::copy_from_slice(&raw_bytes);
+
+        let result = StringI::decode(&mut cursor);
+
+        assert!(matches!(result, Err(BinaryError::Truncated { .. })));
+    }
+}
@@ -0,0 +1,201 @@
+use bytes::{Buf, BufMut};
+
+use crate::common::binary::{BinaryError, FromBytes, ToBytes};
+
+/// STRINGN: characters of 1, 2 or 4 bytes, preceded by the character size and the length
+/// in characters, both UINT. `N` is the capacity in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StringN<const N: usize> {
+    char_size: u16,
+    len: u16,
+    data: [u8; N],
+}
+
+impl<const N: usize> StringN<N> {
+    const HEADER_LEN: usize = 4;
+    pub const MAX_BYTES: usize = N;
+
+    /// Uses the narrowest character size able to hold every character of `value`:
+    /// 1 byte for ISO 8859-1, 2 bytes for the Basic Multilingual Plane, 4 bytes otherwise.
+    /// Characters past the byte capacity are truncated.
+    pub fn new(value: &str) -> Self {
+        let max_char = value.chars().map(u32::from).max().unwrap_or(0);
+        let char_size: u16 = match max_char {
+            0..=0xFF => 1,
+            0x100..=0xFFFF => 2,
+            _ => 4,
+        };
+
+        let mut data = [0u8; N];
+        let mut len = 0u16;
+        for (i, c) in value.chars().enumerate() {
+            let start = i * char_size as usize;
+            let end = start + char_size as usize;
+            if end > N {
+                break;
+            }
+
+            let code = u32::from(c).to_le_bytes();
+            data[start..end].copy_from_slice(&code[..char_size as usize]);
+            len += 1;
+        }
+
+        Self {
+            char_size,
+            len,
+            data,
+        }
+    }
+
+    pub fn char_size(&self) -> u16 {
+        self.char_size
+    }
+
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn data_len(&self) -> usize {
+        self.len() * self.char_size as usize
+    }
+
+    /// Decodes the characters, replacing invalid code points.
+    pub fn value(&self) -> String {
+        self.data[..self.data_len()]
+            .chunks(self.char_size as usize)
+            .map(|chunk| {
+                let mut code = [0u8; 4];
+                code[..chunk.len()].copy_from_slice(chunk);
+                char::from_u32(u32::from_le_bytes(code)).unwrap_or(char::REPLACEMENT_CHARACTER)
+            })
+            .collect()
+    }
+}
+
+impl<const N: usize> Default for StringN<N> {
+    fn default() -> Self {
+        Self {
+            char_size: 1,
+            len: 0,
+            data: [0u8; N],
+        }
+    }
+}
+
+impl<const N: usize> From<&str> for StringN<N> {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl<const N: usize> FromBytes for StringN<N> {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        if buffer.remaining() < Self::HEADER_LEN {
+            return Err(BinaryError::Truncated {
+                expected: Self::HEADER_LEN,
+                actual: buffer.remaining(),
+            });
+        }
+
+        let char_size = buffer.get_u16_le();
+        if !matches!(char_size, 1 | 2 | 4) {
+            return Err(BinaryError::InvalidData {
+                message: "Invalid STRINGN character size".to_string(),
+                expected: "1, 2 or 4".to_string(),
+                actual: char_size.to_string(),
+            });
+        }
+
+        let len = buffer.get_u16_le();
+        let data_len = len as usize * char_size as usize;
+        if data_len > N {
+            return Err(BinaryError::InvalidData {
+                message: "STRINGN length exceeds max".to_string(),
+                expected: N.to_string(),
+                actual: data_len.to_string(),
+            });
+        }
+
+        if buffer.remaining() < data_len {
+            return Err(BinaryError::Truncated {
+                expected: data_len,
+                actual: buffer.remaining(),
+            });
+        }
+
+        let mut data = [0u8; N];
+        buffer.copy_to_slice(&mut data[..data_len]);
+        Ok(Self {
+            char_size,
+            len,
+            data,
+        })
+    }
+}
+
+impl<const N: usize> ToBytes for StringN<N> {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < self.encoded_len() {
+            return Err(BinaryError::BufferTooSmall {
+                expected: self.encoded_len(),
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        buffer.put_u16_le(self.char_size);
+        buffer.put_u16_le(self.len);
+        buffer.put_slice(&self.data[..self.data_len()]);
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        Self::HEADER_LEN + self.data_len()
+    }
+}
+
+#[cfg(feature = "serde")]
+crate::common::serde_text::impl_serde_string!([const N: usize] StringN<N>);
+
+#[cfg(test)]
+mod tests {
+    use bytes::{Bytes, BytesMut};
+
+    use super::*;
+
+    #[test]
+    fn string_n_picks_narrowest_char_size() {
+        assert_eq!(StringN::<16>::new("Test").char_size(), 1);
+        assert_eq!(StringN::<16>::new("Ωhm").char_size(), 2);
+
+        let wide = StringN::<16>::new("€");
+        let mut buffer = BytesMut::new();
+        wide.encode(&mut buffer).expect("Failed to encode");
+        assert_eq!(
+            buffer.as_ref(),
+            &[
+                0x02, 0x00, // Character size
+                0x01, 0x00, // Length
+                0xAC, 0x20, // '€'
+            ]
+        );
+        assert_eq!(
+            StringN::<16>::decode(&mut buffer.freeze())
+                .expect("Failed to decode")
+                .value(),
+            "€"
+        );
+    }
+
+    #[test]
+    fn string_n_invalid_char_size_returns_error() {
+        let mut buffer = Bytes::from_static(&[0x03, 0x00, 0x01, 0x00, 0x41, 0x00, 0x00]);
+
+        let result = StringN::<16>::decode(&mut buffer);
+
+        assert!(matches!(result, Err(BinaryError::InvalidData { .. })));
+    }
+}
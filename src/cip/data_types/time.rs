@@ -0,0 +1,482 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use bytes::{Buf, BufMut};
+
+use crate::common::binary::{BinaryError, FromBytes, ToBytes};
+
+/// Days between the Unix epoch and the CIP epoch, 1972-01-01.
+const CIP_EPOCH_UNIX_DAYS: i64 = 730;
+const MILLIS_PER_DAY: u32 = 86_400_000;
+
+macro_rules! impl_cip_duration {
+    ($(#[$doc:meta])* $name:ident, $type:ident, $from_fn:ident, $as_fn:ident) => {
+        $(#[$doc])*
+        #[repr(transparent)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+        #[cfg_attr(
+            feature = "serde",
+            derive(serde::Serialize, serde::Deserialize),
+            serde(transparent)
+        )]
+        pub struct $name($type);
+
+        impl $name {
+            pub const LEN: usize = std::mem::size_of::<$type>();
+
+            pub fn new(value: $type) -> Self {
+                Self(value)
+            }
+
+            pub fn value(&self) -> $type {
+                self.0
+            }
+
+            /// Returns `None` for negative durations, which `Duration` can't represent.
+            pub fn as_duration(&self) -> Option<Duration> {
+                u64::try_from(self.0).ok().map(Duration::$from_fn)
+            }
+
+            /// Returns `None` when `duration` doesn't fit, sub-unit precision is truncated.
+            pub fn from_duration(duration: Duration) -> Option<Self> {
+                $type::try_from(duration.$as_fn()).ok().map(Self)
+            }
+        }
+
+        impl FromBytes for $name {
+            fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+                if buffer.remaining() < Self::LEN {
+                    return Err(BinaryError::Truncated {
+                        expected: Self::LEN,
+                        actual: buffer.remaining(),
+                    });
+                }
+
+                paste::paste! {
+                    Ok(Self(buffer.[<get_ $type _le>]()))
+                }
+            }
+        }
+
+        impl ToBytes for $name {
+            fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+                if buffer.remaining_mut() < Self::LEN {
+                    return Err(BinaryError::BufferTooSmall {
+                        expected: Self::LEN,
+                        actual: buffer.remaining_mut(),
+                    });
+                }
+
+                paste::paste! {
+                    buffer.[<put_ $type _le>](self.0);
+                }
+                Ok(())
+            }
+
+            fn encoded_len(&self) -> usize {
+                Self::LEN
+            }
+        }
+    };
+}
+
+impl_cip_duration!(
+    /// STIME: synchronous time, DINT milliseconds.
+    STime, i32, from_millis, as_millis
+);
+impl_cip_duration!(
+    /// ITIME: short duration, INT milliseconds.
+    ITime, i16, from_millis, as_millis
+);
+impl_cip_duration!(
+    /// TIME: duration, DINT milliseconds.
+    Time, i32, from_millis, as_millis
+);
+impl_cip_duration!(
+    /// FTIME: high resolution duration, DINT microseconds.
+    FTime, i32, from_micros, as_micros
+);
+impl_cip_duration!(
+    /// LTIME: long duration, LINT microseconds.
+    LTime, i64, from_micros, as_micros
+);
+impl_cip_duration!(
+    /// NTIME: duration, LINT nanoseconds.
+    NTime, i64, from_nanos, as_nanos
+);
+
+/// Converts a civil date to days since the Unix epoch.
+fn days_from_civil(year: i32, month: u8, day: u8) -> i64 {
+    let year = year as i64 - (month <= 2) as i64;
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let month = month as i64;
+    let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// Converts days since the Unix epoch to a civil date.
+fn civil_from_days(days: i64) -> (i32, u8, u8) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u8;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u8;
+    let year = year_of_era + era * 400 + (month <= 2) as i64;
+    (year as i32, month, day)
+}
+
+fn days_in_month(year: i32, month: u8) -> u8 {
+    match month {
+        4 | 6 | 9 | 11 => 30,
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        _ => 31,
+    }
+}
+
+/// DATE: UINT days since 1972-01-01.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct Date(u16);
+
+impl Date {
+    pub const LEN: usize = 2;
+
+    pub fn new(days: u16) -> Self {
+        Self(days)
+    }
+
+    pub fn days(&self) -> u16 {
+        self.0
+    }
+
+    /// Returns `None` for invalid dates and dates outside the representable range.
+    pub fn from_ymd(year: i32, month: u8, day: u8) -> Option<Self> {
+        if !(1..=12).contains(&month) || day == 0 || day > days_in_month(year, month) {
+            return None;
+        }
+
+        let days = days_from_civil(year, month, day) - CIP_EPOCH_UNIX_DAYS;
+        u16::try_from(days).ok().map(Self)
+    }
+
+    /// Returns the date as (year, month, day).
+    pub fn ymd(&self) -> (i32, u8, u8) {
+        civil_from_days(self.0 as i64 + CIP_EPOCH_UNIX_DAYS)
+    }
+}
+
+impl FromBytes for Date {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        Ok(Self(u16::decode(buffer)?))
+    }
+}
+
+impl ToBytes for Date {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        self.0.encode(buffer)
+    }
+
+    fn encoded_len(&self) -> usize {
+        Self::LEN
+    }
+}
+
+/// TIME_OF_DAY: UDINT milliseconds since midnight.
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct TimeOfDay(u32);
+
+impl TimeOfDay {
+    pub const LEN: usize = 4;
+
+    /// Returns `None` when `millis` is a day or longer.
+    pub fn new(millis: u32) -> Option<Self> {
+        (millis < MILLIS_PER_DAY).then_some(Self(millis))
+    }
+
+    pub fn millis(&self) -> u32 {
+        self.0
+    }
+
+    pub fn from_hms_milli(hour: u8, minute: u8, second: u8, milli: u16) -> Option<Self> {
+        if hour > 23 || minute > 59 || second > 59 || milli > 999 {
+            return None;
+        }
+
+        Self::new(((hour as u32 * 60 + minute as u32) * 60 + second as u32) * 1000 + milli as u32)
+    }
+
+    /// Returns the time as (hour, minute, second, millisecond).
+    pub fn hms_milli(&self) -> (u8, u8, u8, u16) {
+        let seconds = self.0 / 1000;
+        (
+            (seconds / 3600) as u8,
+            (seconds / 60 % 60) as u8,
+            (seconds % 60) as u8,
+            (self.0 % 1000) as u16,
+        )
+    }
+}
+
+impl FromBytes for TimeOfDay {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        let millis = u32::decode(buffer)?;
+        Self::new(millis).ok_or_else(|| BinaryError::InvalidData {
+            message: "Time of day out of range".to_string(),
+            expected: format!("< {}", MILLIS_PER_DAY),
+            actual: millis.to_string(),
+        })
+    }
+}
+
+impl ToBytes for TimeOfDay {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        self.0.encode(buffer)
+    }
+
+    fn encoded_len(&self) -> usize {
+        Self::LEN
+    }
+}
+
+/// DATE_AND_TIME: time of day followed by the date.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Default)]
+pub struct DateAndTime {
+    pub time: TimeOfDay,
+    pub date: Date,
+}
+
+impl DateAndTime {
+    pub const LEN: usize = TimeOfDay::LEN + Date::LEN;
+
+    pub fn new(date: Date, time: TimeOfDay) -> Self {
+        Self { time, date }
+    }
+
+    /// Returns `None` for times before 1972-01-01 or past the last representable date.
+    pub fn from_system_time(time: SystemTime) -> Option<Self> {
+        let since_epoch = time.duration_since(UNIX_EPOCH).ok()?;
+        let millis = since_epoch.as_millis();
+        let days = (millis / MILLIS_PER_DAY as u128) as i64 - CIP_EPOCH_UNIX_DAYS;
+
+        Some(Self {
+            time: TimeOfDay((millis % MILLIS_PER_DAY as u128) as u32),
+            date: Date(u16::try_from(days).ok()?),
+        })
+    }
+
+    pub fn to_system_time(&self) -> SystemTime {
+        let days = self.date.0 as u64 + CIP_EPOCH_UNIX_DAYS as u64;
+        UNIX_EPOCH + Duration::from_millis(days * MILLIS_PER_DAY as u64 + self.time.0 as u64)
+    }
+}
+
+impl FromBytes for DateAndTime {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        if buffer.remaining() < Self::LEN {
+            return Err(BinaryError::Truncated {
+                expected: Self::LEN,
+                actual: buffer.remaining(),
+            });
+        }
+
+        Ok(Self {
+            time: TimeOfDay::decode(buffer)?,
+            date: Date::decode(buffer)?,
+        })
+    }
+}
+
+impl ToBytes for DateAndTime {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < Self::LEN {
+            return Err(BinaryError::BufferTooSmall {
+                expected: Self::LEN,
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        self.time.encode(buffer)?;
+        self.date.encode(buffer)
+    }
+
+    fn encoded_len(&self) -> usize {
+        Self::LEN
+    }
+}
+
+fn invalid_text(message: &str, expected: &str, text: &str) -> BinaryError {
+    BinaryError::InvalidData {
+        message: message.to_string(),
+        expected: expected.to_string(),
+        actual: text.to_string(),
+    }
+}
+
+/// Formats as `YYYY-MM-DD`.
+impl std::fmt::Display for Date {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (year, month, day) = self.ymd();
+        write!(f, "{:04}-{:02}-{:02}", year, month, day)
+    }
+}
+
+impl std::str::FromStr for Date {
+    type Err = BinaryError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || invalid_text("Invalid date", "YYYY-MM-DD", text);
+        let mut parts = text.splitn(3, '-');
+        let mut next = || parts.next().ok_or_else(invalid);
+        let year = next()?.parse().map_err(|_| invalid())?;
+        let month = next()?.parse().map_err(|_| invalid())?;
+        let day = next()?.parse().map_err(|_| invalid())?;
+
+        Self::from_ymd(year, month, day).ok_or_else(invalid)
+    }
+}
+
+/// Formats as `HH:MM:SS.mmm`.
+impl std::fmt::Display for TimeOfDay {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (hour, minute, second, milli) = self.hms_milli();
+        write!(f, "{:02}:{:02}:{:02}.{:03}", hour, minute, second, milli)
+    }
+}
+
+/// Accepts `HH:MM:SS` with up to three fractional digits.
+impl std::str::FromStr for TimeOfDay {
+    type Err = BinaryError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || invalid_text("Invalid time of day", "HH:MM:SS.mmm", text);
+        let (hms, fraction) = text.split_once('.').unwrap_or((text, "0"));
+        if fraction.is_empty() || fraction.len() > 3 {
+            return Err(invalid());
+        }
+        let milli = format!("{:0<3}", fraction).parse().map_err(|_| invalid())?;
+
+        let mut parts = hms.splitn(3, ':');
+        let mut next = || parts.next().ok_or_else(invalid);
+        let hour = next()?.parse().map_err(|_| invalid())?;
+        let minute = next()?.parse().map_err(|_| invalid())?;
+        let second = next()?.parse().map_err(|_| invalid())?;
+
+        Self::from_hms_milli(hour, minute, second, milli).ok_or_else(invalid)
+    }
+}
+
+/// Formats as `YYYY-MM-DDTHH:MM:SS.mmm`.
+impl std::fmt::Display for DateAndTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}T{}", self.date, self.time)
+    }
+}
+
+impl std::str::FromStr for DateAndTime {
+    type Err = BinaryError;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let (date, time) = text.split_once('T').ok_or_else(|| {
+            invalid_text("Invalid date and time", "YYYY-MM-DDTHH:MM:SS.mmm", text)
+        })?;
+
+        Ok(Self::new(date.parse()?, time.parse()?))
+    }
+}
+
+#[cfg(feature = "serde")]
+crate::common::serde_text::impl_serde_text!(Date, TimeOfDay, DateAndTime);
+
+#[cfg(test)]
+mod tests {
+    use bytes::{Bytes, BytesMut};
+
+    use super::*;
+
+    #[test]
+    fn date_from_ymd_round_trip_success() {
+        assert_eq!(Date::from_ymd(1972, 1, 1), Some(Date::new(0)));
+        assert_eq!(Date::from_ymd(1972, 3, 1), Some(Date::new(60)));
+
+        let date = Date::from_ymd(2024, 2, 29).expect("Failed to build date");
+        assert_eq!(date.ymd(), (2024, 2, 29));
+
+        assert_eq!(Date::from_ymd(2023, 2, 29), None);
+        assert_eq!(Date::from_ymd(1971, 12, 31), None);
+    }
+
+    #[test]
+    fn date_and_time_system_time_round_trip_success() {
+        // 2000-01-01T12:30:00.250Z
+        let time = UNIX_EPOCH + Duration::from_millis(946_729_800_250);
+
+        let date_and_time = DateAndTime::from_system_time(time).expect("Failed to convert");
+
+        assert_eq!(date_and_time.date.ymd(), (2000, 1, 1));
+        assert_eq!(date_and_time.time.hms_milli(), (12, 30, 0, 250));
+        assert_eq!(date_and_time.to_system_time(), time);
+
+        let mut buffer = BytesMut::new();
+        date_and_time.encode(&mut buffer).expect("Failed to encode");
+        assert_eq!(
+            DateAndTime::decode(&mut buffer.freeze()).expect("Failed to decode"),
+            date_and_time
+        );
+    }
+
+    #[test]
+    fn time_of_day_decode_out_of_range_returns_error() {
+        // 86_400_000 ms, a full day
+        let mut buffer = Bytes::from_static(&[0x00, 0x5C, 0x26, 0x05]);
+
+        let result = TimeOfDay::decode(&mut buffer);
+
+        assert!(matches!(result, Err(BinaryError::InvalidData { .. })));
+    }
+
+    #[test]
+    fn duration_conversions_success() {
+        assert_eq!(
+            FTime::new(1_500).as_duration(),
+            Some(Duration::from_micros(1_500))
+        );
+        assert_eq!(ITime::new(-1).as_duration(), None);
+        assert_eq!(
+            ITime::from_duration(Duration::from_secs(32)),
+            Some(ITime::new(32_000))
+        );
+        assert_eq!(ITime::from_duration(Duration::from_secs(33)), None);
+
+        let mut buffer = BytesMut::new();
+        LTime::new(-2)
+            .encode(&mut buffer)
+            .expect("Failed to encode");
+        assert_eq!(
+            buffer.as_ref(),
+            &[0xFE, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF]
+        );
+    }
+
+    #[test]
+    fn date_and_time_text_round_trip_success() {
+        let date_and_time: DateAndTime = "2024-02-29T13:05:09.5".parse().expect("Failed to parse");
+
+        assert_eq!(date_and_time.date.ymd(), (2024, 2, 29));
+        assert_eq!(date_and_time.time.hms_milli(), (13, 5, 9, 500));
+        assert_eq!(date_and_time.to_string(), "2024-02-29T13:05:09.500");
+
+        assert!("2023-02-29".parse::<Date>().is_err());
+        assert!("24:00:00".parse::<TimeOfDay>().is_err());
+    }
+}
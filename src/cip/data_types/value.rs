@@ -0,0 +1,280 @@
+use bytes::{Buf, BufMut};
+
+use crate::{
+    cip::data_types::{
+        Bool, Byte, CipString, DInt, DWord, Date, DateAndTime, FTime, ITime, Int, LInt, LReal,
+        LTime, LWord, NTime, Real, SInt, STime, ShortString, String2, StringI, StringN, Time,
+        TimeOfDay, UDInt, UInt, ULInt, USInt, Word,
+        data_type::{CipDataType, TypeDescriptor},
+    },
+    common::binary::{BinaryError, FromBytes, ToBytes},
+};
+
+/// Maximum length in characters of the STRING, STRING2 and STRINGN values.
+pub const VALUE_STRING_MAX_LEN: usize = 255;
+
+macro_rules! cip_values {
+    ($($variant:ident($type:ty)),* $(,)?) => {
+        /// Value of any supported data type, decoded at runtime from a [`TypeDescriptor`].
+        /// With the `serde` feature a value is written as `{"UInt": 42}`.
+        #[derive(Debug, Clone, PartialEq)]
+        #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+        pub enum CipValue {
+            $( $variant($type), )*
+            Struct(Vec<CipValue>),
+            Array(Vec<CipValue>),
+        }
+
+        impl CipValue {
+            /// Elementary type of the value, `None` for structures and arrays.
+            pub fn data_type(&self) -> Option<CipDataType> {
+                match self {
+                    $( Self::$variant(_) => Some(CipDataType::$variant), )*
+                    Self::Struct(_) | Self::Array(_) => None,
+                }
+            }
+
+            fn decode_elementary<T: Buf>(
+                data_type: CipDataType,
+                buffer: &mut T,
+            ) -> Result<Self, BinaryError> {
+                match data_type {
+                    $( CipDataType::$variant => Ok(Self::$variant(FromBytes::decode(buffer)?)), )*
+                }
+            }
+        }
+
+        impl ToBytes for CipValue {
+            fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+                if buffer.remaining_mut() < self.encoded_len() {
+                    return Err(BinaryError::BufferTooSmall {
+                        expected: self.encoded_len(),
+                        actual: buffer.remaining_mut(),
+                    });
+                }
+
+                match self {
+                    $( Self::$variant(value) => value.encode(buffer), )*
+                    Self::Struct(values) | Self::Array(values) => {
+                        for value in values {
+                            value.encode(buffer)?;
+                        }
+                        Ok(())
+                    }
+                }
+            }
+
+            fn encoded_len(&self) -> usize {
+                match self {
+                    $( Self::$variant(value) => value.encoded_len(), )*
+                    Self::Struct(values) | Self::Array(values) => {
+                        values.iter().map(ToBytes::encoded_len).sum()
+                    }
+                }
+            }
+        }
+    };
+}
+
+cip_values!(
+    Bool(Bool),
+    SInt(SInt),
+    Int(Int),
+    DInt(DInt),
+    LInt(LInt),
+    USInt(USInt),
+    UInt(UInt),
+    UDInt(UDInt),
+    ULInt(ULInt),
+    Real(Real),
+    LReal(LReal),
+    STime(STime),
+    Date(Date),
+    TimeOfDay(TimeOfDay),
+    DateAndTime(DateAndTime),
+    String(Box<CipString<VALUE_STRING_MAX_LEN>>),
+    Byte(Byte),
+    Word(Word),
+    DWord(DWord),
+    LWord(LWord),
+    String2(Box<String2<VALUE_STRING_MAX_LEN>>),
+    FTime(FTime),
+    LTime(LTime),
+    ITime(ITime),
+    StringN(Box<StringN<{ VALUE_STRING_MAX_LEN * 4 }>>),
+    ShortString(Box<ShortString>),
+    Time(Time),
+    StringI(Box<StringI>),
+    NTime(NTime),
+);
+
+impl CipValue {
+    /// Decodes a value of the type described by `descriptor`. Structure members and array
+    /// elements are packed without padding. An array needs at least one byte per element,
+    /// so counts larger than the bytes left are rejected before decoding any element.
+    pub fn decode<T: Buf>(
+        descriptor: &TypeDescriptor,
+        buffer: &mut T,
+    ) -> Result<Self, BinaryError> {
+        match descriptor {
+            TypeDescriptor::Elementary(data_type) => Self::decode_elementary(*data_type, buffer),
+            TypeDescriptor::Struct(members) => members
+                .iter()
+                .map(|member| Self::decode(member, buffer))
+                .collect::<Result<_, _>>()
+                .map(Self::Struct),
+            TypeDescriptor::Array { element, .. } => {
+                let count = descriptor.element_count().unwrap_or(0);
+                if count > buffer.remaining() {
+                    return Err(BinaryError::Truncated {
+                        expected: count,
+                        actual: buffer.remaining(),
+                    });
+                }
+                (0..count)
+                    .map(|_| Self::decode(element, buffer))
+                    .collect::<Result<_, _>>()
+                    .map(Self::Array)
+            }
+        }
+    }
+}
+
+fn write_list(
+    f: &mut std::fmt::Formatter<'_>,
+    values: &[CipValue],
+    open: &str,
+    close: &str,
+) -> std::fmt::Result {
+    write!(f, "{}", open)?;
+    for (i, value) in values.iter().enumerate() {
+        if i > 0 {
+            write!(f, ", ")?;
+        }
+        write!(f, "{}", value)?;
+    }
+    write!(f, "{}", close)
+}
+
+impl std::fmt::Display for CipValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Bool(value) => write!(f, "{}", value.value()),
+            Self::SInt(value) => write!(f, "{}", value.value()),
+            Self::Int(value) => write!(f, "{}", value.value()),
+            Self::DInt(value) => write!(f, "{}", value.value()),
+            Self::LInt(value) => write!(f, "{}", value.value()),
+            Self::USInt(value) => write!(f, "{}", value.value()),
+            Self::UInt(value) => write!(f, "{}", value.value()),
+            Self::UDInt(value) => write!(f, "{}", value.value()),
+            Self::ULInt(value) => write!(f, "{}", value.value()),
+            Self::Real(value) => write!(f, "{}", value.value()),
+            Self::LReal(value) => write!(f, "{}", value.value()),
+            Self::Byte(value) => write!(f, "{:#04X}", value.value()),
+            Self::Word(value) => write!(f, "{:#06X}", value.value()),
+            Self::DWord(value) => write!(f, "{:#010X}", value.value()),
+            Self::LWord(value) => write!(f, "{:#018X}", value.value()),
+            Self::STime(value) => write!(f, "{}ms", value.value()),
+            Self::ITime(value) => write!(f, "{}ms", value.value()),
+            Self::Time(value) => write!(f, "{}ms", value.value()),
+            Self::FTime(value) => write!(f, "{}us", value.value()),
+            Self::LTime(value) => write!(f, "{}us", value.value()),
+            Self::NTime(value) => write!(f, "{}ns", value.value()),
+            Self::Date(value) => write!(f, "{}", value),
+            Self::TimeOfDay(value) => write!(f, "{}", value),
+            Self::DateAndTime(value) => write!(f, "{}", value),
+            Self::String(value) => write!(f, "{}", value.value()),
+            Self::String2(value) => write!(f, "{}", value.value()),
+            Self::StringN(value) => write!(f, "{}", value.value()),
+            Self::ShortString(value) => write!(f, "{}", value.value()),
+            Self::StringI(value) => match value.entries().first() {
+                Some(entry) => write!(f, "{}", entry.value.value()),
+                None => Ok(()),
+            },
+            Self::Struct(values) => write_list(f, values, "{", "}"),
+            Self::Array(values) => write_list(f, values, "[", "]"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::{Bytes, BytesMut};
+
+    use super::*;
+
+    #[test]
+    fn struct_value_round_trip_success() {
+        let descriptor = TypeDescriptor::Struct(vec![
+            CipDataType::UInt.into(),
+            CipDataType::ShortString.into(),
+            TypeDescriptor::array(CipDataType::SInt.into(), 2),
+        ]);
+        let raw_bytes = [
+            0x2A, 0x00, // UINT 42
+            0x02, 0x4F, 0x4B, // SHORT_STRING "OK"
+            0xFF, 0x01, // SINT -1, SINT 1
+        ];
+
+        let value = CipValue::decode(&descriptor, &mut Bytes::copy_from_slice(&raw_bytes))
+            .expect("Failed to decode");
+        assert_eq!(
+            value,
+            CipValue::Struct(vec![
+                CipValue::UInt(UInt::new(42)),
+                CipValue::ShortString(Box::new(ShortString::new("OK"))),
+                CipValue::Array(vec![
+                    CipValue::SInt(SInt::new(-1)),
+                    CipValue::SInt(SInt::new(1)),
+                ]),
+            ])
+        );
+        assert_eq!(value.to_string(), "{42, OK, [-1, 1]}");
+
+        let mut buffer = BytesMut::new();
+        value.encode(&mut buffer).expect("Failed to encode");
+        assert_eq!(buffer.as_ref(), &raw_bytes);
+    }
+
+    #[test]
+    fn value_display_formats_time_and_bit_strings() {
+        let date_and_time = DateAndTime::new(
+            Date::from_ymd(2024, 2, 29).expect("Failed to build date"),
+            TimeOfDay::from_hms_milli(13, 5, 9, 42).expect("Failed to build time"),
+        );
+
+        assert_eq!(
+            CipValue::DateAndTime(date_and_time).to_string(),
+            "2024-02-29T13:05:09.042"
+        );
+        assert_eq!(CipValue::Word(Word::new(0xAB)).to_string(), "0x00AB");
+        assert_eq!(CipValue::FTime(FTime::new(250)).to_string(), "250us");
+    }
+
+    #[test]
+    fn value_decode_truncated_returns_error() {
+        let descriptor = TypeDescriptor::array(CipDataType::UDInt.into(), 2);
+
+        let result = CipValue::decode(
+            &descriptor,
+            &mut Bytes::from_static(&[0x01, 0x00, 0x00, 0x00]),
+        );
+
+        assert!(matches!(result, Err(BinaryError::Truncated { .. })));
+    }
+
+    #[test]
+    fn value_decode_array_count_beyond_buffer_returns_error() {
+        let descriptor = TypeDescriptor::array(TypeDescriptor::Struct(vec![]), u32::MAX);
+
+        let result = CipValue::decode(&descriptor, &mut Bytes::from_static(&[0x00, 0x00]));
+
+        assert!(matches!(
+            result,
+            Err(BinaryError::Truncated {
+                expected: 4_294_967_295,
+                actual: 2
+            })
+        ));
+    }
+}
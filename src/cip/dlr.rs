@@ -0,0 +1,313 @@
+use std::{
+    net::Ipv4Addr,
+    sync::{Arc, RwLock},
+};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use cip_macros::{CipClass, CipInstance, cip_object_impl};
+
+use super::{
+    ClassCode,
+    common::error::CipError,
+    common::object::{CipClass, CipInstance, CipObject, CipResult},
+};
+use crate::common::binary::{BinaryError, FromBytes, ToBytes};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetworkTopology {
+    Linear = 0,
+    Ring = 1,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NetworkStatus {
+    Normal = 0,
+    RingFault = 1,
+    UnexpectedLoopDetected = 2,
+    PartialNetworkFault = 3,
+    RapidFaultRestoreCycle = 4,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SupervisorStatus {
+    Backup = 0,
+    Active = 1,
+    NormalRingNode = 2,
+    NonDlrTopology = 3,
+    CannotSupportParameters = 4,
+}
+
+/// IP and MAC address of a ring node.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DlrNodeAddress {
+    pub ip_address: Ipv4Addr,
+    pub mac_address: [u8; 6],
+}
+
+impl DlrNodeAddress {
+    const LEN: usize = 10;
+}
+
+impl Default for DlrNodeAddress {
+    fn default() -> Self {
+        Self {
+            ip_address: Ipv4Addr::UNSPECIFIED,
+            mac_address: [0; 6],
+        }
+    }
+}
+
+impl ToBytes for DlrNodeAddress {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < Self::LEN {
+            return Err(BinaryError::BufferTooSmall {
+                expected: Self::LEN,
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        buffer.put_u32_le(u32::from(self.ip_address));
+        buffer.put_slice(&self.mac_address);
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        Self::LEN
+    }
+}
+
+/// Attribute 4, only reported by supervisor capable nodes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SupervisorConfig {
+    pub enabled: bool,
+    pub precedence: u8,
+    pub beacon_interval_us: u32,
+    pub beacon_timeout_us: u32,
+    pub vlan_id: u16,
+}
+
+impl SupervisorConfig {
+    const LEN: usize = 12;
+}
+
+impl ToBytes for SupervisorConfig {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < Self::LEN {
+            return Err(BinaryError::BufferTooSmall {
+                expected: Self::LEN,
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        buffer.put_u8(self.enabled as u8);
+        buffer.put_u8(self.precedence);
+        buffer.put_u32_le(self.beacon_interval_us);
+        buffer.put_u32_le(self.beacon_timeout_us);
+        buffer.put_u16_le(self.vlan_id);
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        Self::LEN
+    }
+}
+
+/// Ring state reported by the DLR object, implemented by the application's ring node.
+pub trait DlrProvider: Send + Sync {
+    fn network_topology(&self) -> NetworkTopology;
+
+    fn network_status(&self) -> NetworkStatus;
+
+    fn supervisor_status(&self) -> SupervisorStatus;
+
+    /// `None` for nodes that cannot act as ring supervisor.
+    fn supervisor_config(&self) -> Option<SupervisorConfig> {
+        None
+    }
+
+    fn ring_fault_count(&self) -> u16;
+
+    fn active_supervisor(&self) -> DlrNodeAddress;
+
+    fn active_supervisor_precedence(&self) -> u8;
+
+    fn capability_flags(&self) -> u32;
+}
+
+/// Provider for devices without DLR support, always reporting a linear topology.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LinearOnlyDlr;
+
+impl DlrProvider for LinearOnlyDlr {
+    fn network_topology(&self) -> NetworkTopology {
+        NetworkTopology::Linear
+    }
+
+    fn network_status(&self) -> NetworkStatus {
+        NetworkStatus::Normal
+    }
+
+    fn supervisor_status(&self) -> SupervisorStatus {
+        SupervisorStatus::NonDlrTopology
+    }
+
+    fn ring_fault_count(&self) -> u16 {
+        0
+    }
+
+    fn active_supervisor(&self) -> DlrNodeAddress {
+        DlrNodeAddress::default()
+    }
+
+    fn active_supervisor_precedence(&self) -> u8 {
+        0
+    }
+
+    fn capability_flags(&self) -> u32 {
+        0
+    }
+}
+
+#[derive(CipClass)]
+#[cip(id = ClassCode::Dlr, name = "Device Level Ring", singleton = true, custom_services = true)]
+pub struct DlrClass {
+    pub instance: RwLock<Arc<DlrInstance>>,
+}
+
+#[cip_object_impl]
+impl DlrClass {
+    pub fn new(instance: Arc<DlrInstance>) -> Arc<Self> {
+        Arc::new(Self {
+            instance: RwLock::new(instance),
+        })
+    }
+}
+
+#[derive(CipInstance)]
+#[cip(custom_services = true)]
+pub struct DlrInstance {
+    id: u16,
+    class_id: ClassCode,
+    provider: Arc<dyn DlrProvider>,
+}
+
+#[cip_object_impl]
+impl DlrInstance {
+    pub const CAPABILITY_ANNOUNCE_BASED_RING_NODE: u32 = 1 << 0;
+    pub const CAPABILITY_BEACON_BASED_RING_NODE: u32 = 1 << 1;
+    pub const CAPABILITY_SUPERVISOR: u32 = 1 << 5;
+    pub const CAPABILITY_REDUNDANT_GATEWAY: u32 = 1 << 6;
+    pub const CAPABILITY_FLUSH_TABLE_FRAME: u32 = 1 << 7;
+
+    pub fn new(provider: Arc<dyn DlrProvider>) -> Self {
+        Self {
+            id: 1,
+            class_id: ClassCode::Dlr,
+            provider,
+        }
+    }
+
+    #[service(0x0E)]
+    pub fn get_attribute_single(&self, req: &mut Bytes, resp: &mut BytesMut) -> CipResult {
+        let attribute_id = u16::decode(req)?;
+
+        match attribute_id {
+            1 => (self.provider.network_topology() as u8).encode(resp)?,
+            2 => (self.provider.network_status() as u8).encode(resp)?,
+            3 => (self.provider.supervisor_status() as u8).encode(resp)?,
+            4 => self
+                .provider
+                .supervisor_config()
+                .ok_or(CipError::AttributeNotSupported)?
+                .encode(resp)?,
+            5 => self.provider.ring_fault_count().encode(resp)?,
+            10 => self.provider.active_supervisor().encode(resp)?,
+            11 => self.provider.active_supervisor_precedence().encode(resp)?,
+            12 => self.provider.capability_flags().encode(resp)?,
+            _ => return Err(CipError::AttributeNotSupported),
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct RingNode;
+
+    impl DlrProvider for RingNode {
+        fn network_topology(&self) -> NetworkTopology {
+            NetworkTopology::Ring
+        }
+
+        fn network_status(&self) -> NetworkStatus {
+            NetworkStatus::RingFault
+        }
+
+        fn supervisor_status(&self) -> SupervisorStatus {
+            SupervisorStatus::NormalRingNode
+        }
+
+        fn ring_fault_count(&self) -> u16 {
+            3
+        }
+
+        fn active_supervisor(&self) -> DlrNodeAddress {
+            DlrNodeAddress {
+                ip_address: Ipv4Addr::new(192, 168, 1, 1),
+                mac_address: [0x00, 0x1D, 0x9C, 0x01, 0x02, 0x03],
+            }
+        }
+
+        fn active_supervisor_precedence(&self) -> u8 {
+            5
+        }
+
+        fn capability_flags(&self) -> u32 {
+            DlrInstance::CAPABILITY_BEACON_BASED_RING_NODE
+        }
+    }
+
+    fn get_attribute(instance: &mut DlrInstance, attribute_id: u16) -> Result<BytesMut, CipError> {
+        let mut req = Bytes::copy_from_slice(&attribute_id.to_le_bytes());
+        let mut resp = BytesMut::new();
+        instance.execute_service(0x0E, &mut req, &mut resp)?;
+        Ok(resp)
+    }
+
+    #[test]
+    fn linear_only_provider_reports_non_dlr_topology() {
+        let mut instance = DlrInstance::new(Arc::new(LinearOnlyDlr));
+
+        assert_eq!(get_attribute(&mut instance, 1).unwrap().as_ref(), &[0x00]);
+        assert_eq!(get_attribute(&mut instance, 3).unwrap().as_ref(), &[0x03]);
+        assert!(matches!(
+            get_attribute(&mut instance, 4),
+            Err(CipError::AttributeNotSupported)
+        ));
+    }
+
+    #[test]
+    fn ring_node_provider_reports_supervisor_and_faults() {
+        let mut instance = DlrInstance::new(Arc::new(RingNode));
+
+        assert_eq!(get_attribute(&mut instance, 2).unwrap().as_ref(), &[0x01]);
+        assert_eq!(
+            get_attribute(&mut instance, 5).unwrap().as_ref(),
+            &[0x03, 0x00]
+        );
+        assert_eq!(
+            get_attribute(&mut instance, 10).unwrap().as_ref(),
+            &[
+                0x01, 0x01, 0xA8, 0xC0, // 192.168.1.1
+                0x00, 0x1D, 0x9C, 0x01, 0x02, 0x03, // MAC address
+            ]
+        );
+        assert_eq!(
+            get_attribute(&mut instance, 12).unwrap().as_ref(),
+            &[0x02, 0x00, 0x00, 0x00]
+        );
+    }
+}
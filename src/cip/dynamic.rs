@@ -0,0 +1,469 @@
+//! Objects defined at runtime from closures, for plugins and configuration-driven objects
+//! that cannot be written with the derive macros.
+
+use std::{
+    any::Any,
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+use super::{
+    ClassCode,
+    common::error::CipError,
+    common::object::{CipClass, CipInstance, CipObject, CipResult},
+};
+
+/// Encodes the value of an attribute of the instance with the given id.
+pub type AttributeGetter = Arc<dyn Fn(u16, &mut BytesMut) -> CipResult + Send + Sync>;
+
+/// Decodes and stores the value of an attribute of the instance with the given id.
+pub type AttributeSetter = Arc<dyn Fn(u16, &mut Bytes) -> CipResult + Send + Sync>;
+
+/// Executes a service on the instance with the given id.
+pub type ServiceHandler = Arc<dyn Fn(u16, &mut Bytes, &mut BytesMut) -> CipResult + Send + Sync>;
+
+struct DynamicAttribute {
+    getter: AttributeGetter,
+    setter: Option<AttributeSetter>,
+}
+
+/// Attributes and services shared by all instances of a dynamic class.
+#[derive(Default)]
+struct DynamicDefinition {
+    attributes: HashMap<u16, DynamicAttribute>,
+    services: HashMap<u8, ServiceHandler>,
+}
+
+/// Builds a [`DynamicClass`] attribute by attribute and service by service.
+///
+/// ### Example
+/// ```rust,ignore
+/// let class = DynamicClass::builder(0x64)
+///     .name("Vendor Object")
+///     .attribute(1, get_speed, set_speed)
+///     .service(0x4B, reset)
+///     .build();
+/// registry.register(class)?;
+/// ```
+pub struct DynamicClassBuilder {
+    id: ClassCode,
+    name: &'static str,
+    revision: u16,
+    instance_ids: Vec<u16>,
+    definition: DynamicDefinition,
+}
+
+impl DynamicClassBuilder {
+    pub fn name(mut self, name: &'static str) -> Self {
+        self.name = name;
+        self
+    }
+
+    pub fn revision(mut self, revision: u16) -> Self {
+        self.revision = revision;
+        self
+    }
+
+    /// Adds an instance, instance 1 is created when no instance is added.
+    pub fn instance(mut self, instance_id: u16) -> Self {
+        self.instance_ids.push(instance_id);
+        self
+    }
+
+    /// Adds an attribute supporting Get_Attribute_Single only.
+    pub fn get_attribute<G>(mut self, attribute_id: u16, getter: G) -> Self
+    where
+        G: Fn(u16, &mut BytesMut) -> CipResult + Send + Sync + 'static,
+    {
+        self.definition.attributes.insert(
+            attribute_id,
+            DynamicAttribute {
+                getter: Arc::new(getter),
+                setter: None,
+            },
+        );
+        self
+    }
+
+    /// Adds an attribute supporting Get_Attribute_Single and Set_Attribute_Single.
+    pub fn attribute<G, S>(mut self, attribute_id: u16, getter: G, setter: S) -> Self
+    where
+        G: Fn(u16, &mut BytesMut) -> CipResult + Send + Sync + 'static,
+        S: Fn(u16, &mut Bytes) -> CipResult + Send + Sync + 'static,
+    {
+        self.definition.attributes.insert(
+            attribute_id,
+            DynamicAttribute {
+                getter: Arc::new(getter),
+                setter: Some(Arc::new(setter)),
+            },
+        );
+        self
+    }
+
+    /// Adds an instance service, taking precedence over the attribute services.
+    pub fn service<H>(mut self, service_id: u8, handler: H) -> Self
+    where
+        H: Fn(u16, &mut Bytes, &mut BytesMut) -> CipResult + Send + Sync + 'static,
+    {
+        self.definition
+            .services
+            .insert(service_id, Arc::new(handler));
+        self
+    }
+
+    pub fn build(self) -> Arc<DynamicClass> {
+        let definition = Arc::new(self.definition);
+        let instance_ids = match self.instance_ids.is_empty() {
+            true => vec![1],
+            false => self.instance_ids,
+        };
+
+        let instances = instance_ids
+            .into_iter()
+            .map(|id| {
+                let instance: Arc<dyn CipInstance> = Arc::new(DynamicInstance {
+                    id,
+                    class_id: self.id,
+                    definition: definition.clone(),
+                });
+                (id, instance)
+            })
+            .collect();
+
+        Arc::new(DynamicClass {
+            id: self.id,
+            name: self.name,
+            revision: self.revision,
+            instances: RwLock::new(instances),
+        })
+    }
+}
+
+/// Class whose instance attributes and services are closures registered at runtime.
+/// Class attributes 1 to 3 report the revision, the maximum instance id and the number
+/// of instances.
+pub struct DynamicClass {
+    id: ClassCode,
+    name: &'static str,
+    revision: u16,
+    instances: RwLock<HashMap<u16, Arc<dyn CipInstance>>>,
+}
+
+impl DynamicClass {
+    pub fn builder(class_id: u16) -> DynamicClassBuilder {
+        DynamicClassBuilder {
+            id: ClassCode::from(class_id),
+            name: "Dynamic",
+            revision: 1,
+            instance_ids: Vec::new(),
+            definition: DynamicDefinition::default(),
+        }
+    }
+
+    fn instance_ids(&self) -> Result<Vec<u16>, CipError> {
+        let read_guard = self.instances.read().map_err(|_| {
+            log::error!("Failed to get read guard for DynamicClass instances");
+            CipError::GeneralError
+        })?;
+
+        Ok(read_guard.keys().copied().collect())
+    }
+}
+
+impl CipObject for DynamicClass {
+    fn execute_service(
+        &mut self,
+        service_id: u8,
+        req: &mut Bytes,
+        resp: &mut BytesMut,
+    ) -> CipResult {
+        self.execute_shared(service_id, req, resp)
+    }
+
+    fn execute_shared(&self, service_id: u8, req: &mut Bytes, resp: &mut BytesMut) -> CipResult {
+        match service_id {
+            0x0E => {
+                if req.remaining() < 2 {
+                    return Err(CipError::NotEnoughData);
+                }
+
+                let instance_ids = self.instance_ids()?;
+                match req.get_u16_le() {
+                    1 => resp.put_u16_le(self.revision),
+                    2 => resp.put_u16_le(instance_ids.iter().copied().max().unwrap_or(0)),
+                    3 => resp.put_u16_le(instance_ids.len() as u16),
+                    _ => return Err(CipError::AttributeNotSupported),
+                }
+                Ok(())
+            }
+            // Find_Next_Object_Instance addressed to the class starts after instance 0
+            0x11 => {
+                if !req.has_remaining() {
+                    return Err(CipError::NotEnoughData);
+                }
+
+                let instance_ids = self.find_next_instances(0, req.get_u8())?;
+                resp.put_u8(instance_ids.len() as u8);
+                for instance_id in instance_ids {
+                    resp.put_u16_le(instance_id);
+                }
+                Ok(())
+            }
+            _ => Err(CipError::ServiceNotSupported),
+        }
+    }
+}
+
+impl CipClass for DynamicClass {
+    fn id(&self) -> ClassCode {
+        self.id
+    }
+
+    fn name(&self) -> &'static str {
+        self.name
+    }
+
+    fn get_instance(&self, instance_id: u16) -> Result<Arc<dyn CipInstance>, CipError> {
+        self.instances
+            .read()
+            .map_err(|_| {
+                log::error!(
+                    "Failed to get read guard for DynamicClass instance: {}",
+                    instance_id
+                );
+                CipError::GeneralError
+            })?
+            .get(&instance_id)
+            .cloned()
+            .ok_or(CipError::ObjectDoesNotExist)
+    }
+
+    fn add_instance(&self, instance: Arc<dyn CipInstance>) -> Result<(), CipError> {
+        if instance.class_id() != self.id() || instance.id() == 0 {
+            return Err(CipError::InvalidParameter);
+        }
+
+        let mut write_guard = self.instances.write().map_err(|_| {
+            log::error!("Failed to get write guard for DynamicClass instances");
+            CipError::GeneralError
+        })?;
+
+        if write_guard.contains_key(&instance.id()) {
+            return Err(CipError::ObjectAlreadyExists);
+        }
+
+        write_guard.insert(instance.id(), instance);
+        Ok(())
+    }
+
+    fn find_next_instances(&self, after: u16, max_count: u8) -> Result<Vec<u16>, CipError> {
+        let mut instance_ids = self.instance_ids()?;
+        instance_ids.retain(|id| *id > after);
+        instance_ids.sort_unstable();
+        instance_ids.truncate(max_count as usize);
+        Ok(instance_ids)
+    }
+}
+
+/// Instance of a [`DynamicClass`]. Services only read the shared definition, so they can
+/// be executed through the `Arc` held by the registry.
+pub struct DynamicInstance {
+    id: u16,
+    class_id: ClassCode,
+    definition: Arc<DynamicDefinition>,
+}
+
+impl DynamicInstance {
+    pub fn execute(&self, service_id: u8, req: &mut Bytes, resp: &mut BytesMut) -> CipResult {
+        if let Some(handler) = self.definition.services.get(&service_id) {
+            return handler(self.id, req, resp);
+        }
+
+        match service_id {
+            0x0E | 0x10 => {
+                if req.remaining() < 2 {
+                    return Err(CipError::NotEnoughData);
+                }
+
+                let attribute = self
+                    .definition
+                    .attributes
+                    .get(&req.get_u16_le())
+                    .ok_or(CipError::AttributeNotSupported)?;
+
+                match (service_id, &attribute.setter) {
+                    (0x0E, _) => (attribute.getter)(self.id, resp),
+                    (_, Some(setter)) => setter(self.id, req),
+                    (_, None) => Err(CipError::AttributeNotSetable),
+                }
+            }
+            _ => Err(CipError::ServiceNotSupported),
+        }
+    }
+}
+
+impl CipObject for DynamicInstance {
+    fn execute_service(
+        &mut self,
+        service_id: u8,
+        req: &mut Bytes,
+        resp: &mut BytesMut,
+    ) -> CipResult {
+        self.execute(service_id, req, resp)
+    }
+
+    fn execute_shared(&self, service_id: u8, req: &mut Bytes, resp: &mut BytesMut) -> CipResult {
+        self.execute(service_id, req, resp)
+    }
+}
+
+impl CipInstance for DynamicInstance {
+    fn id(&self) -> u16 {
+        self.id
+    }
+
+    fn class_id(&self) -> ClassCode {
+        self.class_id
+    }
+
+    fn as_any_arc(self: Arc<Self>) -> Arc<dyn Any + Send + Sync> {
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cip::registry::Registry;
+    use std::sync::atomic::{AtomicU16, Ordering};
+
+    fn vendor_class(speed: Arc<AtomicU16>) -> Arc<DynamicClass> {
+        let get_speed = speed.clone();
+        DynamicClass::builder(0x64)
+            .name("Vendor Object")
+            .instance(1)
+            .instance(2)
+            .attribute(
+                1,
+                move |_, resp| {
+                    resp.put_u16_le(get_speed.load(Ordering::Relaxed));
+                    Ok(())
+                },
+                move |_, req| {
+                    if req.remaining() < 2 {
+                        return Err(CipError::NotEnoughData);
+                    }
+                    speed.store(req.get_u16_le(), Ordering::Relaxed);
+                    Ok(())
+                },
+            )
+            .get_attribute(2, |instance_id, resp| {
+                resp.put_u16_le(instance_id);
+                Ok(())
+            })
+            .service(0x4B, |_, _, resp| {
+                resp.put_u8(0x01);
+                Ok(())
+            })
+            .build()
+    }
+
+    #[test]
+    fn registered_dynamic_class_executes_closures() {
+        let speed = Arc::new(AtomicU16::new(0));
+        let mut registry = Registry::new();
+        registry
+            .register(vendor_class(speed.clone()))
+            .expect("Failed to register class");
+
+        let instance = registry
+            .get_instance::<DynamicInstance>(ClassCode::UserDefined(0x64), 2)
+            .expect("Failed to get dynamic instance");
+
+        let mut resp = BytesMut::new();
+        instance
+            .execute(
+                0x10,
+                &mut Bytes::from_static(&[0x01, 0x00, 0xE8, 0x03]),
+                &mut resp,
+            )
+            .expect("Failed to set speed");
+        instance
+            .execute(0x0E, &mut Bytes::from_static(&[0x01, 0x00]), &mut resp)
+            .expect("Failed to get speed");
+        instance
+            .execute(0x0E, &mut Bytes::from_static(&[0x02, 0x00]), &mut resp)
+            .expect("Failed to get instance id");
+        instance
+            .execute(0x4B, &mut Bytes::new(), &mut resp)
+            .expect("Failed to execute vendor service");
+
+        assert_eq!(speed.load(Ordering::Relaxed), 1000);
+        assert_eq!(resp.as_ref(), &[0xE8, 0x03, 0x02, 0x00, 0x01]);
+        assert_eq!(
+            registry.get(0x64).expect("Class not registered").name(),
+            "Vendor Object"
+        );
+    }
+
+    #[test]
+    fn set_read_only_or_unknown_attribute_returns_error() {
+        let class = vendor_class(Arc::new(AtomicU16::new(0)));
+        let instance = class.get_instance(1).expect("Failed to get instance");
+        let instance = instance
+            .as_any_arc()
+            .downcast::<DynamicInstance>()
+            .expect("Failed to downcast");
+
+        let read_only = instance.execute(
+            0x10,
+            &mut Bytes::from_static(&[0x02, 0x00, 0x05, 0x00]),
+            &mut BytesMut::new(),
+        );
+        let unknown = instance.execute(
+            0x0E,
+            &mut Bytes::from_static(&[0x09, 0x00]),
+            &mut BytesMut::new(),
+        );
+
+        assert!(matches!(read_only, Err(CipError::AttributeNotSetable)));
+        assert!(matches!(unknown, Err(CipError::AttributeNotSupported)));
+    }
+
+    #[test]
+    fn find_next_instances_follows_instances_after_registration() {
+        let class = DynamicClass::builder(0x64)
+            .instance(5)
+            .instance(2)
+            .instance(9)
+            .build();
+        let mut registry = Registry::new();
+        registry
+            .register(class.clone())
+            .expect("Failed to register class");
+        class
+            .add_instance(Arc::new(DynamicInstance {
+                id: 3,
+                class_id: ClassCode::UserDefined(0x64),
+                definition: Arc::new(DynamicDefinition::default()),
+            }))
+            .expect("Failed to add instance");
+
+        let registered = registry.get(0x64).expect("Class not registered");
+        let mut resp = BytesMut::new();
+        registered
+            .execute_shared(0x11, &mut Bytes::from_static(&[0x03]), &mut resp)
+            .expect("Failed to find next instances");
+
+        assert_eq!(resp.as_ref(), &[0x03, 0x02, 0x00, 0x03, 0x00, 0x05, 0x00]);
+        assert_eq!(registered.find_next_instances(3, 10).ok(), Some(vec![5, 9]));
+        assert_eq!(registered.find_next_instances(9, 10).ok(), Some(vec![]));
+        assert!(matches!(
+            registered.execute_shared(0x11, &mut Bytes::new(), &mut BytesMut::new()),
+            Err(CipError::NotEnoughData)
+        ));
+    }
+}
@@ -0,0 +1,51 @@
+use std::net::SocketAddr;
+
+use bytes::Bytes;
+use tokio::sync::broadcast;
+
+use super::ClassCode;
+
+/// Attribute written through Set_Attribute_Single or by the application.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AttributeChanged {
+    pub class: ClassCode,
+    pub instance: u16,
+    pub attribute: u16,
+    /// New value as encoded on the network.
+    pub new_value_bytes: Bytes,
+    /// Peer that wrote the attribute, `None` for application writes.
+    pub source_peer: Option<SocketAddr>,
+}
+
+/// Publishes [`AttributeChanged`] events to every subscriber. Events are dropped when
+/// nobody subscribed, slow subscribers miss the oldest events.
+#[derive(Debug, Clone)]
+pub struct AttributeNotifier {
+    sender: broadcast::Sender<AttributeChanged>,
+}
+
+impl AttributeNotifier {
+    pub const CAPACITY: usize = 64;
+
+    pub fn new() -> Self {
+        Self {
+            sender: broadcast::channel(Self::CAPACITY).0,
+        }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<AttributeChanged> {
+        self.sender.subscribe()
+    }
+
+    pub fn notify(&self, event: AttributeChanged) {
+        log::debug!("Attribute changed: {:?}", event);
+        // Sending only fails without subscribers
+        _ = self.sender.send(event);
+    }
+}
+
+impl Default for AttributeNotifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,724 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use cip_macros::{CipClass, CipInstance, cip_object_impl};
+
+use super::{
+    ClassCode,
+    cip_identity::Revision,
+    common::error::CipError,
+    common::object::{CipClass, CipInstance, CipObject, CipResult},
+    common::shared::SharedInstance,
+    data_types::string_i::StringI,
+};
+use crate::common::binary::{BinaryError, FromBytes, ToBytes};
+
+/// Instance reserved for the device EDS file.
+pub const EDS_FILE_INSTANCE: u16 = 0xC8;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileState {
+    Nonexistent = 0,
+    FileEmpty = 1,
+    FileLoaded = 2,
+    UploadInitiated = 3,
+    DownloadInitiated = 4,
+    UploadInProgress = 5,
+    DownloadInProgress = 6,
+    Storing = 7,
+}
+
+impl ToBytes for FileState {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        (*self as u8).encode(buffer)
+    }
+
+    fn encoded_len(&self) -> usize {
+        1
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferPacketType {
+    First = 0,
+    Middle = 1,
+    Last = 2,
+    Abort = 3,
+    FirstAndLast = 4,
+}
+
+impl TryFrom<u8> for TransferPacketType {
+    type Error = CipError;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::First),
+            1 => Ok(Self::Middle),
+            2 => Ok(Self::Last),
+            3 => Ok(Self::Abort),
+            4 => Ok(Self::FirstAndLast),
+            _ => Err(CipError::InvalidParameter),
+        }
+    }
+}
+
+impl TransferPacketType {
+    fn is_last(self) -> bool {
+        matches!(self, Self::Last | Self::FirstAndLast)
+    }
+}
+
+/// Two's complement of the 16-bit sum of all file bytes.
+pub fn file_checksum(data: &[u8]) -> u16 {
+    let sum = data
+        .iter()
+        .fold(0u16, |sum, byte| sum.wrapping_add(*byte as u16));
+    (!sum).wrapping_add(1)
+}
+
+/// EDS file served by the File object instance 0xC8.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EdsFile {
+    file_name: String,
+    data: Bytes,
+    compressed: bool,
+}
+
+impl EdsFile {
+    /// Plain text EDS file.
+    pub fn plain(file_name: &str, data: impl Into<Bytes>) -> Self {
+        Self {
+            file_name: file_name.to_string(),
+            data: data.into(),
+            compressed: false,
+        }
+    }
+
+    /// EDS file already gzip-compressed by the application.
+    pub fn compressed(file_name: &str, data: impl Into<Bytes>) -> Self {
+        Self {
+            file_name: file_name.to_string(),
+            data: data.into(),
+            compressed: true,
+        }
+    }
+}
+
+/// File received through Initiate_Download/Download_Transfer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DownloadedFile {
+    pub instance_id: u16,
+    pub file_name: StringI,
+    pub revision: Revision,
+    pub data: Bytes,
+}
+
+type DownloadCallback = Box<dyn Fn(&DownloadedFile) -> CipResult + Send + Sync>;
+
+#[derive(Debug)]
+enum Transfer {
+    Upload {
+        transfer_size: usize,
+        /// Index of the next packet, the transfer number is this index modulo 256.
+        next_packet: usize,
+        /// Transfer number and reply of the previous packet, kept after the last
+        /// packet until the next Initiate_Upload so it can be repeated.
+        last_reply: Option<(u8, Bytes)>,
+    },
+    Download {
+        transfer_size: usize,
+        file_size: usize,
+        file_name: StringI,
+        revision: Revision,
+        last_transfer: Option<u8>,
+        data: BytesMut,
+    },
+}
+
+#[derive(CipClass)]
+#[cip(id = ClassCode::File, name = "File", singleton = false)]
+pub struct FileClass {
+    #[attribute(id = 1, access = "get")]
+    revision: u16,
+
+    #[attribute(id = 2, access = "get")]
+    max_instance: u16,
+
+    pub instances: RwLock<HashMap<u16, Arc<dyn CipInstance>>>,
+}
+
+impl FileClass {
+    pub const REVISION: u16 = 1;
+
+    pub fn with_instances(files: Vec<FileInstance>) -> Arc<Self> {
+        let max_instance = files.iter().map(|file| file.id).max().unwrap_or(0);
+        let instances = files
+            .into_iter()
+            // Transfers mutate the instance, so it runs behind its own lock
+            .map(|file| (file.id, SharedInstance::new(file) as Arc<dyn CipInstance>))
+            .collect::<HashMap<_, _>>();
+
+        Arc::new(Self {
+            revision: Self::REVISION,
+            max_instance,
+            instances: RwLock::new(instances),
+        })
+    }
+}
+
+#[derive(CipInstance)]
+#[cip(custom_services = true)]
+pub struct FileInstance {
+    id: u16,
+    class_id: ClassCode,
+
+    #[attribute(id = 1, access = "get")]
+    state: FileState,
+
+    #[attribute(id = 2, access = "get")]
+    instance_name: StringI,
+
+    #[attribute(id = 3, access = "get")]
+    format_version: u16,
+
+    #[attribute(id = 4, access = "get")]
+    file_name: StringI,
+
+    #[attribute(id = 5, access = "get")]
+    file_revision: Revision,
+
+    #[attribute(id = 6, access = "get")]
+    file_size: u32,
+
+    #[attribute(id = 7, access = "get")]
+    file_checksum: u16,
+
+    #[attribute(id = 8, access = "get")]
+    invocation_method: u8,
+
+    #[attribute(id = 9, access = "get")]
+    file_save_parameters: u8,
+
+    #[attribute(id = 10, access = "get")]
+    file_type: u8,
+
+    #[attribute(id = 11, access = "get")]
+    file_encoding_format: u16,
+
+    contents: Bytes,
+    max_file_size: usize,
+    transfer: Option<Transfer>,
+    on_download: Option<DownloadCallback>,
+}
+
+#[cip_object_impl]
+impl FileInstance {
+    /// Largest file data chunk sent or accepted per transfer.
+    pub const MAX_TRANSFER_SIZE: u8 = 0xF0;
+    /// Largest file accepted by Initiate_Download unless set with [`Self::with_max_file_size`].
+    pub const DEFAULT_MAX_FILE_SIZE: usize = 1024 * 1024;
+    const FILE_TYPE_READ_WRITE: u8 = 0;
+    const FILE_TYPE_READ_ONLY: u8 = 1;
+    const INVOCATION_NOT_APPLICABLE: u8 = 0xFF;
+    const CHECKSUM_LEN: usize = 2;
+
+    fn new(id: u16, instance_name: &str) -> Self {
+        Self {
+            id,
+            class_id: ClassCode::File,
+            state: FileState::FileEmpty,
+            instance_name: StringI::new(instance_name),
+            format_version: 1,
+            file_name: StringI::default(),
+            file_revision: Revision { major: 0, minor: 0 },
+            file_size: 0,
+            file_checksum: 0,
+            invocation_method: Self::INVOCATION_NOT_APPLICABLE,
+            file_save_parameters: 0,
+            file_type: Self::FILE_TYPE_READ_ONLY,
+            file_encoding_format: 0,
+            contents: Bytes::new(),
+            max_file_size: Self::DEFAULT_MAX_FILE_SIZE,
+            transfer: None,
+            on_download: None,
+        }
+    }
+
+    /// Read-only instance 0xC8 serving the device EDS file.
+    pub fn eds(eds: EdsFile, revision: Revision) -> Self {
+        let mut instance = Self::new(EDS_FILE_INSTANCE, "EDS and Icon Files");
+        instance.file_name = StringI::new(&eds.file_name);
+        instance.file_revision = revision;
+        instance.file_encoding_format = eds.compressed as u16;
+        instance.load(eds.data);
+        instance
+    }
+
+    /// Writable instance whose downloads are handed to `on_download`.
+    /// Returning an error from the callback rejects the download.
+    pub fn download_target<F>(id: u16, instance_name: &str, on_download: F) -> Self
+    where
+        F: Fn(&DownloadedFile) -> CipResult + Send + Sync + 'static,
+    {
+        let mut instance = Self::new(id, instance_name);
+        instance.file_type = Self::FILE_TYPE_READ_WRITE;
+        instance.on_download = Some(Box::new(on_download));
+        instance
+    }
+
+    /// Largest file size accepted by Initiate_Download.
+    pub fn with_max_file_size(mut self, max_file_size: usize) -> Self {
+        self.max_file_size = max_file_size;
+        self
+    }
+
+    pub fn state(&self) -> FileState {
+        self.state
+    }
+
+    pub fn contents(&self) -> &Bytes {
+        &self.contents
+    }
+
+    fn load(&mut self, data: Bytes) {
+        self.file_size = data.len() as u32;
+        self.file_checksum = file_checksum(&data);
+        self.contents = data;
+        self.state = FileState::FileLoaded;
+    }
+
+    fn finish_transfer(&mut self) {
+        self.transfer = None;
+        self.state = if self.contents.is_empty() {
+            FileState::FileEmpty
+        } else {
+            FileState::FileLoaded
+        };
+    }
+
+    #[service(0x4B)]
+    pub fn initiate_upload(&mut self, req: &mut Bytes, resp: &mut BytesMut) -> CipResult {
+        let max_transfer_size = u8::decode(req)?;
+        if max_transfer_size == 0 {
+            return Err(CipError::InvalidParameter);
+        }
+
+        if self.state != FileState::FileLoaded {
+            return Err(CipError::ObjectStateConflict);
+        }
+
+        let transfer_size = max_transfer_size.min(Self::MAX_TRANSFER_SIZE);
+        self.transfer = Some(Transfer::Upload {
+            transfer_size: transfer_size as usize,
+            next_packet: 0,
+            last_reply: None,
+        });
+        self.state = FileState::UploadInitiated;
+
+        self.file_size.encode(resp)?;
+        transfer_size.encode(resp)?;
+        Ok(())
+    }
+
+    #[service(0x4F)]
+    pub fn upload_transfer(&mut self, req: &mut Bytes, resp: &mut BytesMut) -> CipResult {
+        let transfer_number = u8::decode(req)?;
+
+        let Some(Transfer::Upload {
+            transfer_size,
+            next_packet,
+            last_reply,
+        }) = &mut self.transfer
+        else {
+            return Err(CipError::ObjectStateConflict);
+        };
+
+        // The client may repeat the previous transfer if it lost the response
+        if let Some((last_transfer, reply)) = last_reply
+            && *last_transfer == transfer_number
+        {
+            resp.put_slice(reply);
+            return Ok(());
+        }
+
+        let packets = self.contents.len().div_ceil(*transfer_size).max(1);
+        if *next_packet >= packets {
+            return Err(CipError::ObjectStateConflict);
+        }
+        if transfer_number != *next_packet as u8 {
+            return Err(CipError::InvalidParameter);
+        }
+
+        let packet_index = *next_packet;
+        let start = packet_index * *transfer_size;
+        let end = (start + *transfer_size).min(self.contents.len());
+        let packet_type = match (packet_index == 0, packet_index + 1 == packets) {
+            (true, true) => TransferPacketType::FirstAndLast,
+            (true, false) => TransferPacketType::First,
+            (false, true) => TransferPacketType::Last,
+            (false, false) => TransferPacketType::Middle,
+        };
+
+        let mut reply = BytesMut::new();
+        transfer_number.encode(&mut reply)?;
+        (packet_type as u8).encode(&mut reply)?;
+        reply.put_slice(&self.contents[start..end]);
+        if packet_type.is_last() {
+            self.file_checksum.encode(&mut reply)?;
+        }
+
+        let reply = reply.freeze();
+        resp.put_slice(&reply);
+        *next_packet += 1;
+        *last_reply = Some((transfer_number, reply));
+
+        self.state = if packet_type.is_last() {
+            FileState::FileLoaded
+        } else {
+            FileState::UploadInProgress
+        };
+
+        Ok(())
+    }
+
+    #[service(0x4C)]
+    pub fn initiate_download(&mut self, req: &mut Bytes, resp: &mut BytesMut) -> CipResult {
+        if self.file_type == Self::FILE_TYPE_READ_ONLY || self.on_download.is_none() {
+            return Err(CipError::ObjectStateConflict);
+        }
+
+        if matches!(
+            self.state,
+            FileState::UploadInitiated | FileState::UploadInProgress
+        ) {
+            return Err(CipError::ObjectStateConflict);
+        }
+
+        let file_size = u32::decode(req)? as usize;
+        let revision = Revision::decode(req)?;
+        let file_name = StringI::decode(req)?;
+        if file_size > self.max_file_size {
+            log::warn!(
+                "Rejected download of {} bytes to file instance {}, limit is {}",
+                file_size,
+                self.id,
+                self.max_file_size
+            );
+            return Err(CipError::ResourceUnavailable);
+        }
+        let transfer_size = Self::MAX_TRANSFER_SIZE;
+
+        self.transfer = Some(Transfer::Download {
+            transfer_size: transfer_size as usize,
+            file_size,
+            file_name,
+            revision,
+            last_transfer: None,
+            // Grows with the received fragments, the announced size is not trusted
+            data: BytesMut::new(),
+        });
+        self.state = FileState::DownloadInitiated;
+
+        // Incremental burn and incremental burn time are not used
+        0u32.encode(resp)?;
+        0u16.encode(resp)?;
+        transfer_size.encode(resp)?;
+        Ok(())
+    }
+
+    #[service(0x50)]
+    pub fn download_transfer(&mut self, req: &mut Bytes, resp: &mut BytesMut) -> CipResult {
+        let transfer_number = u8::decode(req)?;
+        let packet_type = TransferPacketType::try_from(u8::decode(req)?)?;
+
+        let Some(Transfer::Download {
+            transfer_size,
+            file_size,
+            last_transfer,
+            data,
+            ..
+        }) = &mut self.transfer
+        else {
+            return Err(CipError::ObjectStateConflict);
+        };
+
+        if packet_type == TransferPacketType::Abort {
+            self.finish_transfer();
+            transfer_number.encode(resp)?;
+            return Ok(());
+        }
+
+        // A repeated transfer was already stored, just acknowledge it again
+        if Some(transfer_number) == *last_transfer {
+            transfer_number.encode(resp)?;
+            return Ok(());
+        }
+
+        let expected = last_transfer.map_or(0, |last| last.wrapping_add(1));
+        if transfer_number != expected {
+            return Err(CipError::InvalidParameter);
+        }
+
+        let payload_len = if packet_type.is_last() {
+            req.remaining()
+                .checked_sub(Self::CHECKSUM_LEN)
+                .ok_or(CipError::NotEnoughData)?
+        } else {
+            req.remaining()
+        };
+
+        if payload_len > *transfer_size || data.len() + payload_len > *file_size {
+            return Err(CipError::TooMuchData);
+        }
+
+        data.put_slice(&req.split_to(payload_len));
+        *last_transfer = Some(transfer_number);
+        self.state = FileState::DownloadInProgress;
+
+        if packet_type.is_last() {
+            let checksum = u16::decode(req)?;
+            self.complete_download(checksum)?;
+        }
+
+        transfer_number.encode(resp)?;
+        Ok(())
+    }
+
+    fn complete_download(&mut self, checksum: u16) -> CipResult {
+        let Some(Transfer::Download {
+            file_size,
+            file_name,
+            revision,
+            data,
+            ..
+        }) = self.transfer.take()
+        else {
+            return Err(CipError::ObjectStateConflict);
+        };
+
+        if data.len() != file_size || file_checksum(&data) != checksum {
+            log::warn!(
+                "Rejected download to file instance {}: size {}/{}, checksum {:#06x}",
+                self.id,
+                data.len(),
+                file_size,
+                checksum
+            );
+            self.finish_transfer();
+            return Err(CipError::InvalidParameter);
+        }
+
+        let downloaded = DownloadedFile {
+            instance_id: self.id,
+            file_name,
+            revision,
+            data: data.freeze(),
+        };
+
+        self.state = FileState::Storing;
+        let result = match &self.on_download {
+            Some(on_download) => on_download(&downloaded),
+            None => Err(CipError::ObjectStateConflict),
+        };
+
+        if result.is_ok() {
+            self.file_name = downloaded.file_name;
+            self.file_revision = downloaded.revision;
+            self.load(downloaded.data);
+        }
+
+        self.finish_transfer();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    fn execute(instance: &mut FileInstance, service_id: u8, req: &[u8]) -> BytesMut {
+        let mut req = Bytes::copy_from_slice(req);
+        let mut resp = BytesMut::new();
+        instance
+            .execute_service(service_id, &mut req, &mut resp)
+            .expect("Failed to execute service");
+        resp
+    }
+
+    #[test]
+    fn upload_eds_in_fragments_success() {
+        let eds = EdsFile::plain("device.eds", &b"[File]\r\n"[..]);
+        let mut instance = FileInstance::eds(eds, Revision { major: 1, minor: 0 });
+
+        let resp = execute(&mut instance, 0x4B, &[0x05]);
+        assert_eq!(
+            resp.as_ref(),
+            &[
+                0x08, 0x00, 0x00, 0x00, // File size
+                0x05, // Transfer size
+            ]
+        );
+
+        let resp = execute(&mut instance, 0x4F, &[0x00]);
+        assert_eq!(resp.as_ref(), &[0x00, 0x00, b'[', b'F', b'i', b'l', b'e']);
+        assert_eq!(instance.state(), FileState::UploadInProgress);
+
+        let resp = execute(&mut instance, 0x4F, &[0x01]);
+        let checksum = file_checksum(b"[File]\r\n").to_le_bytes();
+        assert_eq!(
+            resp.as_ref(),
+            &[0x01, 0x02, b']', b'\r', b'\n', checksum[0], checksum[1]]
+        );
+        assert_eq!(instance.state(), FileState::FileLoaded);
+    }
+
+    #[test]
+    fn upload_more_than_256_packets_success() {
+        let data = (0..300u16).map(|i| i as u8).collect::<Vec<_>>();
+        let eds = EdsFile::plain("device.eds", data.clone());
+        let mut instance = FileInstance::eds(eds, Revision { major: 1, minor: 0 });
+        execute(&mut instance, 0x4B, &[0x01]);
+
+        let mut uploaded = Vec::new();
+        for packet in 0..300usize {
+            let resp = execute(&mut instance, 0x4F, &[packet as u8]);
+            assert_eq!(resp[0], packet as u8);
+            uploaded.push(resp[2]);
+        }
+
+        assert_eq!(uploaded, data);
+        assert_eq!(instance.state(), FileState::FileLoaded);
+    }
+
+    #[test]
+    fn upload_repeated_last_transfer_returns_same_reply() {
+        let eds = EdsFile::plain("device.eds", &b"[File]"[..]);
+        let mut instance = FileInstance::eds(eds, Revision { major: 1, minor: 0 });
+        execute(&mut instance, 0x4B, &[0x10]);
+
+        let first = execute(&mut instance, 0x4F, &[0x00]);
+        let repeated = execute(&mut instance, 0x4F, &[0x00]);
+
+        assert_eq!(first, repeated);
+        assert_eq!(instance.state(), FileState::FileLoaded);
+
+        let mut req = Bytes::from_static(&[0x01]);
+        let result = instance.execute_service(0x4F, &mut req, &mut BytesMut::new());
+        assert!(matches!(result, Err(CipError::ObjectStateConflict)));
+    }
+
+    #[test]
+    fn upload_through_registered_instance_success() {
+        let eds = EdsFile::plain("device.eds", &b"[File]"[..]);
+        let class = FileClass::with_instances(vec![FileInstance::eds(
+            eds,
+            Revision { major: 1, minor: 0 },
+        )]);
+        let instance = class
+            .get_instance(EDS_FILE_INSTANCE)
+            .expect("Failed to get instance");
+
+        instance
+            .execute_shared(0x4B, &mut Bytes::from_static(&[0x10]), &mut BytesMut::new())
+            .expect("Failed to initiate upload");
+        let mut resp = BytesMut::new();
+        instance
+            .execute_shared(0x4F, &mut Bytes::from_static(&[0x00]), &mut resp)
+            .expect("Failed to upload");
+
+        let checksum = file_checksum(b"[File]").to_le_bytes();
+        assert_eq!(resp[..2], [0x00, 0x04]);
+        assert_eq!(&resp[2..8], b"[File]");
+        assert_eq!(resp[8..], checksum);
+    }
+
+    #[test]
+    fn upload_transfer_out_of_sequence_returns_error() {
+        let eds = EdsFile::compressed("device.eds.gz", &[0x1F, 0x8B, 0x08, 0x00][..]);
+        let mut instance = FileInstance::eds(eds, Revision { major: 1, minor: 0 });
+        execute(&mut instance, 0x4B, &[0x02]);
+
+        let mut req = Bytes::from_static(&[0x01]);
+        let result = instance.execute_service(0x4F, &mut req, &mut BytesMut::new());
+
+        assert!(matches!(result, Err(CipError::InvalidParameter)));
+    }
+
+    #[test]
+    fn download_delivers_file_to_callback() {
+        let received = Arc::new(Mutex::new(None));
+        let received_clone = received.clone();
+        let mut instance = FileInstance::download_target(1, "Firmware", move |file| {
+            *received_clone.lock().unwrap() = Some(file.data.clone());
+            Ok(())
+        });
+
+        let resp = execute(
+            &mut instance,
+            0x4C,
+            &[
+                0x03, 0x00, 0x00, 0x00, // File size
+                0x02, 0x01, // Revision 2.1
+                0x01, b'e', b'n', b'g', 0xDA, 0x04, 0x00, 0x02, b'f', b'w', // File name
+            ],
+        );
+        assert_eq!(resp.as_ref(), &[0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xF0]);
+
+        let checksum = file_checksum(&[0x01, 0x02, 0x03]).to_le_bytes();
+        let resp = execute(
+            &mut instance,
+            0x50,
+            &[0x00, 0x04, 0x01, 0x02, 0x03, checksum[0], checksum[1]],
+        );
+
+        assert_eq!(resp.as_ref(), &[0x00]);
+        assert_eq!(instance.state(), FileState::FileLoaded);
+        assert_eq!(
+            received.lock().unwrap().as_deref(),
+            Some(&[0x01, 0x02, 0x03][..])
+        );
+    }
+
+    #[test]
+    fn initiate_download_larger_than_max_file_size_returns_error() {
+        let mut instance =
+            FileInstance::download_target(1, "Firmware", |_| Ok(())).with_max_file_size(4);
+
+        let mut req = Bytes::from_static(&[0x05, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00]);
+        let result = instance.execute_service(0x4C, &mut req, &mut BytesMut::new());
+
+        assert!(matches!(result, Err(CipError::ResourceUnavailable)));
+        assert_eq!(instance.state(), FileState::FileEmpty);
+    }
+
+    #[test]
+    fn download_bad_checksum_returns_error() {
+        let mut instance = FileInstance::download_target(1, "Firmware", |_| Ok(()));
+        execute(
+            &mut instance,
+            0x4C,
+            &[0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00],
+        );
+
+        let mut req = Bytes::from_static(&[0x00, 0x04, 0x01, 0x00, 0x00]);
+        let result = instance.execute_service(0x50, &mut req, &mut BytesMut::new());
+
+        assert!(matches!(result, Err(CipError::InvalidParameter)));
+        assert_eq!(instance.state(), FileState::FileEmpty);
+    }
+
+    #[test]
+    fn initiate_download_read_only_returns_error() {
+        let eds = EdsFile::plain("device.eds", &b"[File]"[..]);
+        let mut instance = FileInstance::eds(eds, Revision { major: 1, minor: 0 });
+
+        let mut req = Bytes::from_static(&[0x01, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00]);
+        let result = instance.execute_service(0x4C, &mut req, &mut BytesMut::new());
+
+        assert!(matches!(result, Err(CipError::ObjectStateConflict)));
+    }
+}
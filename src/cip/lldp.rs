@@ -0,0 +1,522 @@
+use std::{
+    net::Ipv4Addr,
+    sync::{Arc, RwLock},
+};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use cip_macros::{CipClass, CipInstance, cip_object_impl};
+
+use super::{
+    ClassCode,
+    cip_identity::Revision,
+    common::error::CipError,
+    common::object::{CipClass, CipInstance, CipObject, CipResult},
+    data_types::short_string::ShortString,
+};
+use crate::common::binary::{BinaryError, FromBytes, ToBytes};
+
+/// LLDP Datastore bit reporting that neighbors are exposed through the LLDP Data Table object.
+pub const LLDP_DATASTORE_DATA_TABLE: u16 = 1 << 0;
+
+/// CIP Identification TLV advertised by EtherNet/IP neighbors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LldpCipIdentification {
+    pub vendor_id: u16,
+    pub device_type: u16,
+    pub product_code: u16,
+    pub revision: Revision,
+    pub serial_number: u32,
+}
+
+impl LldpCipIdentification {
+    const LEN: usize = 12;
+}
+
+impl Default for LldpCipIdentification {
+    fn default() -> Self {
+        Self {
+            vendor_id: 0,
+            device_type: 0,
+            product_code: 0,
+            revision: Revision { major: 0, minor: 0 },
+            serial_number: 0,
+        }
+    }
+}
+
+impl ToBytes for LldpCipIdentification {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < Self::LEN {
+            return Err(BinaryError::BufferTooSmall {
+                expected: Self::LEN,
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        buffer.put_u16_le(self.vendor_id);
+        buffer.put_u16_le(self.device_type);
+        buffer.put_u16_le(self.product_code);
+        self.revision.encode(buffer)?;
+        buffer.put_u32_le(self.serial_number);
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        Self::LEN
+    }
+}
+
+/// A neighbor learned on one of the device's Ethernet links.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LldpNeighbor {
+    /// Ethernet Link object instance the neighbor was received on.
+    pub ethernet_link_instance: u16,
+    pub mac_address: [u8; 6],
+    pub interface_label: ShortString,
+    pub time_to_live: u16,
+    pub system_capabilities: u16,
+    pub enabled_capabilities: u16,
+    /// `0.0.0.0` when the neighbor does not advertise an IPv4 management address.
+    pub management_address: Ipv4Addr,
+    pub cip_identification: LldpCipIdentification,
+}
+
+impl LldpNeighbor {
+    pub fn new(ethernet_link_instance: u16, mac_address: [u8; 6]) -> Self {
+        Self {
+            ethernet_link_instance,
+            mac_address,
+            interface_label: ShortString::new(""),
+            time_to_live: 0,
+            system_capabilities: 0,
+            enabled_capabilities: 0,
+            management_address: Ipv4Addr::UNSPECIFIED,
+            cip_identification: LldpCipIdentification::default(),
+        }
+    }
+
+    const MAX_ATTRIBUTE: u16 = 7;
+
+    fn encode_attribute(&self, attribute_id: u16, resp: &mut BytesMut) -> CipResult {
+        match attribute_id {
+            1 => self.ethernet_link_instance.encode(resp)?,
+            2 => {
+                if resp.remaining_mut() < self.mac_address.len() {
+                    return Err(CipError::ReplyDataTooLarge);
+                }
+                resp.put_slice(&self.mac_address);
+            }
+            3 => self.interface_label.encode(resp)?,
+            4 => self.time_to_live.encode(resp)?,
+            5 => {
+                self.system_capabilities.encode(resp)?;
+                self.enabled_capabilities.encode(resp)?;
+            }
+            6 => u32::from(self.management_address).encode(resp)?,
+            7 => self.cip_identification.encode(resp)?,
+            _ => return Err(CipError::AttributeNotSupported),
+        }
+
+        Ok(())
+    }
+}
+
+/// Source of LLDP neighbor information, typically backed by the device's LLDP agent.
+pub trait LldpNeighborSource: Send + Sync {
+    fn neighbors(&self) -> Vec<LldpNeighbor>;
+
+    /// Seconds since the neighbor table last changed.
+    fn last_change(&self) -> u32 {
+        0
+    }
+}
+
+/// Neighbor source for devices without an LLDP agent, always reporting empty tables.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoLldpNeighbors;
+
+impl LldpNeighborSource for NoLldpNeighbors {
+    fn neighbors(&self) -> Vec<LldpNeighbor> {
+        Vec::new()
+    }
+}
+
+/// Instance attribute 1: global enable bit followed by one bit per Ethernet port.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LldpEnable {
+    pub global: bool,
+    pub ports: Vec<bool>,
+}
+
+impl ToBytes for LldpEnable {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < self.encoded_len() {
+            return Err(BinaryError::BufferTooSmall {
+                expected: self.encoded_len(),
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        let bits = std::iter::once(self.global)
+            .chain(self.ports.iter().copied())
+            .collect::<Vec<_>>();
+
+        buffer.put_u16_le(bits.len() as u16);
+        for chunk in bits.chunks(8) {
+            let byte = chunk
+                .iter()
+                .enumerate()
+                .fold(0u8, |byte, (bit, enabled)| byte | ((*enabled as u8) << bit));
+            buffer.put_u8(byte);
+        }
+
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        2 + (self.ports.len() + 1).div_ceil(8)
+    }
+}
+
+#[derive(CipClass)]
+#[cip(id = ClassCode::LldpManagement, name = "LLDP Management", singleton = true)]
+pub struct LldpManagementClass {
+    #[attribute(id = 1, access = "get")]
+    revision: u16,
+
+    pub instance: RwLock<Arc<LldpManagementInstance>>,
+}
+
+impl LldpManagementClass {
+    pub const REVISION: u16 = 1;
+
+    pub fn new(instance: Arc<LldpManagementInstance>) -> Arc<Self> {
+        Arc::new(Self {
+            revision: Self::REVISION,
+            instance: RwLock::new(instance),
+        })
+    }
+}
+
+#[derive(CipInstance)]
+#[cip(custom_services = true)]
+pub struct LldpManagementInstance {
+    id: u16,
+    class_id: ClassCode,
+    enable: LldpEnable,
+    msg_tx_interval: u16,
+    msg_tx_hold: u8,
+    datastore: u16,
+    source: Arc<dyn LldpNeighborSource>,
+}
+
+#[cip_object_impl]
+impl LldpManagementInstance {
+    pub const DEFAULT_MSG_TX_INTERVAL: u16 = 30;
+    pub const DEFAULT_MSG_TX_HOLD: u8 = 4;
+
+    /// Creates the instance with LLDP enabled on `port_count` ports.
+    pub fn new(port_count: usize, source: Arc<dyn LldpNeighborSource>) -> Self {
+        Self {
+            id: 1,
+            class_id: ClassCode::LldpManagement,
+            enable: LldpEnable {
+                global: true,
+                ports: vec![true; port_count],
+            },
+            msg_tx_interval: Self::DEFAULT_MSG_TX_INTERVAL,
+            msg_tx_hold: Self::DEFAULT_MSG_TX_HOLD,
+            datastore: LLDP_DATASTORE_DATA_TABLE,
+            source,
+        }
+    }
+
+    fn encode_attribute(&self, attribute_id: u16, resp: &mut BytesMut) -> CipResult {
+        match attribute_id {
+            1 => self.enable.encode(resp)?,
+            2 => self.msg_tx_interval.encode(resp)?,
+            3 => self.msg_tx_hold.encode(resp)?,
+            4 => self.datastore.encode(resp)?,
+            5 => self.source.last_change().encode(resp)?,
+            _ => return Err(CipError::AttributeNotSupported),
+        }
+
+        Ok(())
+    }
+
+    #[service(0x01)]
+    pub fn get_attributes_all(&self, _req: &mut Bytes, resp: &mut BytesMut) -> CipResult {
+        (1..=5).try_for_each(|attribute_id| self.encode_attribute(attribute_id, resp))
+    }
+
+    #[service(0x0E)]
+    pub fn get_attribute_single(&self, req: &mut Bytes, resp: &mut BytesMut) -> CipResult {
+        let attribute_id = u16::decode(req)?;
+        self.encode_attribute(attribute_id, resp)
+    }
+}
+
+/// Exposes one instance per neighbor reported by the [`LldpNeighborSource`]. The source is
+/// queried on every request, so the table follows the LLDP agent without a refresh.
+pub struct LldpDataTableClass {
+    source: Arc<dyn LldpNeighborSource>,
+}
+
+impl LldpDataTableClass {
+    pub const REVISION: u16 = 1;
+
+    pub fn new(source: Arc<dyn LldpNeighborSource>) -> Arc<Self> {
+        Arc::new(Self { source })
+    }
+
+    fn encode_attribute(&self, attribute_id: u16, resp: &mut BytesMut) -> CipResult {
+        match attribute_id {
+            1 => Self::REVISION.encode(resp)?,
+            // Instances are numbered contiguously, so max instance equals the instance count
+            2 | 3 => (self.source.neighbors().len() as u16).encode(resp)?,
+            _ => return Err(CipError::AttributeNotSupported),
+        }
+
+        Ok(())
+    }
+}
+
+impl CipObject for LldpDataTableClass {
+    fn execute_service(
+        &mut self,
+        service_id: u8,
+        req: &mut Bytes,
+        resp: &mut BytesMut,
+    ) -> CipResult {
+        self.execute_shared(service_id, req, resp)
+    }
+
+    fn execute_shared(&self, service_id: u8, req: &mut Bytes, resp: &mut BytesMut) -> CipResult {
+        match service_id {
+            0x01 => (1..=3).try_for_each(|attribute_id| self.encode_attribute(attribute_id, resp)),
+            0x0E => {
+                let attribute_id = u16::decode(req)?;
+                self.encode_attribute(attribute_id, resp)
+            }
+            _ => Err(CipError::ServiceNotSupported),
+        }
+    }
+}
+
+impl CipClass for LldpDataTableClass {
+    fn id(&self) -> ClassCode {
+        ClassCode::LldpDataTable
+    }
+
+    fn name(&self) -> &'static str {
+        "LLDP Data Table"
+    }
+
+    /// Neighbors are numbered from 1 in the order the source returns them.
+    fn get_instance(&self, instance_id: u16) -> Result<Arc<dyn CipInstance>, CipError> {
+        let index = usize::from(instance_id)
+            .checked_sub(1)
+            .ok_or(CipError::ObjectDoesNotExist)?;
+        let neighbor = self
+            .source
+            .neighbors()
+            .into_iter()
+            .nth(index)
+            .ok_or(CipError::ObjectDoesNotExist)?;
+        Ok(Arc::new(LldpDataTableInstance::new(instance_id, neighbor)))
+    }
+
+    fn add_instance(&self, _instance: Arc<dyn CipInstance>) -> Result<(), CipError> {
+        Err(CipError::ResourceUnavailable)
+    }
+
+    fn find_next_instances(&self, after: u16, max_count: u8) -> Result<Vec<u16>, CipError> {
+        let count = self.source.neighbors().len() as u16;
+        Ok((after.saturating_add(1)..=count)
+            .take(max_count as usize)
+            .collect())
+    }
+}
+
+#[derive(CipInstance)]
+#[cip(custom_services = true)]
+pub struct LldpDataTableInstance {
+    id: u16,
+    class_id: ClassCode,
+    neighbor: LldpNeighbor,
+}
+
+#[cip_object_impl]
+impl LldpDataTableInstance {
+    pub fn new(id: u16, neighbor: LldpNeighbor) -> Self {
+        Self {
+            id,
+            class_id: ClassCode::LldpDataTable,
+            neighbor,
+        }
+    }
+
+    pub fn neighbor(&self) -> &LldpNeighbor {
+        &self.neighbor
+    }
+
+    #[service(0x01)]
+    pub fn get_attributes_all(&self, _req: &mut Bytes, resp: &mut BytesMut) -> CipResult {
+        (1..=LldpNeighbor::MAX_ATTRIBUTE)
+            .try_for_each(|attribute_id| self.neighbor.encode_attribute(attribute_id, resp))
+    }
+
+    #[service(0x0E)]
+    pub fn get_attribute_single(&self, req: &mut Bytes, resp: &mut BytesMut) -> CipResult {
+        let attribute_id = u16::decode(req)?;
+        self.neighbor.encode_attribute(attribute_id, resp)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cip::registry::Registry;
+
+    struct StaticNeighbors(Vec<LldpNeighbor>);
+
+    impl LldpNeighborSource for StaticNeighbors {
+        fn neighbors(&self) -> Vec<LldpNeighbor> {
+            self.0.clone()
+        }
+
+        fn last_change(&self) -> u32 {
+            120
+        }
+    }
+
+    fn neighbor() -> LldpNeighbor {
+        LldpNeighbor {
+            interface_label: ShortString::new("P1"),
+            time_to_live: 120,
+            management_address: Ipv4Addr::new(192, 168, 1, 20),
+            ..LldpNeighbor::new(1, [0x00, 0x1D, 0x9C, 0xAA, 0xBB, 0xCC])
+        }
+    }
+
+    #[test]
+    fn default_source_reports_empty_data_table() {
+        let mut class = LldpDataTableClass::new(Arc::new(NoLldpNeighbors));
+
+        let mut resp = BytesMut::new();
+        Arc::get_mut(&mut class)
+            .expect("Failed to get class")
+            .execute_service(0x01, &mut Bytes::new(), &mut resp)
+            .expect("Failed to get attributes all");
+
+        assert_eq!(resp.as_ref(), &[0x01, 0x00, 0x00, 0x00, 0x00, 0x00]);
+        assert!(matches!(
+            class.get_instance(1),
+            Err(CipError::ObjectDoesNotExist)
+        ));
+    }
+
+    #[test]
+    fn data_table_instance_registered_and_encoded() {
+        let mut registry = Registry::new();
+        registry
+            .register(LldpDataTableClass::new(Arc::new(StaticNeighbors(vec![
+                neighbor(),
+            ]))))
+            .expect("Failed to register class");
+
+        let registered = registry
+            .get_instance::<LldpDataTableInstance>(ClassCode::LldpDataTable, 1)
+            .expect("Failed to get neighbor instance");
+        assert_eq!(registered.neighbor(), &neighbor());
+
+        let mut instance = LldpDataTableInstance::new(1, neighbor());
+
+        let mut resp = BytesMut::new();
+        let mut req = Bytes::from_static(&[0x06, 0x00]);
+        instance
+            .execute_service(0x0E, &mut req, &mut resp)
+            .expect("Failed to get management address");
+        assert_eq!(resp.as_ref(), &[0x14, 0x01, 0xA8, 0xC0]);
+
+        let mut resp = BytesMut::new();
+        instance
+            .execute_service(0x01, &mut Bytes::new(), &mut resp)
+            .expect("Failed to get attributes all");
+        assert_eq!(
+            resp.as_ref(),
+            &[
+                0x01, 0x00, // Ethernet link instance
+                0x00, 0x1D, 0x9C, 0xAA, 0xBB, 0xCC, // MAC address
+                0x02, b'P', b'1', // Interface label
+                0x78, 0x00, // Time to live
+                0x00, 0x00, 0x00, 0x00, // System capabilities
+                0x14, 0x01, 0xA8, 0xC0, // Management address
+                0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+                0x00, // CIP identification
+            ]
+        );
+    }
+
+    #[test]
+    fn data_table_follows_source_after_registration() {
+        struct ChangingNeighbors(RwLock<Vec<LldpNeighbor>>);
+
+        impl LldpNeighborSource for ChangingNeighbors {
+            fn neighbors(&self) -> Vec<LldpNeighbor> {
+                self.0.read().expect("Failed to read neighbors").clone()
+            }
+        }
+
+        let source = Arc::new(ChangingNeighbors(RwLock::new(Vec::new())));
+        let mut registry = Registry::new();
+        registry
+            .register(LldpDataTableClass::new(source.clone()))
+            .expect("Failed to register class");
+        assert!(
+            registry
+                .get_instance::<LldpDataTableInstance>(ClassCode::LldpDataTable, 1)
+                .is_err()
+        );
+
+        source
+            .0
+            .write()
+            .expect("Failed to write neighbors")
+            .push(neighbor());
+
+        let registered = registry
+            .get_instance::<LldpDataTableInstance>(ClassCode::LldpDataTable, 1)
+            .expect("Failed to get neighbor instance");
+        assert_eq!(registered.neighbor(), &neighbor());
+
+        let class = LldpDataTableClass::new(source);
+        let mut resp = BytesMut::new();
+        class
+            .execute_shared(0x0E, &mut Bytes::from_static(&[0x03, 0x00]), &mut resp)
+            .expect("Failed to get number of instances");
+        assert_eq!(resp.as_ref(), &[0x01, 0x00]);
+        assert_eq!(class.find_next_instances(0, 10).ok(), Some(vec![1]));
+    }
+
+    #[test]
+    fn management_get_attributes_all_success() {
+        let mut instance = LldpManagementInstance::new(2, Arc::new(StaticNeighbors(vec![])));
+
+        let mut resp = BytesMut::new();
+        instance
+            .execute_service(0x01, &mut Bytes::new(), &mut resp)
+            .expect("Failed to get attributes all");
+
+        assert_eq!(
+            resp.as_ref(),
+            &[
+                0x03, 0x00, 0x07, // LLDP enable: 3 bits, all enabled
+                0x1E, 0x00, // msgTxInterval
+                0x04, // msgTxHold
+                0x01, 0x00, // LLDP datastore
+                0x78, 0x00, 0x00, 0x00, // Last change
+            ]
+        );
+    }
+}
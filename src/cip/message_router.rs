@@ -0,0 +1,1006 @@
+use std::{net::SocketAddr, pin::Pin, time::Duration};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+
+use super::{
+    ClassCode,
+    common::{
+        error::CipError,
+        object::{CipFuture, CipObject, CipResult, ReplyStatus, ServiceResult},
+    },
+    connection_manager::{
+        ConnectionManagerInstance, MessageRequest, RoutingError, UnconnectedSendRequest,
+    },
+    data_types::epath::{LogicalSegment, LogicalType, PaddedEPath, Segment},
+    events::AttributeChanged,
+    registry::Registry,
+};
+use crate::common::binary::{BinaryError, FromBytes, ToBytes};
+
+/// Time async services have to complete unless configured otherwise.
+pub const DEFAULT_SERVICE_DEADLINE: Duration = Duration::from_secs(5);
+
+/// Message router reply: reply service, general status, extended status and reply data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageResponse {
+    /// Service of the request with the reply bit set.
+    pub service: u8,
+    pub general_status: u8,
+    pub extended_status: Vec<u16>,
+    pub data: Bytes,
+}
+
+impl MessageResponse {
+    pub const REPLY_FLAG: u8 = 0x80;
+
+    pub fn success(request_service: u8, data: Bytes) -> Self {
+        Self {
+            service: request_service | Self::REPLY_FLAG,
+            general_status: CipError::Success.into(),
+            extended_status: Vec::new(),
+            data,
+        }
+    }
+
+    /// Successful reply with a general status other than Success, ex: Partial Transfer.
+    pub fn with_status(request_service: u8, status: ReplyStatus, data: Bytes) -> Self {
+        Self {
+            general_status: status.into(),
+            ..Self::success(request_service, data)
+        }
+    }
+
+    pub fn error(request_service: u8, error: CipError) -> Self {
+        Self {
+            service: request_service | Self::REPLY_FLAG,
+            general_status: error.into(),
+            extended_status: Vec::new(),
+            data: Bytes::new(),
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        self.general_status == CipError::Success as u8
+    }
+
+    /// Replaces a reply carrying more than `max_len` bytes of data with Reply Data Too
+    /// Large (0x11).
+    pub fn limit_data(self, max_len: usize) -> Self {
+        if self.data.len() <= max_len {
+            return self;
+        }
+
+        log::warn!(
+            "Reply to service {:#04X} has {} bytes of data, more than {}",
+            self.service & !Self::REPLY_FLAG,
+            self.data.len(),
+            max_len
+        );
+        Self::error(
+            self.service & !Self::REPLY_FLAG,
+            CipError::ReplyDataTooLarge,
+        )
+    }
+}
+
+impl FromBytes for MessageResponse {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        if buffer.remaining() < 4 {
+            return Err(BinaryError::Truncated {
+                expected: 4,
+                actual: buffer.remaining(),
+            });
+        }
+
+        let service = buffer.get_u8();
+        buffer.advance(1);
+        let general_status = buffer.get_u8();
+        let extended_len = buffer.get_u8() as usize;
+        if buffer.remaining() < extended_len * 2 {
+            return Err(BinaryError::Truncated {
+                expected: extended_len * 2,
+                actual: buffer.remaining(),
+            });
+        }
+
+        let extended_status = (0..extended_len).map(|_| buffer.get_u16_le()).collect();
+        let data = buffer.copy_to_bytes(buffer.remaining());
+
+        Ok(Self {
+            service,
+            general_status,
+            extended_status,
+            data,
+        })
+    }
+}
+
+impl ToBytes for MessageResponse {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < self.encoded_len() {
+            return Err(BinaryError::BufferTooSmall {
+                expected: self.encoded_len(),
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        buffer.put_u8(self.service);
+        buffer.put_u8(0);
+        buffer.put_u8(self.general_status);
+        buffer.put_u8(self.extended_status.len() as u8);
+        for status in &self.extended_status {
+            buffer.put_u16_le(*status);
+        }
+        buffer.put_slice(&self.data);
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        4 + self.extended_status.len() * 2 + self.data.len()
+    }
+}
+
+/// Object addressed by a request path: a class, an instance (0 for the class itself) and
+/// an optional attribute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RequestPath {
+    pub class_id: u16,
+    pub instance_id: u16,
+    pub attribute_id: Option<u16>,
+}
+
+impl TryFrom<&PaddedEPath> for RequestPath {
+    type Error = CipError;
+
+    /// The path holds a class segment, optionally followed by an instance and an attribute
+    /// segment. Instances outside the 16-bit range do not exist in the registry.
+    fn try_from(path: &PaddedEPath) -> Result<Self, Self::Error> {
+        let mut segments = path.segments().iter();
+        let class_id = match segments.next() {
+            Some(&Segment::Logical(LogicalSegment {
+                logical_type: LogicalType::ClassId,
+                value,
+            })) => u16::try_from(value).map_err(|_| CipError::PathDestinationUnknown)?,
+            _ => return Err(CipError::PathSegmentError),
+        };
+
+        let mut request_path = Self {
+            class_id,
+            instance_id: 0,
+            attribute_id: None,
+        };
+        let mut next = segments.next();
+        if let Some(&Segment::Logical(LogicalSegment {
+            logical_type: LogicalType::InstanceId,
+            value,
+        })) = next
+        {
+            request_path.instance_id =
+                u16::try_from(value).map_err(|_| CipError::PathDestinationUnknown)?;
+            next = segments.next();
+        }
+        if let Some(&Segment::Logical(LogicalSegment {
+            logical_type: LogicalType::AttributeId,
+            value,
+        })) = next
+        {
+            request_path.attribute_id =
+                Some(u16::try_from(value).map_err(|_| CipError::PathSegmentError)?);
+            next = segments.next();
+        }
+
+        match next {
+            None => Ok(request_path),
+            Some(segment) => {
+                log::warn!("Unexpected segment {} in request path", segment);
+                Err(CipError::PathSegmentError)
+            }
+        }
+    }
+}
+
+/// Executes services on objects. Async services are awaited up to the deadline and fail
+/// with [`CipError::ResourceUnavailable`] when they take longer.
+///
+/// Explicit messages are [`dispatch`](Self::dispatch)ed to the objects of a registry
+/// through their shared entry points, `execute_shared` and `execute_shared_async`.
+#[derive(Debug, Clone, Copy)]
+pub struct MessageRouter {
+    deadline: Duration,
+}
+
+impl MessageRouter {
+    pub fn new(deadline: Duration) -> Self {
+        Self { deadline }
+    }
+
+    pub fn deadline(&self) -> Duration {
+        self.deadline
+    }
+
+    pub async fn execute(
+        &self,
+        object: &mut dyn CipObject,
+        service_id: u8,
+        req: &mut Bytes,
+        resp: &mut BytesMut,
+    ) -> CipResult {
+        let Some(service) = object.execute_async_service(service_id, req, resp) else {
+            return object.execute_service(service_id, req, resp);
+        };
+
+        let result = self.await_service(service_id, service).await;
+        if result.is_none() {
+            resp.clear();
+        }
+        result.unwrap_or(Err(CipError::ResourceUnavailable))
+    }
+
+    /// Executes a service on an object held by the registry, with the general status of a
+    /// successful reply.
+    pub async fn execute_shared(
+        &self,
+        object: &dyn CipObject,
+        service_id: u8,
+        req: &mut Bytes,
+        resp: &mut BytesMut,
+    ) -> ServiceResult {
+        let Some(service) = object.execute_shared_async(service_id, req, resp) else {
+            return object.execute_shared_with_status(service_id, req, resp);
+        };
+
+        let result = self.await_service(service_id, service).await;
+        if result.is_none() {
+            resp.clear();
+        }
+        result
+            .unwrap_or(Err(CipError::ResourceUnavailable))
+            .map(|()| ReplyStatus::Success)
+    }
+
+    /// Executes an explicit message on the object of the registry addressed by its path.
+    /// Requests addressed to instance 0 or to no instance go to the class. The attribute of
+    /// the path is passed in front of the request data of Get and Set_Attribute_Single.
+    ///
+    /// Successful Set_Attribute_Single requests publish an [`AttributeChanged`] event with
+    /// `peer` as its source. Unconnected_Send requests to the Connection Manager reply with
+    /// the reply of their embedded request, Forward_Open and Forward_Close requests open
+    /// and close connections for `peer`.
+    pub async fn dispatch(
+        &self,
+        registry: &Registry,
+        request: &MessageRequest,
+        peer: Option<SocketAddr>,
+    ) -> MessageResponse {
+        if let Ok(path) = RequestPath::try_from(&request.path)
+            && ClassCode::from(path.class_id) == ClassCode::ConnectionManager
+            && matches!(request.service, 0x4E | 0x52 | 0x54 | 0x5B)
+        {
+            return self
+                .connection_manager_service(registry, request, path, peer)
+                .await;
+        }
+
+        let mut resp = BytesMut::new();
+        match self.dispatch_to(registry, request, peer, &mut resp).await {
+            Ok(status) => MessageResponse::with_status(request.service, status, resp.freeze()),
+            Err(error) => {
+                log::warn!(
+                    "Service {:#04X} on {} failed: {:?}",
+                    request.service,
+                    request.path,
+                    error
+                );
+                MessageResponse::error(request.service, error)
+            }
+        }
+    }
+
+    async fn dispatch_to(
+        &self,
+        registry: &Registry,
+        request: &MessageRequest,
+        peer: Option<SocketAddr>,
+        resp: &mut BytesMut,
+    ) -> ServiceResult {
+        let path = RequestPath::try_from(&request.path)?;
+        if request.service == 0x09 && path.instance_id != 0 {
+            // Delete is addressed to the instance but carried out by its class
+            registry.get_object(path.class_id, path.instance_id)?;
+            registry
+                .get(path.class_id)
+                .ok_or(CipError::PathDestinationUnknown)?
+                .delete_instance(path.instance_id)?;
+            return Ok(ReplyStatus::Success);
+        }
+        if request.service == 0x11 && path.instance_id != 0 {
+            // Find_Next_Object_Instance starts after the instance of the path
+            let class = registry
+                .get(path.class_id)
+                .ok_or(CipError::PathDestinationUnknown)?;
+            let max_count = *request.data.first().ok_or(CipError::NotEnoughData)?;
+            let instance_ids = class.find_next_instances(path.instance_id, max_count)?;
+            resp.put_u8(instance_ids.len() as u8);
+            for instance_id in instance_ids {
+                resp.put_u16_le(instance_id);
+            }
+            return Ok(ReplyStatus::Success);
+        }
+        let object = registry.get_object(path.class_id, path.instance_id)?;
+
+        let mut req = match (request.service, path.attribute_id) {
+            (0x0E | 0x10, None) => return Err(CipError::PathSegmentError),
+            (0x0E | 0x10, Some(attribute_id)) => {
+                let mut data = BytesMut::with_capacity(2 + request.data.len());
+                data.put_u16_le(attribute_id);
+                data.put_slice(&request.data);
+                data.freeze()
+            }
+            _ => request.data.clone(),
+        };
+
+        let status = self
+            .execute_shared(object.as_ref(), request.service, &mut req, resp)
+            .await?;
+
+        if let (0x10, Some(attribute)) = (request.service, path.attribute_id) {
+            // The value as sent, the attribute id travels in the path
+            registry.notifier().notify(AttributeChanged {
+                class: ClassCode::from(path.class_id),
+                instance: path.instance_id,
+                attribute,
+                new_value_bytes: request.data.clone(),
+                source_peer: peer,
+            });
+        }
+        Ok(status)
+    }
+
+    /// Executes a connection or Unconnected_Send request on the Connection Manager.
+    /// Unconnected_Send requests for this device are dispatched to the registry and fail
+    /// with [`RoutingError::timed_out`] when they have not completed within the request
+    /// timeout, other routes go through the backplane router of the Connection Manager.
+    /// Boxed, the embedded request may itself be an Unconnected_Send.
+    fn connection_manager_service<'a>(
+        &'a self,
+        registry: &'a Registry,
+        request: &'a MessageRequest,
+        path: RequestPath,
+        peer: Option<SocketAddr>,
+    ) -> Pin<Box<dyn Future<Output = MessageResponse> + Send + 'a>> {
+        Box::pin(async move {
+            let manager = match registry.get_instance::<ConnectionManagerInstance>(
+                ClassCode::ConnectionManager,
+                path.instance_id,
+            ) {
+                Ok(manager) => manager,
+                Err(error) => {
+                    log::warn!("Unconnected_Send to {} failed: {}", request.path, error);
+                    return MessageResponse::error(
+                        request.service,
+                        CipError::PathDestinationUnknown,
+                    );
+                }
+            };
+            if let Some(response) =
+                manager.execute_connection_service(request.service, &request.data, peer)
+            {
+                return response;
+            }
+
+            let send = match UnconnectedSendRequest::decode(&mut request.data.clone()) {
+                Ok(send) => send,
+                Err(error) => {
+                    log::warn!("Invalid Unconnected_Send request: {}", error);
+                    return MessageResponse::error(request.service, error.into());
+                }
+            };
+
+            let timeout = send.timeout();
+            let route = manager.remote_route(&send);
+            if !route.is_empty() {
+                return match manager.route(route, &send.message, timeout).await {
+                    Ok(data) => MessageResponse::success(send.message.service, data),
+                    Err(error) => error.into_response(),
+                };
+            }
+
+            let local = self.dispatch(registry, &send.message, peer);
+            match tokio::time::timeout(timeout, local).await {
+                Ok(response) => response,
+                Err(_) => {
+                    log::warn!("Unconnected request timed out after {:?}", timeout);
+                    RoutingError::timed_out().into_response()
+                }
+            }
+        })
+    }
+
+    /// Awaits an async service up to the deadline, `None` when it took longer.
+    async fn await_service(&self, service_id: u8, service: CipFuture<'_>) -> Option<CipResult> {
+        match tokio::time::timeout(self.deadline, service).await {
+            Ok(result) => Some(result),
+            Err(_) => {
+                log::warn!(
+                    "Service {:#04X} did not complete within {:?}",
+                    service_id,
+                    self.deadline
+                );
+                None
+            }
+        }
+    }
+}
+
+impl Default for MessageRouter {
+    fn default() -> Self {
+        Self::new(DEFAULT_SERVICE_DEADLINE)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cip::{
+        ClassCode,
+        cip_identity::{IdentityClass, IdentityInfo},
+        common::object::{CipClass, CipInstance, InstanceFactory},
+        connection_manager::{
+            ConnectionManagerClass, PORT_NOT_AVAILABLE, UNCONNECTED_REQUEST_TIMED_OUT,
+        },
+        data_types::epath::EPathBuilder,
+        dynamic::DynamicClass,
+        parameter::{BlobParameter, ParameterClass},
+    };
+    use cip_macros::{CipClass, CipInstance, cip_object_impl};
+    use std::{
+        collections::HashMap,
+        sync::{Arc, RwLock},
+    };
+
+    #[derive(CipClass)]
+    #[cip(id = ClassCode::Parameter, name = "Parameter", singleton = false, create = true, max_instance = 4)]
+    struct BufferClass {
+        factory: InstanceFactory,
+        instances: RwLock<HashMap<u16, Arc<dyn CipInstance>>>,
+    }
+
+    #[derive(CipInstance)]
+    struct BufferInstance {
+        id: u16,
+        class_id: ClassCode,
+    }
+
+    #[derive(CipInstance)]
+    #[cip(custom_services = true)]
+    struct SensorInstance {
+        id: u16,
+        class_id: ClassCode,
+        delay: Duration,
+    }
+
+    #[cip_object_impl]
+    impl SensorInstance {
+        #[service(0x4B, async)]
+        async fn read_sensor(&mut self, _req: &mut Bytes, resp: &mut BytesMut) -> CipResult {
+            tokio::time::sleep(self.delay).await;
+            resp.put_u16_le(0x1234);
+            Ok(())
+        }
+
+        #[service(0x4C)]
+        fn read_cached(&mut self, _req: &mut Bytes, resp: &mut BytesMut) -> CipResult {
+            resp.put_u16_le(0x5678);
+            Ok(())
+        }
+
+        #[service(0x4D, async)]
+        async fn read_shared(&self, _req: &mut Bytes, resp: &mut BytesMut) -> CipResult {
+            tokio::time::sleep(self.delay).await;
+            resp.put_u16_le(0x9ABC);
+            Ok(())
+        }
+    }
+
+    fn sensor(delay: Duration) -> SensorInstance {
+        SensorInstance {
+            id: 1,
+            class_id: ClassCode::UserDefined(0x64),
+            delay,
+        }
+    }
+
+    fn registry(sensor_delay: Duration) -> Registry {
+        let mut registry = Registry::new();
+        let identity_info = IdentityInfo {
+            vendor_id: 0x0001,
+            device_type: 0x0002,
+            product_code: 0x0003,
+            revision_major: 1,
+            revision_minor: 0,
+            serial_number: 0,
+            product_name: "X",
+        };
+        registry
+            .register(IdentityClass::with_default_instance(&identity_info))
+            .expect("Failed to register Identity class");
+        registry
+            .register(ConnectionManagerClass::with_default_instance())
+            .expect("Failed to register Connection Manager class");
+
+        let sensors = DynamicClass::builder(0x64).instance(2).build();
+        sensors
+            .add_instance(Arc::new(sensor(sensor_delay)))
+            .expect("Failed to add sensor");
+        registry
+            .register(sensors)
+            .expect("Failed to register sensor class");
+        registry
+    }
+
+    fn request(service: u8, path: PaddedEPath, data: &'static [u8]) -> MessageRequest {
+        MessageRequest {
+            service,
+            path,
+            data: Bytes::from_static(data),
+        }
+    }
+
+    /// Unconnected_Send of `message` with 1 ms ticks along `route`.
+    fn unconnected_send(
+        message: MessageRequest,
+        timeout_ticks: u8,
+        route: &[u8],
+    ) -> MessageRequest {
+        let mut data = BytesMut::new();
+        data.put_u8(0x00);
+        data.put_u8(timeout_ticks);
+        data.put_u16_le(message.encoded_len() as u16);
+        message.encode(&mut data).expect("Failed to encode");
+        if message.encoded_len() % 2 == 1 {
+            data.put_u8(0x00);
+        }
+        data.put_u8((route.len() / 2) as u8);
+        data.put_u8(0x00);
+        data.put_slice(route);
+
+        MessageRequest {
+            service: 0x52,
+            path: EPathBuilder::new().class(0x06).instance(1).build(),
+            data: data.freeze(),
+        }
+    }
+
+    fn attribute_path(class: u16, instance: u32, attribute: u16) -> PaddedEPath {
+        EPathBuilder::new()
+            .class(class)
+            .instance(instance)
+            .attribute(attribute)
+            .build()
+    }
+
+    #[tokio::test]
+    async fn dispatch_sets_and_gets_registered_instance_attribute() {
+        let registry = registry(Duration::ZERO);
+        let router = MessageRouter::default();
+
+        // Heartbeat interval of the Identity instance
+        let set = router
+            .dispatch(
+                &registry,
+                &request(0x10, attribute_path(0x01, 1, 10), &[5]),
+                None,
+            )
+            .await;
+        let get = router
+            .dispatch(
+                &registry,
+                &request(0x0E, attribute_path(0x01, 1, 10), &[]),
+                None,
+            )
+            .await;
+
+        assert_eq!(set, MessageResponse::success(0x10, Bytes::new()));
+        assert_eq!(get.service, 0x8E);
+        assert_eq!(get.data.as_ref(), &[5]);
+    }
+
+    #[tokio::test]
+    async fn dispatch_with_instance_zero_addresses_the_class() {
+        let registry = registry(Duration::ZERO);
+        let response = MessageRouter::default()
+            .dispatch(
+                &registry,
+                &request(0x0E, attribute_path(0x64, 0, 3), &[]),
+                None,
+            )
+            .await;
+
+        // Number of instances of the sensor class
+        assert!(response.is_success());
+        assert_eq!(response.data.as_ref(), &[0x02, 0x00]);
+    }
+
+    #[tokio::test]
+    async fn dispatch_to_unknown_object_or_invalid_path_returns_error_status() {
+        let registry = registry(Duration::ZERO);
+        let router = MessageRouter::default();
+
+        let unknown_class = router
+            .dispatch(
+                &registry,
+                &request(0x0E, attribute_path(0x70, 1, 1), &[]),
+                None,
+            )
+            .await;
+        let unknown_instance = router
+            .dispatch(
+                &registry,
+                &request(0x0E, attribute_path(0x01, 9, 1), &[]),
+                None,
+            )
+            .await;
+        let missing_attribute = router
+            .dispatch(
+                &registry,
+                &request(
+                    0x0E,
+                    EPathBuilder::new().class(0x01).instance(1).build(),
+                    &[],
+                ),
+                None,
+            )
+            .await;
+
+        assert_eq!(
+            unknown_class,
+            MessageResponse::error(0x0E, CipError::PathDestinationUnknown)
+        );
+        assert_eq!(
+            unknown_instance.general_status,
+            CipError::PathDestinationUnknown as u8
+        );
+        assert_eq!(
+            missing_attribute.general_status,
+            CipError::PathSegmentError as u8
+        );
+    }
+
+    #[tokio::test]
+    async fn dispatch_awaits_shared_async_service_up_to_deadline() {
+        let path = EPathBuilder::new().class(0x64).instance(1).build();
+
+        let fast = MessageRouter::default()
+            .dispatch(
+                &registry(Duration::ZERO),
+                &request(0x4D, path.clone(), &[]),
+                None,
+            )
+            .await;
+        let slow = MessageRouter::new(Duration::from_millis(20))
+            .dispatch(
+                &registry(Duration::from_secs(1)),
+                &request(0x4D, path, &[]),
+                None,
+            )
+            .await;
+
+        assert_eq!(fast.data.as_ref(), &[0xBC, 0x9A]);
+        assert_eq!(
+            slow,
+            MessageResponse::error(0x4D, CipError::ResourceUnavailable)
+        );
+    }
+
+    #[test]
+    fn message_response_round_trip() {
+        let response = MessageResponse {
+            service: 0xCE,
+            general_status: 0x01,
+            extended_status: vec![0x0204],
+            data: Bytes::from_static(&[0xAA]),
+        };
+        let mut buffer = BytesMut::new();
+        response.encode(&mut buffer).expect("Failed to encode");
+
+        assert_eq!(buffer.as_ref(), &[0xCE, 0x00, 0x01, 0x01, 0x04, 0x02, 0xAA]);
+        assert_eq!(
+            MessageResponse::decode(&mut buffer.freeze()).expect("Failed to decode"),
+            response
+        );
+    }
+
+    #[tokio::test]
+    async fn dispatch_delete_to_instance_path_removes_instance() {
+        let mut registry = registry(Duration::ZERO);
+        let buffers = BufferClass {
+            factory: Box::new(|id, _req| {
+                Ok(Arc::new(BufferInstance {
+                    id,
+                    class_id: ClassCode::Parameter,
+                }))
+            }),
+            instances: RwLock::new(HashMap::new()),
+        };
+        buffers
+            .create_instance(&mut Bytes::new())
+            .expect("Failed to create buffer");
+        registry
+            .register(Arc::new(buffers))
+            .expect("Failed to register buffer class");
+        let router = MessageRouter::default();
+        let instance_path = EPathBuilder::new().class(0x0F).instance(1).build();
+
+        let delete = router
+            .dispatch(&registry, &request(0x09, instance_path.clone(), &[]), None)
+            .await;
+        let deleted_again = router
+            .dispatch(&registry, &request(0x09, instance_path, &[]), None)
+            .await;
+        let class_delete = router
+            .dispatch(
+                &registry,
+                &request(0x09, EPathBuilder::new().class(0x0F).build(), &[0x01, 0x00]),
+                None,
+            )
+            .await;
+        let not_deletable = router
+            .dispatch(
+                &registry,
+                &request(
+                    0x09,
+                    EPathBuilder::new().class(0x01).instance(1).build(),
+                    &[],
+                ),
+                None,
+            )
+            .await;
+
+        assert_eq!(delete, MessageResponse::success(0x09, Bytes::new()));
+        assert!(registry.get_object(0x0F, 1).is_err());
+        assert_eq!(
+            deleted_again.general_status,
+            CipError::PathDestinationUnknown as u8
+        );
+        assert_eq!(
+            class_delete.general_status,
+            CipError::ServiceNotSupported as u8
+        );
+        assert_eq!(
+            not_deletable.general_status,
+            CipError::ServiceNotSupported as u8
+        );
+    }
+
+    #[tokio::test]
+    async fn dispatch_find_next_starts_after_path_instance() {
+        let mut registry = registry(Duration::ZERO);
+        let parameters = BufferClass {
+            factory: Box::new(|id, _req| {
+                Ok(Arc::new(BufferInstance {
+                    id,
+                    class_id: ClassCode::Parameter,
+                }))
+            }),
+            instances: RwLock::new(HashMap::new()),
+        };
+        for _ in 0..3 {
+            parameters
+                .create_instance(&mut Bytes::new())
+                .expect("Failed to create parameter");
+        }
+        registry
+            .register(Arc::new(parameters))
+            .expect("Failed to register parameter class");
+        let router = MessageRouter::default();
+
+        let from_class = router
+            .dispatch(
+                &registry,
+                &request(0x11, EPathBuilder::new().class(0x0F).build(), &[2]),
+                None,
+            )
+            .await;
+        let after_instance = router
+            .dispatch(
+                &registry,
+                &request(
+                    0x11,
+                    EPathBuilder::new().class(0x0F).instance(1).build(),
+                    &[5],
+                ),
+                None,
+            )
+            .await;
+        let missing_count = router
+            .dispatch(
+                &registry,
+                &request(
+                    0x11,
+                    EPathBuilder::new().class(0x0F).instance(1).build(),
+                    &[],
+                ),
+                None,
+            )
+            .await;
+
+        assert_eq!(
+            from_class,
+            MessageResponse::success(0x11, Bytes::from_static(&[0x02, 0x01, 0x00, 0x02, 0x00]))
+        );
+        assert_eq!(
+            after_instance,
+            MessageResponse::success(0x11, Bytes::from_static(&[0x02, 0x02, 0x00, 0x03, 0x00]))
+        );
+        assert_eq!(missing_count.general_status, CipError::NotEnoughData as u8);
+    }
+
+    #[tokio::test]
+    async fn dispatch_set_attribute_single_publishes_value_as_sent() {
+        let mut registry = Registry::new();
+        registry
+            .register(
+                DynamicClass::builder(0x64)
+                    .attribute(1, |_, _| Ok(()), |_, _| Ok(()))
+                    .build(),
+            )
+            .expect("Failed to register class");
+        let mut changes = registry.notifier().subscribe();
+        let peer: SocketAddr = "192.168.1.10:44818".parse().expect("Invalid address");
+        let request = MessageRequest {
+            service: 0x10,
+            path: EPathBuilder::new()
+                .class(0x64)
+                .instance(1)
+                .attribute(1)
+                .build(),
+            data: Bytes::from_static(&[0x01, 0x02, 0x03]),
+        };
+
+        // The setter leaves the value unread
+        let response = MessageRouter::default()
+            .dispatch(&registry, &request, Some(peer))
+            .await;
+
+        assert!(response.is_success());
+        assert_eq!(
+            changes.try_recv().expect("Missing attribute change"),
+            AttributeChanged {
+                class: ClassCode::UserDefined(0x64),
+                instance: 1,
+                attribute: 1,
+                new_value_bytes: request.data,
+                source_peer: Some(peer),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn dispatch_get_attribute_fragment_replies_partial_transfer_until_complete() {
+        let mut registry = registry(Duration::ZERO);
+        let table: Vec<u8> = (0..=255).cycle().take(600).collect();
+        registry
+            .register(ParameterClass::with_parameters(vec![Box::new(
+                BlobParameter::new("Table", table.clone(), 1024),
+            )]))
+            .expect("Failed to register Parameter class");
+        let router = MessageRouter::default();
+
+        let mut read = Vec::new();
+        let mut statuses = Vec::new();
+        while statuses.last() != Some(&(CipError::Success as u8)) {
+            let mut data = BytesMut::new();
+            data.put_u16_le(1);
+            data.put_u32_le(read.len() as u32);
+            let fragment = MessageRequest {
+                service: 0x4C,
+                path: EPathBuilder::new().class(0x0F).instance(1).build(),
+                data: data.freeze(),
+            };
+
+            let response = router.dispatch(&registry, &fragment, None).await;
+            read.extend_from_slice(&response.data);
+            statuses.push(response.general_status);
+        }
+
+        assert_eq!(statuses, vec![0x06, CipError::Success as u8]);
+        assert_eq!(read, table);
+    }
+
+    #[tokio::test]
+    async fn dispatch_unconnected_send_with_zero_timeout_executes_local_request() {
+        let registry = registry(Duration::ZERO);
+        let router = MessageRouter::default();
+        let vendor_id = request(0x0E, attribute_path(0x01, 1, 1), &[]);
+
+        let response = router
+            .dispatch(
+                &registry,
+                &unconnected_send(vendor_id, 0, &[0x01, 0x00]),
+                None,
+            )
+            .await;
+
+        assert_eq!(
+            response,
+            MessageResponse::success(0x0E, Bytes::from_static(&[0x01, 0x00]))
+        );
+    }
+
+    #[tokio::test]
+    async fn dispatch_unconnected_send_past_timeout_returns_timed_out() {
+        let registry = registry(Duration::from_secs(1));
+        let router = MessageRouter::default();
+        let read_shared = request(
+            0x4D,
+            EPathBuilder::new().class(0x64).instance(1).build(),
+            &[],
+        );
+
+        let response = router
+            .dispatch(&registry, &unconnected_send(read_shared, 20, &[]), None)
+            .await;
+
+        assert_eq!(response.service, 0xD2);
+        assert_eq!(response.general_status, CipError::ConnectionFailure as u8);
+        assert_eq!(
+            response.extended_status,
+            vec![UNCONNECTED_REQUEST_TIMED_OUT]
+        );
+    }
+
+    #[tokio::test]
+    async fn dispatch_unconnected_send_to_other_slot_returns_port_not_available() {
+        let registry = registry(Duration::ZERO);
+        let router = MessageRouter::default();
+        let vendor_id = request(0x0E, attribute_path(0x01, 1, 1), &[]);
+
+        let response = router
+            .dispatch(
+                &registry,
+                &unconnected_send(vendor_id, 10, &[0x01, 0x03]),
+                None,
+            )
+            .await;
+
+        assert_eq!(response.service, 0xD2);
+        assert_eq!(response.extended_status, vec![PORT_NOT_AVAILABLE]);
+    }
+
+    #[tokio::test]
+    async fn router_awaits_async_and_executes_sync_services() {
+        let router = MessageRouter::default();
+        let mut instance = sensor(Duration::from_millis(1));
+        let mut resp = BytesMut::new();
+
+        router
+            .execute(&mut instance, 0x4B, &mut Bytes::new(), &mut resp)
+            .await
+            .expect("Failed to execute async service");
+        router
+            .execute(&mut instance, 0x4C, &mut Bytes::new(), &mut resp)
+            .await
+            .expect("Failed to execute sync service");
+
+        assert_eq!(resp.get_u16_le(), 0x1234);
+        assert_eq!(resp.get_u16_le(), 0x5678);
+        assert!(matches!(
+            instance.execute_service(0x4B, &mut Bytes::new(), &mut BytesMut::new()),
+            Err(CipError::ServiceNotSupported)
+        ));
+    }
+
+    #[tokio::test]
+    async fn async_service_past_deadline_returns_error() {
+        let router = MessageRouter::new(Duration::from_millis(20));
+        let mut instance = sensor(Duration::from_secs(1));
+        let mut resp = BytesMut::new();
+
+        let result = router
+            .execute(&mut instance, 0x4B, &mut Bytes::new(), &mut resp)
+            .await;
+
+        assert!(matches!(result, Err(CipError::ResourceUnavailable)));
+        assert!(resp.is_empty());
+    }
+}
@@ -0,0 +1,878 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    sync::{Arc, Mutex, RwLock},
+};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use cip_macros::{CipClass, CipInstance, cip_object_impl};
+
+use super::{
+    ClassCode,
+    common::error::CipError,
+    common::fragment::{FragmentedWrite, MAX_UNCONNECTED_REPLY_SIZE, read_fragment},
+    common::object::{CipClass, CipInstance, CipObject, CipResult, ReplyStatus, ServiceResult},
+    data_types::{
+        Byte, DInt, DWord, ElementaryType, Int, LInt, LWord, SInt, UDInt, UInt, ULInt, USInt, Word,
+        epath::PaddedEPath, short_string::ShortString,
+    },
+};
+use crate::common::binary::{FromBytes, ToBytes};
+
+/// Value types that can back a Parameter object instance.
+pub trait ParameterValue:
+    Copy + PartialOrd + std::fmt::Debug + FromBytes + ToBytes + Send + Sync + 'static
+{
+    /// Elementary data type code reported in the Data Type attribute.
+    const DATA_TYPE: u8;
+}
+
+macro_rules! impl_parameter_value {
+    ($($name:ident),* $(,)?) => {
+        $(
+            impl ParameterValue for $name {
+                const DATA_TYPE: u8 = <$name as ElementaryType>::TYPE_CODE;
+            }
+        )*
+    };
+}
+
+impl_parameter_value!(
+    USInt, UInt, UDInt, ULInt, SInt, Int, DInt, LInt, Byte, Word, DWord, LWord
+);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParameterScaling {
+    pub multiplier: u16,
+    pub divisor: u16,
+    pub base: u16,
+    pub offset: i16,
+    pub decimal_precision: u8,
+}
+
+impl Default for ParameterScaling {
+    fn default() -> Self {
+        Self {
+            multiplier: 1,
+            divisor: 1,
+            base: 1,
+            offset: 0,
+            decimal_precision: 0,
+        }
+    }
+}
+
+type ChangeCallback<T> = Arc<dyn Fn(T) + Send + Sync>;
+
+/// Change callback bound to the new value. Parameters return it instead of running it so
+/// that it runs once the parameter is unlocked, callbacks may read the parameter back.
+pub type ChangeNotification = Box<dyn FnOnce()>;
+
+pub struct Parameter<T: ParameterValue> {
+    value: T,
+    minimum: T,
+    maximum: T,
+    default: T,
+    name: ShortString,
+    units: ShortString,
+    help: ShortString,
+    link_path: PaddedEPath,
+    scaling: Option<ParameterScaling>,
+    read_only: bool,
+    on_change: Option<ChangeCallback<T>>,
+}
+
+impl<T: ParameterValue> Parameter<T> {
+    pub const DESCRIPTOR_SCALING: u16 = 1 << 2;
+    pub const DESCRIPTOR_READ_ONLY: u16 = 1 << 4;
+
+    /// Creates a parameter holding its default value, accepting writes in `minimum..=maximum`.
+    pub fn new(name: &str, default: T, minimum: T, maximum: T) -> Self {
+        Self {
+            value: default,
+            minimum,
+            maximum,
+            default,
+            name: ShortString::new(name),
+            units: ShortString::new(""),
+            help: ShortString::new(""),
+            link_path: PaddedEPath::new(Vec::new()),
+            scaling: None,
+            read_only: false,
+            on_change: None,
+        }
+    }
+
+    pub fn with_units(mut self, units: &str) -> Self {
+        self.units = ShortString::new(units);
+        self
+    }
+
+    pub fn with_help(mut self, help: &str) -> Self {
+        self.help = ShortString::new(help);
+        self
+    }
+
+    pub fn with_link_path(mut self, link_path: PaddedEPath) -> Self {
+        self.link_path = link_path;
+        self
+    }
+
+    pub fn with_scaling(mut self, scaling: ParameterScaling) -> Self {
+        self.scaling = Some(scaling);
+        self
+    }
+
+    pub fn read_only(mut self) -> Self {
+        self.read_only = true;
+        self
+    }
+
+    /// Registers a callback invoked with the new value whenever the value changes.
+    pub fn on_change<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(T) + Send + Sync + 'static,
+    {
+        self.on_change = Some(Arc::new(callback));
+        self
+    }
+
+    pub fn value(&self) -> T {
+        self.value
+    }
+
+    pub fn minimum(&self) -> T {
+        self.minimum
+    }
+
+    pub fn maximum(&self) -> T {
+        self.maximum
+    }
+
+    pub fn default_value(&self) -> T {
+        self.default
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.value()
+    }
+
+    /// Updates the value after checking it against the configured limits.
+    pub fn set_value(&mut self, value: T) -> CipResult {
+        if let Some(notify) = self.store_value(value)? {
+            notify();
+        }
+        Ok(())
+    }
+
+    /// Updates the value like [`set_value`](Self::set_value), returning the change
+    /// callback instead of running it.
+    fn store_value(&mut self, value: T) -> Result<Option<ChangeNotification>, CipError> {
+        if value < self.minimum || value > self.maximum {
+            log::warn!(
+                "Rejected value {:?} for parameter '{}': outside {:?}..={:?}",
+                value,
+                self.name.value(),
+                self.minimum,
+                self.maximum
+            );
+            return Err(CipError::InvalidAttributeValue);
+        }
+
+        let changed = value != self.value;
+        self.value = value;
+
+        Ok(self
+            .on_change
+            .clone()
+            .filter(|_| changed)
+            .map(|callback| Box::new(move || callback(value)) as ChangeNotification))
+    }
+
+    pub fn descriptor(&self) -> u16 {
+        let mut descriptor = 0;
+        if self.scaling.is_some() {
+            descriptor |= Self::DESCRIPTOR_SCALING;
+        }
+        if self.read_only {
+            descriptor |= Self::DESCRIPTOR_READ_ONLY;
+        }
+
+        descriptor
+    }
+}
+
+type BlobChangeCallback = Arc<dyn Fn(&[u8]) + Send + Sync>;
+
+/// Parameter holding a byte string of up to `max_len` bytes, ex: a calibration table.
+/// Values larger than an unconnected reply are read and written in fragments with
+/// Get_Attribute_Fragment (0x4C) and Set_Attribute_Fragment (0x4D).
+pub struct BlobParameter {
+    value: Vec<u8>,
+    max_len: usize,
+    name: ShortString,
+    help: ShortString,
+    read_only: bool,
+    on_change: Option<BlobChangeCallback>,
+}
+
+impl BlobParameter {
+    pub const DATA_TYPE: u8 = <Byte as ElementaryType>::TYPE_CODE;
+
+    pub fn new(name: &str, value: Vec<u8>, max_len: usize) -> Self {
+        Self {
+            value,
+            max_len,
+            name: ShortString::new(name),
+            help: ShortString::new(""),
+            read_only: false,
+            on_change: None,
+        }
+    }
+
+    pub fn with_help(mut self, help: &str) -> Self {
+        self.help = ShortString::new(help);
+        self
+    }
+
+    pub fn read_only(mut self) -> Self {
+        self.read_only = true;
+        self
+    }
+
+    /// Registers a callback invoked with the new value whenever the value changes.
+    pub fn on_change<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(&[u8]) + Send + Sync + 'static,
+    {
+        self.on_change = Some(Arc::new(callback));
+        self
+    }
+
+    pub fn value(&self) -> &[u8] {
+        &self.value
+    }
+
+    pub fn set_value(&mut self, value: Vec<u8>) -> CipResult {
+        if let Some(notify) = self.store_value(value)? {
+            notify();
+        }
+        Ok(())
+    }
+
+    /// Updates the value like [`set_value`](Self::set_value), returning the change
+    /// callback instead of running it.
+    fn store_value(&mut self, value: Vec<u8>) -> Result<Option<ChangeNotification>, CipError> {
+        if value.len() > self.max_len {
+            log::warn!(
+                "Rejected {} bytes for parameter '{}': longer than {}",
+                value.len(),
+                self.name.value(),
+                self.max_len
+            );
+            return Err(CipError::TooMuchData);
+        }
+
+        let changed = value != self.value;
+        self.value = value;
+
+        Ok(self.on_change.clone().filter(|_| changed).map(|callback| {
+            let value = self.value.clone();
+            Box::new(move || callback(&value)) as ChangeNotification
+        }))
+    }
+}
+
+impl ParameterAttributes for BlobParameter {
+    fn get_attribute(&self, attribute_id: u16, resp: &mut BytesMut) -> CipResult {
+        match attribute_id {
+            1 => resp.put_slice(&self.value),
+            2 => 0u8.encode(resp)?,
+            3 => {}
+            4 => {
+                let read_only = if self.read_only {
+                    Parameter::<USInt>::DESCRIPTOR_READ_ONLY
+                } else {
+                    0
+                };
+                read_only.encode(resp)?
+            }
+            5 => Self::DATA_TYPE.encode(resp)?,
+            // Data Size is a USINT, longer values report its maximum
+            6 => (self.value.len().min(u8::MAX as usize) as u8).encode(resp)?,
+            7 => self.name.encode(resp)?,
+            8 => ShortString::new("").encode(resp)?,
+            9 => self.help.encode(resp)?,
+            _ => return Err(CipError::AttributeNotSupported),
+        }
+
+        Ok(())
+    }
+
+    fn set_attribute(
+        &mut self,
+        attribute_id: u16,
+        req: &mut Bytes,
+    ) -> Result<Option<ChangeNotification>, CipError> {
+        match attribute_id {
+            1 if self.read_only => Err(CipError::AttributeNotSetable),
+            1 => {
+                let value = req.copy_to_bytes(req.remaining());
+                self.store_value(value.to_vec())
+            }
+            2..=9 => Err(CipError::AttributeNotSetable),
+            _ => Err(CipError::AttributeNotSupported),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+/// Type-erased access to a [`Parameter`] so instances with different value types
+/// can live in the same class.
+pub trait ParameterAttributes: Send + Sync {
+    fn get_attribute(&self, attribute_id: u16, resp: &mut BytesMut) -> CipResult;
+    /// Sets an attribute, returning the change callback to run once the parameter is
+    /// unlocked.
+    fn set_attribute(
+        &mut self,
+        attribute_id: u16,
+        req: &mut Bytes,
+    ) -> Result<Option<ChangeNotification>, CipError>;
+    fn as_any(&self) -> &dyn Any;
+    fn as_any_mut(&mut self) -> &mut dyn Any;
+}
+
+impl<T: ParameterValue> ParameterAttributes for Parameter<T> {
+    fn get_attribute(&self, attribute_id: u16, resp: &mut BytesMut) -> CipResult {
+        let scaling = self.scaling.unwrap_or_default();
+
+        match attribute_id {
+            1 => self.value.encode(resp)?,
+            2 => (self.link_path.encoded_len() as u8).encode(resp)?,
+            3 => self.link_path.encode(resp)?,
+            4 => self.descriptor().encode(resp)?,
+            5 => T::DATA_TYPE.encode(resp)?,
+            6 => (self.value.encoded_len() as u8).encode(resp)?,
+            7 => self.name.encode(resp)?,
+            8 => self.units.encode(resp)?,
+            9 => self.help.encode(resp)?,
+            10 => self.minimum.encode(resp)?,
+            11 => self.maximum.encode(resp)?,
+            12 => self.default.encode(resp)?,
+            13 => scaling.multiplier.encode(resp)?,
+            14 => scaling.divisor.encode(resp)?,
+            15 => scaling.base.encode(resp)?,
+            16 => resp.put_i16_le(scaling.offset),
+            // Scaling links are not supported, report them as unlinked
+            17..=20 => 0u16.encode(resp)?,
+            21 => scaling.decimal_precision.encode(resp)?,
+            _ => return Err(CipError::AttributeNotSupported),
+        }
+
+        Ok(())
+    }
+
+    fn set_attribute(
+        &mut self,
+        attribute_id: u16,
+        req: &mut Bytes,
+    ) -> Result<Option<ChangeNotification>, CipError> {
+        match attribute_id {
+            1 if self.read_only => Err(CipError::AttributeNotSetable),
+            1 => {
+                let value = T::decode(req)?;
+                self.store_value(value)
+            }
+            2..=21 => Err(CipError::AttributeNotSetable),
+            _ => Err(CipError::AttributeNotSupported),
+        }
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_any_mut(&mut self) -> &mut dyn Any {
+        self
+    }
+}
+
+#[derive(CipClass)]
+#[cip(id = ClassCode::Parameter, name = "Parameter", singleton = false)]
+pub struct ParameterClass {
+    #[attribute(id = 1, access = "get")]
+    revision: u16,
+
+    #[attribute(id = 2, access = "get")]
+    max_instance: u16,
+
+    #[attribute(id = 8, access = "get")]
+    class_descriptor: u16,
+
+    /// Assembly instance holding the configuration parameters, 0 when there is none.
+    #[attribute(id = 9, access = "get")]
+    configuration_assembly_instance: u16,
+
+    #[attribute(id = 10, access = "get")]
+    native_language: u8,
+
+    pub instances: RwLock<HashMap<u16, Arc<dyn CipInstance>>>,
+}
+
+impl ParameterClass {
+    pub const REVISION: u16 = 1;
+    /// Parameter Class Descriptor bit: the class has parameter instances.
+    pub const DESCRIPTOR_SUPPORTS_INSTANCES: u16 = 1 << 0;
+    /// Parameter Class Descriptor bit: instances implement the full set of attributes.
+    pub const DESCRIPTOR_FULL_ATTRIBUTES: u16 = 1 << 1;
+    /// Native Language: English.
+    pub const NATIVE_LANGUAGE_ENGLISH: u8 = 0;
+
+    /// Creates the class with one instance per parameter, numbered from 1 in order.
+    pub fn with_parameters(parameters: Vec<Box<dyn ParameterAttributes>>) -> Arc<Self> {
+        let instances = parameters
+            .into_iter()
+            .enumerate()
+            .map(|(index, parameter)| {
+                let id = index as u16 + 1;
+                let instance: Arc<dyn CipInstance> =
+                    Arc::new(ParameterInstance::new(id, parameter));
+                (id, instance)
+            })
+            .collect::<HashMap<_, _>>();
+
+        Arc::new(Self {
+            revision: Self::REVISION,
+            max_instance: instances.len() as u16,
+            class_descriptor: Self::DESCRIPTOR_SUPPORTS_INSTANCES
+                | Self::DESCRIPTOR_FULL_ATTRIBUTES,
+            configuration_assembly_instance: 0,
+            native_language: Self::NATIVE_LANGUAGE_ENGLISH,
+            instances: RwLock::new(instances),
+        })
+    }
+}
+
+#[derive(CipInstance)]
+#[cip(custom_services = true)]
+pub struct ParameterInstance {
+    id: u16,
+    class_id: ClassCode,
+    parameter: RwLock<Box<dyn ParameterAttributes>>,
+    /// Attribute of the Set_Attribute_Fragment sequence in progress and its fragments.
+    fragmented_write: Mutex<(u16, FragmentedWrite)>,
+}
+
+#[cip_object_impl]
+impl ParameterInstance {
+    pub fn new(id: u16, parameter: Box<dyn ParameterAttributes>) -> Self {
+        Self {
+            id,
+            class_id: ClassCode::Parameter,
+            parameter: RwLock::new(parameter),
+            fragmented_write: Mutex::new((0, FragmentedWrite::new())),
+        }
+    }
+
+    /// Returns the current value, or `None` if the parameter does not hold values of type `T`.
+    pub fn value<T: ParameterValue>(&self) -> Option<T> {
+        let parameter = self.parameter.read().ok()?;
+        parameter
+            .as_any()
+            .downcast_ref::<Parameter<T>>()
+            .map(Parameter::value)
+    }
+
+    /// Updates the value from the application, checked against the parameter limits.
+    pub fn set_value<T: ParameterValue>(&self, value: T) -> CipResult {
+        let notification = self
+            .write_parameter()?
+            .as_any_mut()
+            .downcast_mut::<Parameter<T>>()
+            .ok_or(CipError::InvalidParameter)?
+            .store_value(value)?;
+        // The write guard is released, the callback may read the parameter
+        if let Some(notify) = notification {
+            notify();
+        }
+        Ok(())
+    }
+
+    /// Returns the value of a [`BlobParameter`], `None` for other parameters.
+    pub fn blob(&self) -> Option<Vec<u8>> {
+        let parameter = self.parameter.read().ok()?;
+        parameter
+            .as_any()
+            .downcast_ref::<BlobParameter>()
+            .map(|blob| blob.value().to_vec())
+    }
+
+    /// Sets an attribute and runs the change callback once the write guard is released.
+    fn set_attribute(&self, attribute_id: u16, req: &mut Bytes) -> CipResult {
+        let notification = self.write_parameter()?.set_attribute(attribute_id, req)?;
+        if let Some(notify) = notification {
+            notify();
+        }
+        Ok(())
+    }
+
+    fn write_parameter(
+        &self,
+    ) -> Result<std::sync::RwLockWriteGuard<'_, Box<dyn ParameterAttributes>>, CipError> {
+        self.parameter.write().map_err(|_| {
+            log::error!("Failed to get write guard for parameter {}", self.id);
+            CipError::GeneralError
+        })
+    }
+
+    #[service(0x0E)]
+    pub fn get_attribute_single(&self, req: &mut Bytes, resp: &mut BytesMut) -> CipResult {
+        let attribute_id = u16::decode(req)?;
+        self.parameter
+            .read()
+            .map_err(|_| {
+                log::error!("Failed to get read guard for parameter {}", self.id);
+                CipError::GeneralError
+            })?
+            .get_attribute(attribute_id, resp)
+    }
+
+    #[service(0x10)]
+    pub fn set_attribute_single(&self, req: &mut Bytes, _resp: &mut BytesMut) -> CipResult {
+        let attribute_id = u16::decode(req)?;
+        self.set_attribute(attribute_id, req)
+    }
+
+    /// Reads an attribute from a byte offset: attribute id (UINT) and offset (UDINT). The
+    /// reply holds what fits in an unconnected reply, with Partial Transfer while data
+    /// remains.
+    #[service(0x4C)]
+    pub fn get_attribute_fragment(&self, req: &mut Bytes, resp: &mut BytesMut) -> ServiceResult {
+        let attribute_id = u16::decode(req)?;
+        let offset = u32::decode(req)?;
+
+        let mut value = BytesMut::new();
+        self.parameter
+            .read()
+            .map_err(|_| {
+                log::error!("Failed to get read guard for parameter {}", self.id);
+                CipError::GeneralError
+            })?
+            .get_attribute(attribute_id, &mut value)?;
+        read_fragment(&value, offset, MAX_UNCONNECTED_REPLY_SIZE, resp)
+    }
+
+    /// Writes an attribute in fragments: attribute id (UINT), offset (UDINT), total size
+    /// (UDINT) and the fragment. The attribute is set when the last fragment arrives, a
+    /// fragment at offset 0 starts a new sequence.
+    #[service(0x4D)]
+    pub fn set_attribute_fragment(&self, req: &mut Bytes, _resp: &mut BytesMut) -> ServiceResult {
+        let attribute_id = u16::decode(req)?;
+        let offset = u32::decode(req)?;
+        let total_len = u32::decode(req)? as usize;
+
+        let mut fragmented_write = self.fragmented_write.lock().map_err(|_| {
+            log::error!("Failed to lock fragmented write of parameter {}", self.id);
+            CipError::GeneralError
+        })?;
+        let (sequence_attribute, write) = &mut *fragmented_write;
+        if offset == 0 {
+            *sequence_attribute = attribute_id;
+        } else if *sequence_attribute != attribute_id {
+            return Err(CipError::ServiceFragmentationSequenceNotInProgress);
+        }
+
+        let value = write.write(offset, req, total_len)?;
+        drop(fragmented_write);
+        if let Some(value) = value {
+            self.set_attribute(attribute_id, &mut Bytes::from(value))?;
+        }
+        req.advance(req.remaining());
+        Ok(ReplyStatus::Success)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cip::registry::Registry;
+    use std::sync::atomic::{AtomicU16, Ordering};
+
+    fn speed_parameter() -> Parameter<UInt> {
+        Parameter::new("Speed", UInt::new(100), UInt::new(10), UInt::new(1000))
+            .with_units("rpm")
+            .with_help("Motor speed setpoint")
+    }
+
+    #[test]
+    fn get_attribute_single_returns_parameter_attributes() {
+        let mut instance = ParameterInstance::new(1, Box::new(speed_parameter()));
+
+        let mut resp = BytesMut::new();
+        instance
+            .execute_service(0x0E, &mut Bytes::from_static(&[0x01, 0x00]), &mut resp)
+            .expect("Failed to get value");
+        instance
+            .execute_service(0x0E, &mut Bytes::from_static(&[0x05, 0x00]), &mut resp)
+            .expect("Failed to get data type");
+        instance
+            .execute_service(0x0E, &mut Bytes::from_static(&[0x08, 0x00]), &mut resp)
+            .expect("Failed to get units");
+
+        assert_eq!(
+            resp.as_ref(),
+            &[
+                0x64, 0x00, // Value
+                0xC7, // Data type (UINT)
+                0x03, b'r', b'p', b'm', // Units
+            ]
+        );
+    }
+
+    #[test]
+    fn set_attribute_single_updates_value_and_notifies() {
+        let notified = Arc::new(AtomicU16::new(0));
+        let notified_clone = notified.clone();
+        let parameter = speed_parameter()
+            .on_change(move |value| notified_clone.store(value.value(), Ordering::Relaxed));
+        let mut instance = ParameterInstance::new(1, Box::new(parameter));
+
+        let mut req = Bytes::from_static(&[0x01, 0x00, 0xF4, 0x01]);
+        instance
+            .execute_service(0x10, &mut req, &mut BytesMut::new())
+            .expect("Failed to set value");
+
+        assert_eq!(instance.value::<UInt>(), Some(UInt::new(500)));
+        assert_eq!(notified.load(Ordering::Relaxed), 500);
+    }
+
+    #[test]
+    fn set_attribute_single_out_of_range_returns_error() {
+        let mut instance = ParameterInstance::new(1, Box::new(speed_parameter()));
+
+        let mut req = Bytes::from_static(&[0x01, 0x00, 0x05, 0x00]);
+        let result = instance.execute_service(0x10, &mut req, &mut BytesMut::new());
+
+        assert!(matches!(result, Err(CipError::InvalidAttributeValue)));
+        assert_eq!(instance.value::<UInt>(), Some(UInt::new(100)));
+    }
+
+    #[test]
+    fn set_attribute_single_read_only_returns_error() {
+        let mut instance = ParameterInstance::new(1, Box::new(speed_parameter().read_only()));
+
+        let mut req = Bytes::from_static(&[0x01, 0x00, 0xF4, 0x01]);
+        let result = instance.execute_service(0x10, &mut req, &mut BytesMut::new());
+
+        assert!(matches!(result, Err(CipError::AttributeNotSetable)));
+    }
+
+    fn fragment_request(attribute_id: u16, offset: u32, total_len: u32, data: &[u8]) -> Bytes {
+        let mut req = BytesMut::new();
+        req.put_u16_le(attribute_id);
+        req.put_u32_le(offset);
+        req.put_u32_le(total_len);
+        req.put_slice(data);
+        req.freeze()
+    }
+
+    #[test]
+    fn set_attribute_fragment_sets_blob_with_last_fragment() {
+        let table: Vec<u8> = (0..=255).cycle().take(700).collect();
+        let instance =
+            ParameterInstance::new(1, Box::new(BlobParameter::new("Table", vec![], 1024)));
+
+        let first = instance.execute_shared_with_status(
+            0x4D,
+            &mut fragment_request(1, 0, 700, &table[..400]),
+            &mut BytesMut::new(),
+        );
+        assert!(matches!(first, Ok(ReplyStatus::Success)));
+        assert_eq!(instance.blob(), Some(vec![]));
+
+        let last = instance.execute_shared_with_status(
+            0x4D,
+            &mut fragment_request(1, 400, 700, &table[400..]),
+            &mut BytesMut::new(),
+        );
+        assert!(matches!(last, Ok(ReplyStatus::Success)));
+        assert_eq!(instance.blob(), Some(table));
+    }
+
+    #[test]
+    fn set_attribute_fragment_out_of_sequence_returns_error() {
+        let instance =
+            ParameterInstance::new(1, Box::new(BlobParameter::new("Table", vec![], 1024)));
+
+        let result = instance.execute_shared_with_status(
+            0x4D,
+            &mut fragment_request(1, 400, 700, &[0; 300]),
+            &mut BytesMut::new(),
+        );
+
+        assert!(matches!(
+            result,
+            Err(CipError::ServiceFragmentationSequenceNotInProgress)
+        ));
+    }
+
+    #[test]
+    fn set_attribute_fragment_past_max_length_returns_error() {
+        let instance = ParameterInstance::new(1, Box::new(BlobParameter::new("Table", vec![], 16)));
+
+        let result = instance.execute_shared_with_status(
+            0x4D,
+            &mut fragment_request(1, 0, 32, &[0; 32]),
+            &mut BytesMut::new(),
+        );
+
+        assert!(matches!(result, Err(CipError::TooMuchData)));
+        assert_eq!(instance.blob(), Some(vec![]));
+    }
+
+    #[test]
+    fn class_get_attribute_single_returns_class_attributes() {
+        let mut class = ParameterClass::with_parameters(vec![
+            Box::new(speed_parameter()),
+            Box::new(Parameter::new(
+                "Filter",
+                USInt::new(1),
+                USInt::new(0),
+                USInt::new(5),
+            )),
+        ]);
+        let class = Arc::get_mut(&mut class).expect("Failed to get class");
+
+        let mut resp = BytesMut::new();
+        class
+            .execute_service(0x0E, &mut Bytes::from_static(&[0x02, 0x00]), &mut resp)
+            .expect("Failed to get max instance");
+
+        assert_eq!(resp.as_ref(), &[0x02, 0x00]);
+        assert!(class.get_instance(2).is_ok());
+
+        let mut resp = BytesMut::new();
+        for attribute_id in [8u8, 9, 10] {
+            class
+                .execute_service(0x0E, &mut Bytes::from(vec![attribute_id, 0x00]), &mut resp)
+                .expect("Failed to get class attribute");
+        }
+        assert_eq!(
+            resp.as_ref(),
+            &[
+                0x03, 0x00, // Parameter class descriptor
+                0x00, 0x00, // Configuration assembly instance
+                0x00, // Native language
+            ]
+        );
+    }
+
+    #[test]
+    fn set_through_registry_updates_value_and_notifies() {
+        let notified = Arc::new(AtomicU16::new(0));
+        let notified_clone = notified.clone();
+        let parameter = speed_parameter()
+            .on_change(move |value| notified_clone.store(value.value(), Ordering::Relaxed));
+        let mut registry = Registry::new();
+        registry
+            .register(ParameterClass::with_parameters(vec![Box::new(parameter)]))
+            .expect("Failed to register Parameter class");
+
+        let instance = registry
+            .get(0x0F)
+            .expect("Failed to get Parameter class")
+            .get_instance(1)
+            .expect("Failed to get parameter");
+        instance
+            .execute_shared(
+                0x10,
+                &mut Bytes::from_static(&[0x01, 0x00, 0xF4, 0x01]),
+                &mut BytesMut::new(),
+            )
+            .expect("Failed to set value");
+
+        let mut resp = BytesMut::new();
+        instance
+            .execute_shared(0x0E, &mut Bytes::from_static(&[0x01, 0x00]), &mut resp)
+            .expect("Failed to get value");
+        assert_eq!(resp.as_ref(), &[0xF4, 0x01]);
+        assert_eq!(notified.load(Ordering::Relaxed), 500);
+    }
+
+    #[test]
+    fn on_change_can_read_parameter_back_through_registry() {
+        let registry = Arc::new(std::sync::OnceLock::<Registry>::new());
+        let read_back = Arc::new(Mutex::new(Vec::new()));
+        let read_parameter = |registry: &std::sync::OnceLock<Registry>| {
+            registry
+                .get()
+                .expect("Registry not set")
+                .get_instance::<ParameterInstance>(ClassCode::Parameter, 1)
+                .expect("Failed to get parameter")
+        };
+
+        let (speed_registry, speed_read_back) = (registry.clone(), read_back.clone());
+        let speed = speed_parameter().on_change(move |_| {
+            let value = read_parameter(&speed_registry).value::<UInt>();
+            speed_read_back
+                .lock()
+                .expect("Failed to lock read back")
+                .push(value.map(|value| value.value() as usize));
+        });
+        let (blob_registry, blob_read_back) = (registry.clone(), read_back.clone());
+        let table = BlobParameter::new("Table", vec![], 16).on_change(move |_| {
+            let value = blob_registry
+                .get()
+                .expect("Registry not set")
+                .get_instance::<ParameterInstance>(ClassCode::Parameter, 2)
+                .expect("Failed to get parameter")
+                .blob();
+            blob_read_back
+                .lock()
+                .expect("Failed to lock read back")
+                .push(value.map(|value| value.len()));
+        });
+        let mut parameters = Registry::new();
+        parameters
+            .register(ParameterClass::with_parameters(vec![
+                Box::new(speed),
+                Box::new(table),
+            ]))
+            .expect("Failed to register Parameter class");
+        let _ = registry.set(parameters);
+
+        read_parameter(&registry)
+            .execute_shared(
+                0x10,
+                &mut Bytes::from_static(&[0x01, 0x00, 0xF4, 0x01]),
+                &mut BytesMut::new(),
+            )
+            .expect("Failed to set value");
+        read_parameter(&registry)
+            .set_value(UInt::new(600))
+            .expect("Failed to set value from the application");
+        registry
+            .get()
+            .expect("Registry not set")
+            .get_instance::<ParameterInstance>(ClassCode::Parameter, 2)
+            .expect("Failed to get parameter")
+            .execute_shared(
+                0x10,
+                &mut Bytes::from_static(&[0x01, 0x00, 0xAA, 0xBB, 0xCC]),
+                &mut BytesMut::new(),
+            )
+            .expect("Failed to set blob");
+
+        assert_eq!(
+            *read_back.lock().expect("Failed to lock read back"),
+            vec![Some(500), Some(600), Some(3)]
+        );
+    }
+}
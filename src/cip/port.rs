@@ -0,0 +1,227 @@
+use std::{
+    collections::HashMap,
+    net::Ipv4Addr,
+    sync::{Arc, RwLock},
+};
+
+use bytes::{Buf, BufMut};
+use cip_macros::{CipClass, CipInstance};
+
+use super::{
+    ClassCode,
+    common::error::CipError,
+    common::object::{CipClass, CipInstance, CipObject, CipResult},
+    data_types::{
+        epath::{LogicalSegment, PaddedEPath, PortSegment, Segment, SizedEPath},
+        short_string::ShortString,
+    },
+};
+use crate::common::binary::{BinaryError, ToBytes};
+
+/// Port type reported for EtherNet/IP ports.
+pub const PORT_TYPE_ETHERNET_IP: u16 = 4;
+/// Port number 1 is reserved for the backplane, so EtherNet/IP ports start at 2.
+pub const FIRST_ETHERNET_PORT_NUMBER: u16 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PortInstanceInfoEntry {
+    pub port_type: u16,
+    pub port_number: u16,
+}
+
+/// Class attribute 9: port type and number of each instance, in instance order.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PortInstanceInfo(pub Vec<PortInstanceInfoEntry>);
+
+impl PortInstanceInfo {
+    const ENTRY_LEN: usize = 4;
+}
+
+impl ToBytes for PortInstanceInfo {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < self.encoded_len() {
+            return Err(BinaryError::BufferTooSmall {
+                expected: self.encoded_len(),
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        for entry in &self.0 {
+            buffer.put_u16_le(entry.port_type);
+            buffer.put_u16_le(entry.port_number);
+        }
+
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        self.0.len() * Self::ENTRY_LEN
+    }
+}
+
+#[derive(CipClass)]
+#[cip(id = ClassCode::Port, name = "Port", singleton = false)]
+pub struct PortClass {
+    #[attribute(id = 1, access = "get")]
+    revision: u16,
+
+    #[attribute(id = 2, access = "get")]
+    max_instance: u16,
+
+    #[attribute(id = 3, access = "get")]
+    number_of_instances: u16,
+
+    #[attribute(id = 8, access = "get")]
+    entry_port: u16,
+
+    #[attribute(id = 9, access = "get")]
+    port_instance_info: PortInstanceInfo,
+
+    pub instances: RwLock<HashMap<u16, Arc<dyn CipInstance>>>,
+}
+
+impl PortClass {
+    pub const REVISION: u16 = 2;
+
+    /// Creates one EtherNet/IP port per interface address. Port instance `n` is linked to
+    /// TCP/IP Interface instance `n`, and the first port is reported as the entry port.
+    pub fn with_ethernet_ports(addresses: &[Ipv4Addr]) -> Arc<Self> {
+        let ports = addresses
+            .iter()
+            .enumerate()
+            .map(|(index, address)| PortInstance::ethernet(index as u16 + 1, *address))
+            .collect::<Vec<_>>();
+
+        let port_instance_info = PortInstanceInfo(
+            ports
+                .iter()
+                .map(|port| PortInstanceInfoEntry {
+                    port_type: port.port_type,
+                    port_number: port.port_number,
+                })
+                .collect(),
+        );
+
+        let instances = ports
+            .into_iter()
+            .map(|port| (port.id, Arc::new(port) as Arc<dyn CipInstance>))
+            .collect::<HashMap<_, _>>();
+
+        Arc::new(Self {
+            revision: Self::REVISION,
+            max_instance: instances.len() as u16,
+            number_of_instances: instances.len() as u16,
+            entry_port: if instances.is_empty() { 0 } else { 1 },
+            port_instance_info,
+            instances: RwLock::new(instances),
+        })
+    }
+}
+
+#[derive(Debug, CipInstance)]
+pub struct PortInstance {
+    id: u16,
+    class_id: ClassCode,
+
+    #[attribute(id = 1, access = "get")]
+    port_type: u16,
+
+    #[attribute(id = 2, access = "get")]
+    port_number: u16,
+
+    #[attribute(id = 3, access = "get")]
+    link_object: SizedEPath,
+
+    #[attribute(id = 4, access = "get")]
+    port_name: ShortString,
+
+    #[attribute(id = 7, access = "get")]
+    node_address: PaddedEPath,
+}
+
+impl PortInstance {
+    /// EtherNet/IP port linked to the TCP/IP Interface instance with the same id.
+    pub fn ethernet(id: u16, address: Ipv4Addr) -> Self {
+        let port_number = FIRST_ETHERNET_PORT_NUMBER + id - 1;
+        let link_object = SizedEPath::new(PaddedEPath::new(vec![
+            Segment::Logical(LogicalSegment::class_id(ClassCode::TcpIpInterface.into())),
+            Segment::Logical(LogicalSegment::instance_id(id as u32)),
+        ]));
+        let node_address = PaddedEPath::new(vec![Segment::Port(PortSegment::from_port_and_ip(
+            port_number as u8,
+            address,
+        ))]);
+
+        Self {
+            id,
+            class_id: ClassCode::Port,
+            port_type: PORT_TYPE_ETHERNET_IP,
+            port_number,
+            link_object,
+            port_name: ShortString::new(format!("EtherNet/IP Port {}", id).as_str()),
+            node_address,
+        }
+    }
+
+    pub fn port_number(&self) -> u16 {
+        self.port_number
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bytes::{Bytes, BytesMut};
+
+    fn get_attribute(object: &mut dyn CipObject, attribute_id: u16) -> BytesMut {
+        let mut req = Bytes::copy_from_slice(&attribute_id.to_le_bytes());
+        let mut resp = BytesMut::new();
+        object
+            .execute_service(0x0E, &mut req, &mut resp)
+            .expect("Failed to get attribute");
+        resp
+    }
+
+    #[test]
+    fn ethernet_port_attributes_encode_correctly() {
+        let mut port = PortInstance::ethernet(1, Ipv4Addr::new(192, 168, 1, 10));
+
+        assert_eq!(get_attribute(&mut port, 1).as_ref(), &[0x04, 0x00]);
+        assert_eq!(get_attribute(&mut port, 2).as_ref(), &[0x02, 0x00]);
+        assert_eq!(
+            get_attribute(&mut port, 3).as_ref(),
+            &[
+                0x02, 0x00, // Path size in words
+                0x20, 0xF5, // Class 0xF5 (TCP/IP Interface)
+                0x24, 0x01, // Instance 1
+            ]
+        );
+        assert_eq!(
+            get_attribute(&mut port, 7).as_ref(),
+            &[
+                0x12, 0x0C, // Port 2, extended link address of 12 bytes
+                b'1', b'9', b'2', b'.', b'1', b'6', b'8', b'.', b'1', b'.', b'1', b'0',
+            ]
+        );
+    }
+
+    #[test]
+    fn class_attributes_describe_generated_ports() {
+        let mut class = PortClass::with_ethernet_ports(&[
+            Ipv4Addr::new(10, 0, 0, 1),
+            Ipv4Addr::new(10, 0, 1, 1),
+        ]);
+        let class = Arc::get_mut(&mut class).expect("Failed to get class");
+
+        assert_eq!(get_attribute(class, 2).as_ref(), &[0x02, 0x00]);
+        assert_eq!(get_attribute(class, 8).as_ref(), &[0x01, 0x00]);
+        assert_eq!(
+            get_attribute(class, 9).as_ref(),
+            &[
+                0x04, 0x00, 0x02, 0x00, // Instance 1: EtherNet/IP, port 2
+                0x04, 0x00, 0x03, 0x00, // Instance 2: EtherNet/IP, port 3
+            ]
+        );
+        assert!(class.get_instance(2).is_ok());
+    }
+}
@@ -0,0 +1,310 @@
+use std::{
+    io,
+    sync::{Arc, RwLock},
+};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use cip_macros::{CipClass, CipInstance, cip_object_impl};
+use socket2::SockRef;
+
+use super::{
+    ClassCode,
+    common::error::CipError,
+    common::object::{CipClass, CipInstance, CipObject, CipResult},
+};
+use crate::common::{
+    binary::{BinaryError, FromBytes, ToBytes},
+    storage::NonVolatileStorage,
+};
+
+const STORAGE_KEY: &str = "qos";
+
+/// Traffic classes used to pick the DSCP value of an I/O connection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IoPriority {
+    Urgent,
+    Scheduled,
+    High,
+    Low,
+}
+
+/// QoS instance attributes 1-8, in attribute order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QosConfig {
+    pub tag_enable: u8,
+    pub dscp_ptp_event: u8,
+    pub dscp_ptp_general: u8,
+    pub dscp_urgent: u8,
+    pub dscp_scheduled: u8,
+    pub dscp_high: u8,
+    pub dscp_low: u8,
+    pub dscp_explicit: u8,
+}
+
+impl QosConfig {
+    pub const LEN: usize = 8;
+    pub const MAX_DSCP: u8 = 63;
+
+    pub fn io_dscp(&self, priority: IoPriority) -> u8 {
+        match priority {
+            IoPriority::Urgent => self.dscp_urgent,
+            IoPriority::Scheduled => self.dscp_scheduled,
+            IoPriority::High => self.dscp_high,
+            IoPriority::Low => self.dscp_low,
+        }
+    }
+
+    fn attribute(&self, attribute_id: u16) -> Option<u8> {
+        let mut config = *self;
+        config.attribute_mut(attribute_id).map(|value| *value)
+    }
+
+    fn attribute_mut(&mut self, attribute_id: u16) -> Option<&mut u8> {
+        match attribute_id {
+            1 => Some(&mut self.tag_enable),
+            2 => Some(&mut self.dscp_ptp_event),
+            3 => Some(&mut self.dscp_ptp_general),
+            4 => Some(&mut self.dscp_urgent),
+            5 => Some(&mut self.dscp_scheduled),
+            6 => Some(&mut self.dscp_high),
+            7 => Some(&mut self.dscp_low),
+            8 => Some(&mut self.dscp_explicit),
+            _ => None,
+        }
+    }
+
+    fn validate(&self) -> CipResult {
+        let dscp_values = [
+            self.dscp_ptp_event,
+            self.dscp_ptp_general,
+            self.dscp_urgent,
+            self.dscp_scheduled,
+            self.dscp_high,
+            self.dscp_low,
+            self.dscp_explicit,
+        ];
+
+        if self.tag_enable > 1 || dscp_values.iter().any(|dscp| *dscp > Self::MAX_DSCP) {
+            return Err(CipError::InvalidAttributeValue);
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for QosConfig {
+    fn default() -> Self {
+        Self {
+            tag_enable: 0,
+            dscp_ptp_event: 59,
+            dscp_ptp_general: 47,
+            dscp_urgent: 55,
+            dscp_scheduled: 47,
+            dscp_high: 43,
+            dscp_low: 31,
+            dscp_explicit: 27,
+        }
+    }
+}
+
+impl FromBytes for QosConfig {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        if buffer.remaining() < Self::LEN {
+            return Err(BinaryError::Truncated {
+                expected: Self::LEN,
+                actual: buffer.remaining(),
+            });
+        }
+
+        Ok(Self {
+            tag_enable: buffer.get_u8(),
+            dscp_ptp_event: buffer.get_u8(),
+            dscp_ptp_general: buffer.get_u8(),
+            dscp_urgent: buffer.get_u8(),
+            dscp_scheduled: buffer.get_u8(),
+            dscp_high: buffer.get_u8(),
+            dscp_low: buffer.get_u8(),
+            dscp_explicit: buffer.get_u8(),
+        })
+    }
+}
+
+impl ToBytes for QosConfig {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < Self::LEN {
+            return Err(BinaryError::BufferTooSmall {
+                expected: Self::LEN,
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        buffer.put_u8(self.tag_enable);
+        buffer.put_u8(self.dscp_ptp_event);
+        buffer.put_u8(self.dscp_ptp_general);
+        buffer.put_u8(self.dscp_urgent);
+        buffer.put_u8(self.dscp_scheduled);
+        buffer.put_u8(self.dscp_high);
+        buffer.put_u8(self.dscp_low);
+        buffer.put_u8(self.dscp_explicit);
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        Self::LEN
+    }
+}
+
+/// Marks the traffic sent through `socket` with `dscp` using `IP_TOS`.
+pub fn apply_dscp<'s, S>(socket: &'s S, dscp: u8) -> io::Result<()>
+where
+    SockRef<'s>: From<&'s S>,
+{
+    // DSCP occupies the upper six bits of the TOS byte
+    SockRef::from(socket).set_tos_v4((dscp as u32) << 2)
+}
+
+#[derive(CipClass)]
+#[cip(id = ClassCode::Qos, name = "QoS", singleton = true, custom_services = true)]
+pub struct QosClass {
+    pub instance: RwLock<Arc<QosInstance>>,
+}
+
+#[cip_object_impl]
+impl QosClass {
+    pub fn new(instance: Arc<QosInstance>) -> Arc<Self> {
+        Arc::new(Self {
+            instance: RwLock::new(instance),
+        })
+    }
+}
+
+#[derive(CipInstance)]
+#[cip(custom_services = true)]
+pub struct QosInstance {
+    id: u16,
+    class_id: ClassCode,
+    config: RwLock<QosConfig>,
+    storage: Option<Arc<dyn NonVolatileStorage>>,
+}
+
+#[cip_object_impl]
+impl QosInstance {
+    /// Creates the instance with the configuration persisted in `storage`, or the defaults.
+    pub fn new(storage: Option<Arc<dyn NonVolatileStorage>>) -> Self {
+        let config = storage
+            .as_ref()
+            .and_then(|storage| match storage.load(STORAGE_KEY) {
+                Ok(data) => data,
+                Err(err) => {
+                    log::error!("Failed to load QoS configuration: {}", err);
+                    None
+                }
+            })
+            .and_then(|data| QosConfig::decode(&mut Bytes::from(data)).ok())
+            .filter(|config| config.validate().is_ok())
+            .unwrap_or_default();
+
+        Self {
+            id: 1,
+            class_id: ClassCode::Qos,
+            config: RwLock::new(config),
+            storage,
+        }
+    }
+
+    pub fn config(&self) -> QosConfig {
+        match self.config.read() {
+            Ok(config) => *config,
+            Err(_) => {
+                log::error!("Failed to get read guard for QoS configuration");
+                QosConfig::default()
+            }
+        }
+    }
+
+    /// Validates, applies and persists a new configuration.
+    pub fn set_config(&self, config: QosConfig) -> CipResult {
+        config.validate()?;
+
+        if let Some(storage) = &self.storage {
+            let mut data = BytesMut::with_capacity(QosConfig::LEN);
+            config.encode(&mut data)?;
+            storage.store(STORAGE_KEY, &data).map_err(|err| {
+                log::error!("Failed to store QoS configuration: {}", err);
+                CipError::StoreOperationFailure
+            })?;
+        }
+
+        let mut guard = self.config.write().map_err(|_| {
+            log::error!("Failed to get write guard for QoS configuration");
+            CipError::GeneralError
+        })?;
+        *guard = config;
+        Ok(())
+    }
+
+    #[service(0x0E)]
+    pub fn get_attribute_single(&self, req: &mut Bytes, resp: &mut BytesMut) -> CipResult {
+        let attribute_id = u16::decode(req)?;
+        let value = self
+            .config()
+            .attribute(attribute_id)
+            .ok_or(CipError::AttributeNotSupported)?;
+        value.encode(resp)?;
+        Ok(())
+    }
+
+    #[service(0x10)]
+    pub fn set_attribute_single(&self, req: &mut Bytes, _resp: &mut BytesMut) -> CipResult {
+        let attribute_id = u16::decode(req)?;
+        let mut config = self.config();
+        let value = config
+            .attribute_mut(attribute_id)
+            .ok_or(CipError::AttributeNotSupported)?;
+        *value = u8::decode(req)?;
+        self.set_config(config)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::storage::MemoryStorage;
+
+    #[test]
+    fn set_attribute_single_updates_and_persists_dscp() {
+        let storage = Arc::new(MemoryStorage::new());
+        let mut instance = QosInstance::new(Some(storage.clone()));
+
+        let mut req = Bytes::from_static(&[0x08, 0x00, 0x2E]);
+        instance
+            .execute_service(0x10, &mut req, &mut BytesMut::new())
+            .expect("Failed to set DSCP explicit");
+
+        assert_eq!(instance.config().dscp_explicit, 46);
+        let restored = QosInstance::new(Some(storage));
+        assert_eq!(restored.config().dscp_explicit, 46);
+    }
+
+    #[test]
+    fn set_attribute_single_invalid_dscp_returns_error() {
+        let mut instance = QosInstance::new(None);
+
+        let mut req = Bytes::from_static(&[0x04, 0x00, 0x40]);
+        let result = instance.execute_service(0x10, &mut req, &mut BytesMut::new());
+
+        assert!(matches!(result, Err(CipError::InvalidAttributeValue)));
+        assert_eq!(instance.config(), QosConfig::default());
+    }
+
+    #[test]
+    fn apply_dscp_sets_ip_tos() {
+        let socket = std::net::UdpSocket::bind("127.0.0.1:0").expect("Failed to bind socket");
+
+        apply_dscp(&socket, 46).expect("Failed to apply DSCP");
+
+        let tos = SockRef::from(&socket).tos_v4().expect("Failed to read TOS");
+        assert_eq!(tos, 46 << 2);
+    }
+}
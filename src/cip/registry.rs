@@ -1,21 +1,47 @@
-use std::{collections::HashMap, sync::Arc};
+use std::{
+    collections::{HashMap, hash_map::Entry},
+    sync::Arc,
+};
+
+use bytes::{BufMut, Bytes, BytesMut};
 
 use super::ClassCode;
-use super::common::object::CipClass;
+use super::common::{
+    error::CipError,
+    object::{CipClass, CipObject, CipResult},
+};
+use super::events::{AttributeChanged, AttributeNotifier};
 
 pub struct Registry {
     classes: HashMap<u16, Arc<dyn CipClass>>,
+    notifier: AttributeNotifier,
 }
 
 impl Registry {
     pub fn new() -> Self {
         Self {
             classes: HashMap::new(),
+            notifier: AttributeNotifier::new(),
         }
     }
 
-    pub fn register(&mut self, class: Arc<dyn CipClass>) {
-        self.classes.insert(class.id().into(), class);
+    /// Publishes the attribute changes of the registered objects.
+    pub fn notifier(&self) -> AttributeNotifier {
+        self.notifier.clone()
+    }
+
+    /// Adds a class, a class with the same id must not be registered yet.
+    pub fn register(&mut self, class: Arc<dyn CipClass>) -> Result<(), CipError> {
+        match self.classes.entry(class.id().into()) {
+            Entry::Occupied(_) => {
+                log::error!("Class {} is already registered", class.id());
+                Err(CipError::ObjectAlreadyExists)
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(class);
+                Ok(())
+            }
+        }
     }
 
     pub fn get(&self, class_id: u16) -> Option<Arc<dyn CipClass>> {
@@ -23,6 +49,48 @@ impl Registry {
         self.classes.get(&class_id).cloned()
     }
 
+    /// Class for instance 0, otherwise one of its instances.
+    pub fn get_object(
+        &self,
+        class_id: u16,
+        instance_id: u16,
+    ) -> Result<Arc<dyn CipObject>, CipError> {
+        let class = self.get(class_id).ok_or(CipError::PathDestinationUnknown)?;
+        if instance_id == 0 {
+            return Ok(class);
+        }
+
+        class
+            .get_instance(instance_id)
+            .map(|instance| instance as Arc<dyn CipObject>)
+            .map_err(|_| CipError::PathDestinationUnknown)
+    }
+
+    /// Writes an attribute from the application through Set_Attribute_Single and publishes
+    /// the change. `value` is the attribute encoded as on the network.
+    pub fn write_attribute(
+        &self,
+        class_id: ClassCode,
+        instance_id: u16,
+        attribute_id: u16,
+        value: Bytes,
+    ) -> CipResult {
+        let object = self.get_object(class_id.into(), instance_id)?;
+        let mut req = BytesMut::with_capacity(2 + value.len());
+        req.put_u16_le(attribute_id);
+        req.put_slice(&value);
+        object.execute_shared(0x10, &mut req.freeze(), &mut BytesMut::new())?;
+
+        self.notifier.notify(AttributeChanged {
+            class: class_id,
+            instance: instance_id,
+            attribute: attribute_id,
+            new_value_bytes: value,
+            source_peer: None,
+        });
+        Ok(())
+    }
+
     pub fn get_instance<T: 'static + Send + Sync>(
         &self,
         class_id: ClassCode,
@@ -51,7 +119,9 @@ impl Registry {
 mod tests {
     use super::*;
     use crate::cip::cip_identity::{IdentityClass, IdentityInfo, IdentityInstance};
+    use crate::cip::dynamic::DynamicClass;
     use crate::cip::tcp_ip_interface::{TcpIpInterfaceClass, TcpIpInterfaceInstance};
+    use bytes::{Bytes, BytesMut};
     use std::net::Ipv4Addr;
     use std::sync::Arc;
 
@@ -69,7 +139,9 @@ mod tests {
             product_name: "DeviceA",
         };
         let identity_class = IdentityClass::with_default_instance(&identity_info);
-        registry.register(identity_class.clone());
+        registry
+            .register(identity_class.clone())
+            .expect("Failed to register class");
 
         let retrieved_class = registry
             .get(identity_class_id.into())
@@ -92,7 +164,9 @@ mod tests {
             product_name: "TestDevice",
         };
         let identity_class = IdentityClass::with_default_instance(&identity_info);
-        registry.register(identity_class.clone());
+        registry
+            .register(identity_class.clone())
+            .expect("Failed to register class");
 
         let identity_instance = registry
             .get_instance::<IdentityInstance>(ClassCode::Identity, 1)
@@ -129,7 +203,9 @@ mod tests {
             product_name: "X",
         };
         let identity_class = IdentityClass::with_default_instance(&identity_info);
-        registry.register(identity_class.clone());
+        registry
+            .register(identity_class.clone())
+            .expect("Failed to register class");
 
         let error_message = registry
             .get_instance::<IdentityInstance>(ClassCode::Identity, 2)
@@ -138,13 +214,119 @@ mod tests {
         assert!(error_message.contains("Instance"));
     }
 
+    #[test]
+    fn set_attribute_single_through_registered_instance() {
+        let mut registry = Registry::new();
+        let identity_info = IdentityInfo {
+            vendor_id: 0x0001,
+            device_type: 0x0002,
+            product_code: 0x0003,
+            revision_major: 0,
+            revision_minor: 0,
+            serial_number: 0,
+            product_name: "X",
+        };
+        registry
+            .register(IdentityClass::with_default_instance(&identity_info))
+            .expect("Failed to register class");
+        let instance = registry
+            .get(ClassCode::Identity.into())
+            .expect("Class not registered")
+            .get_instance(1)
+            .expect("Failed to get instance");
+
+        // Heartbeat interval (attribute 10) set to 5
+        instance
+            .execute_shared(
+                0x10,
+                &mut Bytes::from_static(&[0x0A, 0x00, 0x05]),
+                &mut BytesMut::new(),
+            )
+            .expect("Failed to set heartbeat interval");
+
+        let mut resp = BytesMut::new();
+        instance
+            .execute_shared(0x0E, &mut Bytes::from_static(&[0x0A, 0x00]), &mut resp)
+            .expect("Failed to get heartbeat interval");
+        assert_eq!(resp.as_ref(), &[0x05]);
+    }
+
+    #[test]
+    fn write_attribute_updates_instance_and_publishes_change() {
+        let mut registry = Registry::new();
+        let mut changes = registry.notifier().subscribe();
+        let tcp_ip_class = Arc::new(TcpIpInterfaceClass::new());
+        tcp_ip_class
+            .add_instance(Arc::new(TcpIpInterfaceInstance::new(
+                1,
+                Ipv4Addr::LOCALHOST,
+            )))
+            .expect("Failed to add instance");
+        registry
+            .register(tcp_ip_class)
+            .expect("Failed to register class");
+
+        // Host name (attribute 6) as a STRING
+        registry
+            .write_attribute(
+                ClassCode::TcpIpInterface,
+                1,
+                6,
+                Bytes::from_static(&[0x03, 0x00, b'p', b'l', b'c']),
+            )
+            .expect("Failed to write host name");
+
+        let instance = registry
+            .get_instance::<TcpIpInterfaceInstance>(ClassCode::TcpIpInterface, 1)
+            .expect("Failed to get instance");
+        let change = changes.try_recv().expect("Missing attribute change");
+        assert_eq!(instance.host_name(), "plc");
+        assert_eq!(change.class, ClassCode::TcpIpInterface);
+        assert_eq!(change.attribute, 6);
+        assert_eq!(change.source_peer, None);
+        assert!(
+            registry
+                .write_attribute(ClassCode::TcpIpInterface, 1, 1, Bytes::new())
+                .is_err()
+        );
+        assert!(changes.try_recv().is_err());
+    }
+
+    #[test]
+    fn register_duplicate_class_id_returns_error() {
+        let mut registry = Registry::new();
+        let identity_info = IdentityInfo {
+            vendor_id: 0x0001,
+            device_type: 0x0002,
+            product_code: 0x0003,
+            revision_major: 0,
+            revision_minor: 0,
+            serial_number: 0,
+            product_name: "X",
+        };
+        registry
+            .register(IdentityClass::with_default_instance(&identity_info))
+            .expect("Failed to register class");
+
+        let result = registry.register(DynamicClass::builder(0x01).instance(1).build());
+
+        assert!(matches!(result, Err(CipError::ObjectAlreadyExists)));
+        assert!(
+            registry
+                .get_instance::<IdentityInstance>(ClassCode::Identity, 1)
+                .is_ok()
+        );
+    }
+
     #[test]
     fn get_instance_downcast_failure_returns_error() {
         let mut registry = Registry::new();
         let tcp_class = Arc::new(TcpIpInterfaceClass::new());
         let tcp_instance = Arc::new(TcpIpInterfaceInstance::new(1, Ipv4Addr::LOCALHOST));
         tcp_class.add_instance(tcp_instance).unwrap();
-        registry.register(tcp_class.clone());
+        registry
+            .register(tcp_class.clone())
+            .expect("Failed to register class");
 
         let error_message = registry
             .get_instance::<IdentityInstance>(ClassCode::TcpIpInterface, 1)
@@ -2,7 +2,7 @@ use std::net::Ipv4Addr;
 
 use bytes::Buf;
 
-use cip_macros::{CipClass, CipInstance, cip_object_impl};
+use cip_macros::{CipClass, CipDecode, CipEncode, CipInstance, cip_object_impl};
 
 use super::{
     ClassCode,
@@ -12,7 +12,7 @@ use super::{
 use crate::{
     cip::data_types::{
         CipString, UDInt,
-        epath::{PortSegment, Segment},
+        epath::{PortSegment, Segment, SizedEPath},
     },
     common::binary::{FromBytes, ToBytes},
 };
@@ -39,67 +39,12 @@ impl TcpIpInterfaceClass {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
-struct PhysicalLink {
-    path_size: u16,
-    path: PaddedEPath,
-}
-
-impl PhysicalLink {
-    pub const MIN_LEN: usize = 4;
-
-    pub fn new(path: PaddedEPath) -> Self {
-        Self {
-            path_size: (path.encoded_len() / 2) as u16,
-            path,
-        }
-    }
-}
-
-impl FromBytes for PhysicalLink {
-    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
-        if buffer.remaining() < Self::MIN_LEN {
-            return Err(BinaryError::Truncated {
-                expected: Self::MIN_LEN,
-                actual: buffer.remaining(),
-            });
-        }
-
-        let path_size = buffer.get_u16_le();
-        let path_size_bytes = (path_size * 2) as usize;
-
-        if buffer.remaining() < path_size_bytes {
-            return Err(BinaryError::Truncated {
-                expected: path_size_bytes,
-                actual: buffer.remaining(),
-            });
-        }
-
-        let path = PaddedEPath::decode(&mut buffer.copy_to_bytes(path_size_bytes))?;
-        Ok(Self { path_size, path })
-    }
-}
-
-impl ToBytes for PhysicalLink {
-    fn encode<T: bytes::BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
-        if buffer.remaining_mut() < self.encoded_len() {
-            return Err(BinaryError::BufferTooSmall {
-                expected: self.encoded_len(),
-                actual: buffer.remaining_mut(),
-            });
-        }
-
-        buffer.put_u16_le(self.path_size);
-        self.path.encode(buffer)?;
-        Ok(())
-    }
-
-    fn encoded_len(&self) -> usize {
-        self.path.encoded_len() + 2
-    }
-}
-
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, CipEncode, CipDecode)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(from = "InterfaceSettings", into = "InterfaceSettings")
+)]
 pub struct InterfaceConfiguration {
     ip_address: UDInt,
     network_mask: UDInt,
@@ -122,50 +67,45 @@ impl InterfaceConfiguration {
     }
 }
 
-impl FromBytes for InterfaceConfiguration {
-    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
-        let ip_address = UDInt::decode(buffer)?;
-        let network_mask = UDInt::decode(buffer)?;
-        let gateway_address = UDInt::decode(buffer)?;
-        let name_server = UDInt::decode(buffer)?;
-        let name_server_2 = UDInt::decode(buffer)?;
-        let domain_name = CipString::decode(buffer)?;
-        Ok(Self {
-            ip_address,
-            network_mask,
-            gateway_address,
-            name_server,
-            name_server_2,
-            domain_name,
-        })
-    }
+/// Interface configuration as written in configuration files, with dotted addresses.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct InterfaceSettings {
+    ip_address: Ipv4Addr,
+    network_mask: Ipv4Addr,
+    gateway_address: Ipv4Addr,
+    name_server: Ipv4Addr,
+    name_server_2: Ipv4Addr,
+    domain_name: CipString<48>,
 }
 
-impl ToBytes for InterfaceConfiguration {
-    fn encode<T: bytes::BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
-        if buffer.remaining_mut() < self.encoded_len() {
-            return Err(BinaryError::BufferTooSmall {
-                expected: self.encoded_len(),
-                actual: buffer.remaining_mut(),
-            });
+#[cfg(feature = "serde")]
+impl From<InterfaceSettings> for InterfaceConfiguration {
+    fn from(settings: InterfaceSettings) -> Self {
+        let address = |ip: Ipv4Addr| UDInt::new(u32::from(ip));
+        Self {
+            ip_address: address(settings.ip_address),
+            network_mask: address(settings.network_mask),
+            gateway_address: address(settings.gateway_address),
+            name_server: address(settings.name_server),
+            name_server_2: address(settings.name_server_2),
+            domain_name: settings.domain_name,
         }
-
-        self.ip_address.encode(buffer)?;
-        self.network_mask.encode(buffer)?;
-        self.gateway_address.encode(buffer)?;
-        self.name_server.encode(buffer)?;
-        self.name_server_2.encode(buffer)?;
-        self.domain_name.encode(buffer)?;
-        Ok(())
     }
+}
 
-    fn encoded_len(&self) -> usize {
-        return self.ip_address.encoded_len()
-            + self.network_mask.encoded_len()
-            + self.gateway_address.encoded_len()
-            + self.name_server.encoded_len()
-            + self.name_server_2.encoded_len()
-            + self.domain_name.encoded_len();
+#[cfg(feature = "serde")]
+impl From<InterfaceConfiguration> for InterfaceSettings {
+    fn from(config: InterfaceConfiguration) -> Self {
+        let address = |value: UDInt| Ipv4Addr::from(value.value());
+        Self {
+            ip_address: address(config.ip_address),
+            network_mask: address(config.network_mask),
+            gateway_address: address(config.gateway_address),
+            name_server: address(config.name_server),
+            name_server_2: address(config.name_server_2),
+            domain_name: config.domain_name,
+        }
     }
 }
 
@@ -185,20 +125,20 @@ pub struct TcpIpInterfaceInstance {
     configuration_control: DWord,
 
     #[attribute(id = 4, access = "get")]
-    phisical_link_object: PhysicalLink,
+    phisical_link_object: SizedEPath,
 
     #[attribute(id = 5, access = "set")]
-    interface_configuration: InterfaceConfiguration,
+    interface_configuration: std::sync::RwLock<InterfaceConfiguration>,
 
-    #[attribute(id = 6, access = "get")]
-    host_name: CipString<64>,
+    #[attribute(id = 6, access = "set")]
+    host_name: std::sync::RwLock<CipString<64>>,
 }
 
 #[cip_object_impl]
 impl TcpIpInterfaceInstance {
     pub fn new(id: u16, address: Ipv4Addr) -> Self {
         let port_segment = PortSegment::from_port_and_ip(1, address);
-        let physical_link = PhysicalLink::new(PaddedEPath::new(vec![Segment::Port(port_segment)]));
+        let physical_link = SizedEPath::new(PaddedEPath::new(vec![Segment::Port(port_segment)]));
 
         Self {
             id,
@@ -207,8 +147,18 @@ impl TcpIpInterfaceInstance {
             configuration_capability: DWord::new(0),
             configuration_control: DWord::new(0),
             phisical_link_object: physical_link,
-            interface_configuration: InterfaceConfiguration::new(address),
-            host_name: CipString::new(""),
+            interface_configuration: std::sync::RwLock::new(InterfaceConfiguration::new(address)),
+            host_name: std::sync::RwLock::new(CipString::new("")),
+        }
+    }
+
+    pub fn host_name(&self) -> String {
+        match self.host_name.read() {
+            Ok(host_name) => host_name.value().to_string(),
+            Err(_) => {
+                log::error!("Failed to get read guard for host name");
+                String::new()
+            }
         }
     }
 
@@ -217,10 +167,13 @@ impl TcpIpInterfaceInstance {
     }
 
     pub fn sin_addr(&self) -> [u8; 4] {
-        self.interface_configuration
-            .ip_address
-            .value()
-            .to_be_bytes()
+        match self.interface_configuration.read() {
+            Ok(configuration) => configuration.ip_address.value().to_be_bytes(),
+            Err(_) => {
+                log::error!("Failed to get read guard for interface configuration");
+                [0; 4]
+            }
+        }
     }
 
     pub fn sin_port(&self) -> u16 {
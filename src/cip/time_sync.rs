@@ -0,0 +1,528 @@
+use std::{
+    sync::{
+        Arc, Mutex, RwLock,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use bytes::{Buf, BufMut, Bytes, BytesMut};
+use cip_macros::{CipClass, CipInstance, cip_object_impl};
+
+use super::{
+    ClassCode,
+    common::error::CipError,
+    common::object::{CipClass, CipInstance, CipObject, CipResult},
+};
+use crate::common::binary::{BinaryError, FromBytes, ToBytes};
+
+/// IEEE 1588 EUI-64 clock identity.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ClockIdentity(pub [u8; 8]);
+
+impl ToBytes for ClockIdentity {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < self.encoded_len() {
+            return Err(BinaryError::BufferTooSmall {
+                expected: self.encoded_len(),
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        buffer.put_slice(&self.0);
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        8
+    }
+}
+
+/// IEEE 1588 port states.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PortState {
+    Initializing = 1,
+    Faulty = 2,
+    Disabled = 3,
+    Listening = 4,
+    PreMaster = 5,
+    Master = 6,
+    Passive = 7,
+    Uncalibrated = 8,
+    Slave = 9,
+}
+
+/// Attribute 8, properties of the grandmaster clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GrandmasterClockInfo {
+    pub clock_identity: ClockIdentity,
+    pub clock_class: u16,
+    pub time_accuracy: u16,
+    pub offset_scaled_log_variance: u16,
+    pub current_utc_offset: u16,
+    pub time_property_flags: u16,
+    pub time_source: u16,
+    pub priority_1: u16,
+    pub priority_2: u16,
+}
+
+impl GrandmasterClockInfo {
+    const LEN: usize = 24;
+}
+
+impl Default for GrandmasterClockInfo {
+    fn default() -> Self {
+        Self {
+            clock_identity: ClockIdentity::default(),
+            // Default clock class and unknown accuracy for a free-running clock
+            clock_class: 248,
+            time_accuracy: 0xFE,
+            offset_scaled_log_variance: 0xFFFF,
+            current_utc_offset: 0,
+            time_property_flags: 0,
+            // Internal oscillator
+            time_source: 0xA0,
+            priority_1: 128,
+            priority_2: 128,
+        }
+    }
+}
+
+impl ToBytes for GrandmasterClockInfo {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < Self::LEN {
+            return Err(BinaryError::BufferTooSmall {
+                expected: Self::LEN,
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        self.clock_identity.encode(buffer)?;
+        buffer.put_u16_le(self.clock_class);
+        buffer.put_u16_le(self.time_accuracy);
+        buffer.put_u16_le(self.offset_scaled_log_variance);
+        buffer.put_u16_le(self.current_utc_offset);
+        buffer.put_u16_le(self.time_property_flags);
+        buffer.put_u16_le(self.time_source);
+        buffer.put_u16_le(self.priority_1);
+        buffer.put_u16_le(self.priority_2);
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        Self::LEN
+    }
+}
+
+/// Attribute 9, the clock this device synchronizes to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ParentClockInfo {
+    pub clock_identity: ClockIdentity,
+    pub port_number: u16,
+    pub observed_offset_scaled_log_variance: u16,
+    pub observed_phase_change_rate: i32,
+}
+
+impl ParentClockInfo {
+    const LEN: usize = 16;
+}
+
+impl ToBytes for ParentClockInfo {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < Self::LEN {
+            return Err(BinaryError::BufferTooSmall {
+                expected: Self::LEN,
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        self.clock_identity.encode(buffer)?;
+        buffer.put_u16_le(self.port_number);
+        buffer.put_u16_le(self.observed_offset_scaled_log_variance);
+        buffer.put_i32_le(self.observed_phase_change_rate);
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        Self::LEN
+    }
+}
+
+/// Attribute 10, properties of the local clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LocalClockInfo {
+    pub clock_identity: ClockIdentity,
+    pub clock_class: u16,
+    pub time_accuracy: u16,
+    pub offset_scaled_log_variance: u16,
+    pub current_utc_offset: u16,
+    pub time_property_flags: u16,
+    pub time_source: u16,
+}
+
+impl LocalClockInfo {
+    const LEN: usize = 20;
+}
+
+impl Default for LocalClockInfo {
+    fn default() -> Self {
+        let grandmaster = GrandmasterClockInfo::default();
+        Self {
+            clock_identity: grandmaster.clock_identity,
+            clock_class: grandmaster.clock_class,
+            time_accuracy: grandmaster.time_accuracy,
+            offset_scaled_log_variance: grandmaster.offset_scaled_log_variance,
+            current_utc_offset: grandmaster.current_utc_offset,
+            time_property_flags: grandmaster.time_property_flags,
+            time_source: grandmaster.time_source,
+        }
+    }
+}
+
+impl ToBytes for LocalClockInfo {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < Self::LEN {
+            return Err(BinaryError::BufferTooSmall {
+                expected: Self::LEN,
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        self.clock_identity.encode(buffer)?;
+        buffer.put_u16_le(self.clock_class);
+        buffer.put_u16_le(self.time_accuracy);
+        buffer.put_u16_le(self.offset_scaled_log_variance);
+        buffer.put_u16_le(self.current_utc_offset);
+        buffer.put_u16_le(self.time_property_flags);
+        buffer.put_u16_le(self.time_source);
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        Self::LEN
+    }
+}
+
+/// Attribute 12, number of PTP ports followed by the state of each one.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PortStateInfo(pub Vec<(u16, PortState)>);
+
+impl ToBytes for PortStateInfo {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < self.encoded_len() {
+            return Err(BinaryError::BufferTooSmall {
+                expected: self.encoded_len(),
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        buffer.put_u16_le(self.0.len() as u16);
+        for (port_number, state) in &self.0 {
+            buffer.put_u16_le(*port_number);
+            buffer.put_u16_le(*state as u16);
+        }
+
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        2 + self.0.len() * 4
+    }
+}
+
+/// Source of the clock data reported by the Time Sync object.
+/// A PTP engine implements this trait to expose its state to the network.
+pub trait PtpClock: Send + Sync {
+    fn is_synchronized(&self) -> bool;
+
+    /// Current time in microseconds since the PTP epoch, 1970-01-01 00:00:00 TAI.
+    fn system_time_us(&self) -> u64;
+
+    fn offset_from_master_ns(&self) -> i64 {
+        0
+    }
+
+    fn max_offset_from_master_ns(&self) -> u64 {
+        0
+    }
+
+    fn mean_path_delay_ns(&self) -> i64 {
+        0
+    }
+
+    fn grandmaster(&self) -> GrandmasterClockInfo;
+
+    fn parent(&self) -> ParentClockInfo;
+
+    fn local_clock(&self) -> LocalClockInfo;
+
+    fn port_states(&self) -> PortStateInfo;
+}
+
+#[derive(Debug, Default)]
+struct SoftwareClockState {
+    offset_us: i64,
+    synchronized: bool,
+}
+
+/// Free-running clock based on the system time. The application can correct it with
+/// [`SoftwareClock::set_time_us`] and flag it as synchronized when fed by an external source.
+/// The system time is converted to the PTP timescale with the `current_utc_offset` of the
+/// local clock. The clock does not run PTP, so its port reports [`PortState::Disabled`].
+#[derive(Debug, Default)]
+pub struct SoftwareClock {
+    local_clock: LocalClockInfo,
+    state: Mutex<SoftwareClockState>,
+}
+
+impl SoftwareClock {
+    pub fn new(clock_identity: ClockIdentity) -> Self {
+        Self {
+            local_clock: LocalClockInfo {
+                clock_identity,
+                ..Default::default()
+            },
+            state: Mutex::new(SoftwareClockState::default()),
+        }
+    }
+
+    pub fn set_time_us(&self, time_us: u64) {
+        let offset_us = time_us as i64 - self.host_time_us() as i64;
+        match self.state.lock() {
+            Ok(mut state) => state.offset_us = offset_us,
+            Err(_) => log::error!("Failed to lock software clock state"),
+        }
+    }
+
+    pub fn set_synchronized(&self, synchronized: bool) {
+        match self.state.lock() {
+            Ok(mut state) => state.synchronized = synchronized,
+            Err(_) => log::error!("Failed to lock software clock state"),
+        }
+    }
+
+    /// System time on the PTP timescale, UTC plus the configured UTC offset.
+    fn host_time_us(&self) -> u64 {
+        let utc_us = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_micros() as u64)
+            .unwrap_or(0);
+        utc_us + self.local_clock.current_utc_offset as u64 * 1_000_000
+    }
+
+    fn state(&self) -> (i64, bool) {
+        match self.state.lock() {
+            Ok(state) => (state.offset_us, state.synchronized),
+            Err(_) => {
+                log::error!("Failed to lock software clock state");
+                (0, false)
+            }
+        }
+    }
+}
+
+impl PtpClock for SoftwareClock {
+    fn is_synchronized(&self) -> bool {
+        self.state().1
+    }
+
+    fn system_time_us(&self) -> u64 {
+        self.host_time_us().saturating_add_signed(self.state().0)
+    }
+
+    fn grandmaster(&self) -> GrandmasterClockInfo {
+        // Without PTP the local clock is its own grandmaster
+        GrandmasterClockInfo {
+            clock_identity: self.local_clock.clock_identity,
+            clock_class: self.local_clock.clock_class,
+            time_accuracy: self.local_clock.time_accuracy,
+            offset_scaled_log_variance: self.local_clock.offset_scaled_log_variance,
+            current_utc_offset: self.local_clock.current_utc_offset,
+            time_property_flags: self.local_clock.time_property_flags,
+            time_source: self.local_clock.time_source,
+            ..Default::default()
+        }
+    }
+
+    fn parent(&self) -> ParentClockInfo {
+        ParentClockInfo {
+            clock_identity: self.local_clock.clock_identity,
+            ..Default::default()
+        }
+    }
+
+    fn local_clock(&self) -> LocalClockInfo {
+        self.local_clock
+    }
+
+    fn port_states(&self) -> PortStateInfo {
+        PortStateInfo(vec![(1, PortState::Disabled)])
+    }
+}
+
+#[derive(CipClass)]
+#[cip(id = ClassCode::TimeSync, name = "Time Sync", singleton = true, custom_services = true)]
+pub struct TimeSyncClass {
+    pub instance: RwLock<Arc<TimeSyncInstance>>,
+}
+
+#[cip_object_impl]
+impl TimeSyncClass {
+    pub fn new(instance: Arc<TimeSyncInstance>) -> Arc<Self> {
+        Arc::new(Self {
+            instance: RwLock::new(instance),
+        })
+    }
+}
+
+#[derive(CipInstance)]
+#[cip(custom_services = true)]
+pub struct TimeSyncInstance {
+    id: u16,
+    class_id: ClassCode,
+    ptp_enable: AtomicBool,
+    clock: Arc<dyn PtpClock>,
+}
+
+#[cip_object_impl]
+impl TimeSyncInstance {
+    /// PTP starts enabled when the clock has a port that is not disabled.
+    pub fn new(clock: Arc<dyn PtpClock>) -> Self {
+        let ptp_enable = clock
+            .port_states()
+            .0
+            .iter()
+            .any(|(_, state)| *state != PortState::Disabled);
+
+        Self {
+            id: 1,
+            class_id: ClassCode::TimeSync,
+            ptp_enable: AtomicBool::new(ptp_enable),
+            clock,
+        }
+    }
+
+    pub fn ptp_enabled(&self) -> bool {
+        self.ptp_enable.load(Ordering::Relaxed)
+    }
+
+    pub fn clock(&self) -> Arc<dyn PtpClock> {
+        self.clock.clone()
+    }
+
+    #[service(0x0E)]
+    pub fn get_attribute_single(&self, req: &mut Bytes, resp: &mut BytesMut) -> CipResult {
+        let attribute_id = u16::decode(req)?;
+
+        match attribute_id {
+            1 => (self.ptp_enabled() as u8).encode(resp)?,
+            2 => (self.clock.is_synchronized() as u8).encode(resp)?,
+            3 => resp.put_u64_le(self.clock.system_time_us()),
+            4 => resp.put_u64_le(self.clock.system_time_us().saturating_mul(1_000)),
+            5 => resp.put_i64_le(self.clock.offset_from_master_ns()),
+            6 => resp.put_u64_le(self.clock.max_offset_from_master_ns()),
+            7 => resp.put_i64_le(self.clock.mean_path_delay_ns()),
+            8 => self.clock.grandmaster().encode(resp)?,
+            9 => self.clock.parent().encode(resp)?,
+            10 => self.clock.local_clock().encode(resp)?,
+            11 => (self.clock.port_states().0.len() as u16).encode(resp)?,
+            12 => self.clock.port_states().encode(resp)?,
+            _ => return Err(CipError::AttributeNotSupported),
+        }
+
+        Ok(())
+    }
+
+    #[service(0x10)]
+    pub fn set_attribute_single(&self, req: &mut Bytes, _resp: &mut BytesMut) -> CipResult {
+        let attribute_id = u16::decode(req)?;
+
+        match attribute_id {
+            1 => {
+                let enable = match u8::decode(req)? {
+                    0 => false,
+                    1 => true,
+                    _ => return Err(CipError::InvalidAttributeValue),
+                };
+                self.ptp_enable.store(enable, Ordering::Relaxed);
+                Ok(())
+            }
+            2..=12 => Err(CipError::AttributeNotSetable),
+            _ => Err(CipError::AttributeNotSupported),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_attribute(instance: &mut TimeSyncInstance, attribute_id: u16) -> BytesMut {
+        let mut req = Bytes::copy_from_slice(&attribute_id.to_le_bytes());
+        let mut resp = BytesMut::new();
+        instance
+            .execute_service(0x0E, &mut req, &mut resp)
+            .expect("Failed to get attribute");
+        resp
+    }
+
+    #[test]
+    fn software_clock_reports_fed_time_and_sync_state() {
+        let clock = Arc::new(SoftwareClock::new(ClockIdentity([1, 2, 3, 4, 5, 6, 7, 8])));
+        clock.set_time_us(1_000_000_000_000);
+        clock.set_synchronized(true);
+        let mut instance = TimeSyncInstance::new(clock);
+
+        assert_eq!(get_attribute(&mut instance, 2).as_ref(), &[0x01]);
+
+        let mut time = get_attribute(&mut instance, 3).freeze();
+        let time_us = time.get_u64_le();
+        assert!((1_000_000_000_000..1_000_001_000_000).contains(&time_us));
+
+        let local_clock = get_attribute(&mut instance, 10);
+        assert_eq!(&local_clock[..8], &[1, 2, 3, 4, 5, 6, 7, 8]);
+    }
+
+    #[test]
+    fn port_state_info_encodes_ports() {
+        let mut instance = TimeSyncInstance::new(Arc::new(SoftwareClock::default()));
+
+        assert_eq!(
+            get_attribute(&mut instance, 12).as_ref(),
+            &[
+                0x01, 0x00, // Number of ports
+                0x01, 0x00, // Port 1
+                0x03, 0x00, // Disabled
+            ]
+        );
+    }
+
+    #[test]
+    fn software_clock_starts_with_ptp_disabled() {
+        let mut instance = TimeSyncInstance::new(Arc::new(SoftwareClock::default()));
+
+        assert!(!instance.ptp_enabled());
+        assert_eq!(get_attribute(&mut instance, 1).as_ref(), &[0x00]);
+    }
+
+    #[test]
+    fn set_ptp_enable_validates_value() {
+        let mut instance = TimeSyncInstance::new(Arc::new(SoftwareClock::default()));
+
+        let mut req = Bytes::from_static(&[0x01, 0x00, 0x01]);
+        instance
+            .execute_service(0x10, &mut req, &mut BytesMut::new())
+            .expect("Failed to enable PTP");
+        assert!(instance.ptp_enabled());
+
+        let mut req = Bytes::from_static(&[0x01, 0x00, 0x00]);
+        instance
+            .execute_service(0x10, &mut req, &mut BytesMut::new())
+            .expect("Failed to disable PTP");
+        assert!(!instance.ptp_enabled());
+
+        let mut req = Bytes::from_static(&[0x01, 0x00, 0x02]);
+        let result = instance.execute_service(0x10, &mut req, &mut BytesMut::new());
+        assert!(matches!(result, Err(CipError::InvalidAttributeValue)));
+    }
+}
@@ -1 +1,4 @@
 pub mod binary;
+pub mod storage;
+#[cfg(feature = "serde")]
+pub(crate) mod serde_text;
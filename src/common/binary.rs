@@ -26,3 +26,141 @@ pub trait ToBytes {
 
     fn encoded_len(&self) -> usize;
 }
+
+impl<E: FromBytes, const N: usize> FromBytes for [E; N] {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        let elements = (0..N)
+            .map(|_| E::decode(buffer))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        elements
+            .try_into()
+            .map_err(|elements: Vec<E>| BinaryError::InvalidData {
+                message: "Array length mismatch".to_string(),
+                expected: N.to_string(),
+                actual: elements.len().to_string(),
+            })
+    }
+}
+
+impl<E: ToBytes, const N: usize> ToBytes for [E; N] {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < self.encoded_len() {
+            return Err(BinaryError::BufferTooSmall {
+                expected: self.encoded_len(),
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        for element in self {
+            element.encode(buffer)?;
+        }
+
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        self.iter().map(ToBytes::encoded_len).sum()
+    }
+}
+
+/// Lists are preceded by their element count as a UINT.
+impl<E: FromBytes> FromBytes for Vec<E> {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        if buffer.remaining() < 2 {
+            return Err(BinaryError::Truncated {
+                expected: 2,
+                actual: buffer.remaining(),
+            });
+        }
+
+        let count = buffer.get_u16_le() as usize;
+        (0..count).map(|_| E::decode(buffer)).collect()
+    }
+}
+
+impl<E: ToBytes> ToBytes for Vec<E> {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if self.len() > u16::MAX as usize {
+            return Err(BinaryError::InvalidData {
+                message: "Too many elements for a UINT count".to_string(),
+                expected: format!("<= {}", u16::MAX),
+                actual: self.len().to_string(),
+            });
+        }
+
+        if buffer.remaining_mut() < self.encoded_len() {
+            return Err(BinaryError::BufferTooSmall {
+                expected: self.encoded_len(),
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        buffer.put_u16_le(self.len() as u16);
+        for element in self {
+            element.encode(buffer)?;
+        }
+
+        Ok(())
+    }
+
+    fn encoded_len(&self) -> usize {
+        2 + self.iter().map(ToBytes::encoded_len).sum::<usize>()
+    }
+}
+
+impl<E: FromBytes> FromBytes for Box<E> {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        E::decode(buffer).map(Box::new)
+    }
+}
+
+impl<E: ToBytes> ToBytes for Box<E> {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        E::encode(self, buffer)
+    }
+
+    fn encoded_len(&self) -> usize {
+        E::encoded_len(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bytes::{Bytes, BytesMut};
+
+    use super::*;
+
+    #[test]
+    fn array_round_trip_success() {
+        let values: [u16; 3] = [1, 2, 0x0300];
+
+        let mut buffer = BytesMut::new();
+        values.encode(&mut buffer).expect("Failed to encode");
+        assert_eq!(buffer.as_ref(), &[0x01, 0x00, 0x02, 0x00, 0x00, 0x03]);
+
+        let decoded = <[u16; 3]>::decode(&mut buffer.freeze()).expect("Failed to decode");
+        assert_eq!(decoded, values);
+    }
+
+    #[test]
+    fn vec_round_trip_with_count_prefix_success() {
+        let values: Vec<u8> = vec![0xAA, 0xBB];
+
+        let mut buffer = BytesMut::new();
+        values.encode(&mut buffer).expect("Failed to encode");
+        assert_eq!(buffer.as_ref(), &[0x02, 0x00, 0xAA, 0xBB]);
+
+        let decoded = Vec::<u8>::decode(&mut buffer.freeze()).expect("Failed to decode");
+        assert_eq!(decoded, values);
+    }
+
+    #[test]
+    fn vec_decode_truncated_element_returns_error() {
+        let mut buffer = Bytes::from_static(&[0x02, 0x00, 0x01, 0x00]);
+
+        let result = Vec::<u16>::decode(&mut buffer);
+
+        assert!(matches!(result, Err(BinaryError::Truncated { .. })));
+    }
+}
@@ -0,0 +1,115 @@
+//! Helpers for the human-readable serde forms of the data types.
+
+use serde::de::Error;
+
+/// Implements `Serialize` through `Display` and `Deserialize` through `FromStr`.
+macro_rules! impl_serde_text {
+    ($($name:ty),* $(,)?) => {
+        $(
+            impl serde::Serialize for $name {
+                fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    serializer.collect_str(self)
+                }
+            }
+
+            impl<'de> serde::Deserialize<'de> for $name {
+                fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let text = <String as serde::Deserialize>::deserialize(deserializer)?;
+                    text.parse().map_err(serde::de::Error::custom)
+                }
+            }
+        )*
+    };
+}
+
+/// Implements `Serialize` and `Deserialize` as the encoded bytes written in hex, ex: "20 04 24 01".
+macro_rules! impl_serde_hex {
+    ($($name:ty),* $(,)?) => {
+        $(
+            impl serde::Serialize for $name {
+                fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    let mut buffer = bytes::BytesMut::with_capacity(self.encoded_len());
+                    self.encode(&mut buffer).map_err(serde::ser::Error::custom)?;
+                    serializer.serialize_str(&crate::common::serde_text::to_hex(&buffer))
+                }
+            }
+
+            impl<'de> serde::Deserialize<'de> for $name {
+                fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let text = <String as serde::Deserialize>::deserialize(deserializer)?;
+                    let raw = crate::common::serde_text::from_hex(&text)
+                        .ok_or_else(|| serde::de::Error::custom(format!("invalid hex bytes \"{}\"", text)))?;
+                    let mut buffer = bytes::Bytes::from(raw);
+                    let value = Self::decode(&mut buffer).map_err(serde::de::Error::custom)?;
+                    if bytes::Buf::has_remaining(&buffer) {
+                        return Err(serde::de::Error::custom("trailing bytes"));
+                    }
+                    Ok(value)
+                }
+            }
+        )*
+    };
+}
+
+/// Implements `Serialize` and `Deserialize` as plain text for string types with `new(&str)`
+/// and `value()`. Text the type can't hold is rejected instead of truncated.
+macro_rules! impl_serde_string {
+    ([$($generics:tt)*] $name:ty) => {
+        impl<$($generics)*> serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_str(&self.value())
+            }
+        }
+
+        impl<'de, $($generics)*> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                let text = <String as serde::Deserialize>::deserialize(deserializer)?;
+                let value = Self::new(&text);
+                crate::common::serde_text::ensure_stored(&text, &value.value())?;
+                Ok(value)
+            }
+        }
+    };
+}
+
+pub(crate) use impl_serde_hex;
+pub(crate) use impl_serde_string;
+pub(crate) use impl_serde_text;
+
+/// Fails when a string type did not keep all of `text`, because it is too long or has
+/// characters the type cannot hold.
+pub(crate) fn ensure_stored<E: Error>(text: &str, stored: &str) -> Result<(), E> {
+    if text != stored {
+        return Err(E::custom(format!(
+            "\"{}\" does not fit the string type",
+            text
+        )));
+    }
+
+    Ok(())
+}
+
+pub(crate) fn to_hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|byte| format!("{:02X}", byte))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Accepts pairs of hex digits, optionally separated by whitespace.
+pub(crate) fn from_hex(text: &str) -> Option<Vec<u8>> {
+    let digits: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+    if !digits.len().is_multiple_of(2) {
+        return None;
+    }
+
+    digits
+        .chunks(2)
+        .map(|pair| {
+            let high = pair[0].to_digit(16)?;
+            let low = pair[1].to_digit(16)?;
+            Some((high * 16 + low) as u8)
+        })
+        .collect()
+}
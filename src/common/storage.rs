@@ -0,0 +1,95 @@
+use std::{collections::HashMap, fs, io, path::PathBuf, sync::Mutex};
+
+/// Non-volatile storage used by objects that must keep their configuration across restarts.
+/// Each object stores its encoded attributes under its own key.
+pub trait NonVolatileStorage: Send + Sync {
+    fn load(&self, key: &str) -> io::Result<Option<Vec<u8>>>;
+    fn store(&self, key: &str, data: &[u8]) -> io::Result<()>;
+}
+
+/// Stores each key as a `<key>.bin` file inside a directory.
+#[derive(Debug, Clone)]
+pub struct FileStorage {
+    directory: PathBuf,
+}
+
+impl FileStorage {
+    pub fn new(directory: impl Into<PathBuf>) -> Self {
+        Self {
+            directory: directory.into(),
+        }
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.directory.join(format!("{}.bin", key))
+    }
+}
+
+impl NonVolatileStorage for FileStorage {
+    fn load(&self, key: &str) -> io::Result<Option<Vec<u8>>> {
+        match fs::read(self.path(key)) {
+            Ok(data) => Ok(Some(data)),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn store(&self, key: &str, data: &[u8]) -> io::Result<()> {
+        fs::create_dir_all(&self.directory)?;
+        fs::write(self.path(key), data)
+    }
+}
+
+/// Keeps stored values in memory only, useful for tests and devices without storage.
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    entries: Mutex<HashMap<String, Vec<u8>>>,
+}
+
+impl MemoryStorage {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl NonVolatileStorage for MemoryStorage {
+    fn load(&self, key: &str) -> io::Result<Option<Vec<u8>>> {
+        let entries = self
+            .entries
+            .lock()
+            .map_err(|_| io::Error::other("Failed to lock memory storage"))?;
+        Ok(entries.get(key).cloned())
+    }
+
+    fn store(&self, key: &str, data: &[u8]) -> io::Result<()> {
+        let mut entries = self
+            .entries
+            .lock()
+            .map_err(|_| io::Error::other("Failed to lock memory storage"))?;
+        entries.insert(key.to_string(), data.to_vec());
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_storage_store_and_load_success() {
+        let directory = std::env::temp_dir().join(format!("eip-storage-{}", std::process::id()));
+        let storage = FileStorage::new(&directory);
+
+        assert_eq!(storage.load("qos").expect("Failed to load"), None);
+
+        storage
+            .store("qos", &[0x01, 0x02])
+            .expect("Failed to store");
+        assert_eq!(
+            storage.load("qos").expect("Failed to load"),
+            Some(vec![0x01, 0x02])
+        );
+
+        fs::remove_dir_all(directory).expect("Failed to clean up");
+    }
+}
@@ -1,19 +1,51 @@
-use std::{io, net::Ipv4Addr, sync::Arc};
-use tokio::sync::{Mutex, broadcast::Sender};
+use std::{
+    io,
+    net::Ipv4Addr,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+use tokio::sync::{
+    Mutex,
+    broadcast::{self, Sender},
+};
 
 use crate::{
     cip::{
-        cip_identity::{IdentityClass, IdentityInfo},
+        cip_identity::{IdentityClass, IdentityInfo, Revision},
         common::object::CipClass,
+        connection_configuration::{ConnectionConfigurationClass, NoOriginator, OriginatorEngine},
+        connection_manager::{
+            BackplaneRouter, ConnectionManagerClass, ConnectionManagerInstance, NoBackplane,
+        },
+        dlr::{DlrClass, DlrInstance, DlrProvider, LinearOnlyDlr},
+        events::{AttributeChanged, AttributeNotifier},
+        file::{EdsFile, FileClass, FileInstance},
+        lldp::{
+            LldpDataTableClass, LldpManagementClass, LldpManagementInstance, LldpNeighborSource,
+            NoLldpNeighbors,
+        },
+        message_router::MessageRouter,
+        parameter::{
+            BlobParameter, Parameter, ParameterAttributes, ParameterClass, ParameterValue,
+        },
+        port::PortClass,
+        qos::{QosClass, QosInstance},
         registry::Registry,
         tcp_ip_interface::{EIP_RESERVED_PORT, TcpIpInterfaceClass, TcpIpInterfaceInstance},
+        time_sync::{PtpClock, SoftwareClock, TimeSyncClass, TimeSyncInstance},
     },
+    common::storage::NonVolatileStorage,
     encap::{handler::EncapsulationHandler, session_manager::SessionManager},
     transport::{tcp::TcpTransport, udp::UdpTransport},
 };
 
+/// Period at which connections are checked for inactivity timeouts.
+const CONNECTION_WATCHDOG_PERIOD: Duration = Duration::from_millis(10);
+
 pub struct EipStack {
     registry: Arc<Registry>,
+    connection_manager: Arc<ConnectionManagerInstance>,
+    message_router: MessageRouter,
     udp_transport: Arc<Mutex<UdpTransport>>,
     tcp_transport: Arc<Mutex<TcpTransport>>,
     shutdown_tx: Arc<Sender<()>>,
@@ -33,12 +65,26 @@ impl EipStack {
             _ = tcp_transport.lock().await.listen().await;
         });
 
+        let connection_manager = self.connection_manager.clone();
+        let mut shutdown_rx = self.shutdown_tx.subscribe();
+        let watchdog_handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(CONNECTION_WATCHDOG_PERIOD);
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {
+                        connection_manager.expire_connections(Instant::now());
+                    }
+                    _ = shutdown_rx.recv() => break,
+                }
+            }
+        });
+
         let shutdown_tx = self.shutdown_tx.clone();
         let shutdown_handle = tokio::spawn(async move {
             _ = EipStack::handle_graceful_shutdown(shutdown_tx).await;
         });
 
-        tokio::try_join!(udp_handle, tcp_handle, shutdown_handle)?;
+        tokio::try_join!(udp_handle, tcp_handle, watchdog_handle, shutdown_handle)?;
         Ok(())
     }
 
@@ -56,6 +102,15 @@ impl EipStack {
         self.registry.clone()
     }
 
+    pub fn message_router(&self) -> MessageRouter {
+        self.message_router
+    }
+
+    /// Subscribes to the attribute changes published by the registry.
+    pub fn attribute_changes(&self) -> broadcast::Receiver<AttributeChanged> {
+        self.registry.notifier().subscribe()
+    }
+
     async fn handle_graceful_shutdown(shutdown_tx: Arc<Sender<()>>) -> io::Result<()> {
         let mut shutdown_rx = shutdown_tx.subscribe();
         tokio::select! {
@@ -79,6 +134,7 @@ impl EipStack {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct EipConfig {
     pub identity: IdentityInfo,
     pub local_address: Ipv4Addr,
@@ -86,21 +142,59 @@ pub struct EipConfig {
     pub udp_broadcast_port: u16,
 }
 
+/// Registers a built-in or application class, failing the build on duplicate class ids.
+fn register_class(registry: &mut Registry, class: Arc<dyn CipClass>) -> io::Result<()> {
+    let name = class.name();
+    registry.register(class).map_err(|_| {
+        io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("CIP class {} is already registered", name),
+        )
+    })
+}
+
 pub struct EipStackBuilder {
     config: EipConfig,
     registry: Registry,
+    parameters: Vec<Box<dyn ParameterAttributes>>,
+    storage: Option<Arc<dyn NonVolatileStorage>>,
+    eds: Option<EdsFile>,
+    files: Vec<FileInstance>,
+    ptp_clock: Option<Arc<dyn PtpClock>>,
+    dlr_provider: Option<Arc<dyn DlrProvider>>,
+    lldp_source: Option<Arc<dyn LldpNeighborSource>>,
+    originator: Option<Arc<dyn OriginatorEngine>>,
+    backplane_router: Option<Arc<dyn BackplaneRouter>>,
+    classes: Vec<Arc<dyn CipClass>>,
+    service_deadline: Option<Duration>,
 }
 
 impl EipStackBuilder {
     pub fn new(identity: IdentityInfo) -> Self {
+        Self::from_config(EipConfig {
+            identity,
+            local_address: Ipv4Addr::UNSPECIFIED,
+            tcp_port: EIP_RESERVED_PORT,
+            udp_broadcast_port: EIP_RESERVED_PORT,
+        })
+    }
+
+    /// Starts from a complete configuration, ex: one loaded from a file.
+    pub fn from_config(config: EipConfig) -> Self {
         Self {
-            config: EipConfig {
-                identity,
-                local_address: Ipv4Addr::UNSPECIFIED,
-                tcp_port: EIP_RESERVED_PORT,
-                udp_broadcast_port: EIP_RESERVED_PORT,
-            },
+            config,
             registry: Registry::new(),
+            parameters: Vec::new(),
+            storage: None,
+            eds: None,
+            files: Vec::new(),
+            ptp_clock: None,
+            dlr_provider: None,
+            lldp_source: None,
+            originator: None,
+            backplane_router: None,
+            classes: Vec::new(),
+            service_deadline: None,
         }
     }
 
@@ -119,11 +213,94 @@ impl EipStackBuilder {
         self
     }
 
+    /// Sets the storage used by objects that persist their configuration, such as QoS.
+    pub fn with_storage(mut self, storage: Arc<dyn NonVolatileStorage>) -> Self {
+        self.storage = Some(storage);
+        self
+    }
+
+    /// Serves the EDS file through File object instance 0xC8.
+    pub fn with_eds(mut self, eds: EdsFile) -> Self {
+        self.eds = Some(eds);
+        self
+    }
+
+    /// Adds a File object instance, such as a download target for firmware-like blobs.
+    pub fn with_file(mut self, file: FileInstance) -> Self {
+        self.files.push(file);
+        self
+    }
+
+    /// Sets the clock reported by the Time Sync object. Defaults to a [`SoftwareClock`].
+    pub fn with_ptp_clock(mut self, clock: Arc<dyn PtpClock>) -> Self {
+        self.ptp_clock = Some(clock);
+        self
+    }
+
+    /// Sets the ring state reported by the DLR object. Defaults to [`LinearOnlyDlr`].
+    pub fn with_dlr_provider(mut self, provider: Arc<dyn DlrProvider>) -> Self {
+        self.dlr_provider = Some(provider);
+        self
+    }
+
+    /// Sets the neighbor source of the LLDP objects. Defaults to [`NoLldpNeighbors`].
+    pub fn with_lldp_source(mut self, source: Arc<dyn LldpNeighborSource>) -> Self {
+        self.lldp_source = Some(source);
+        self
+    }
+
+    /// Sets the engine opening the connections configured through the Connection
+    /// Configuration object. Defaults to [`NoOriginator`].
+    pub fn with_originator_engine(mut self, engine: Arc<dyn OriginatorEngine>) -> Self {
+        self.originator = Some(engine);
+        self
+    }
+
+    /// Sets the router forwarding Unconnected_Send requests to other ports or slots.
+    /// Defaults to [`NoBackplane`].
+    pub fn with_backplane_router(mut self, router: Arc<dyn BackplaneRouter>) -> Self {
+        self.backplane_router = Some(router);
+        self
+    }
+
+    /// Notifier of the registry, for shared instances created before the stack is built,
+    /// see [`SharedInstance::with_notifier`](crate::cip::common::shared::SharedInstance::with_notifier).
+    pub fn attribute_notifier(&self) -> AttributeNotifier {
+        self.registry.notifier()
+    }
+
+    /// Registers an application class, ex: a [`DynamicClass`](crate::cip::dynamic::DynamicClass).
+    /// [`build`](Self::build) fails if the class id is already used by a built-in or another
+    /// application class.
+    pub fn with_class(mut self, class: Arc<dyn CipClass>) -> Self {
+        self.classes.push(class);
+        self
+    }
+
+    /// Sets how long the message router awaits async services before replying with an
+    /// error. Defaults to [`DEFAULT_SERVICE_DEADLINE`](crate::cip::message_router::DEFAULT_SERVICE_DEADLINE).
+    pub fn with_service_deadline(mut self, deadline: Duration) -> Self {
+        self.service_deadline = Some(deadline);
+        self
+    }
+
+    /// Adds a Parameter object instance. Instances are numbered from 1 in the order they are added.
+    pub fn with_parameter<T: ParameterValue>(mut self, parameter: Parameter<T>) -> Self {
+        self.parameters.push(Box::new(parameter));
+        self
+    }
+
+    /// Adds a Parameter object instance holding a byte string, numbered like [`Self::with_parameter`].
+    pub fn with_blob_parameter(mut self, parameter: BlobParameter) -> Self {
+        self.parameters.push(Box::new(parameter));
+        self
+    }
+
     pub async fn build(mut self) -> io::Result<EipStack> {
         log::info!("Building EIP Stack");
         log::debug!("Building EIP Stack with configuration: {:?}", self.config);
         let identity_class = IdentityClass::with_default_instance(&self.config.identity);
-        self.registry.register(identity_class);
+        register_class(&mut self.registry, identity_class)?;
         log::info!("Registering Identity Class");
 
         let tcp_ip_if_class = Arc::new(TcpIpInterfaceClass::new());
@@ -135,27 +312,113 @@ impl EipStackBuilder {
             .add_instance(tcp_ip_if_instance)
             .map_err(|_| io::Error::new(io::ErrorKind::Other, "CIP Registration Error"))?;
 
-        self.registry.register(tcp_ip_if_class);
+        register_class(&mut self.registry, tcp_ip_if_class)?;
+
+        log::info!("Registering Port Class");
+        register_class(
+            &mut self.registry,
+            PortClass::with_ethernet_ports(&[self.config.local_address]),
+        )?;
+
+        log::info!("Registering Connection Manager Class");
+        let backplane_router = self
+            .backplane_router
+            .unwrap_or_else(|| Arc::new(NoBackplane));
+        let connection_manager = Arc::new(ConnectionManagerInstance::with_router(backplane_router));
+        register_class(
+            &mut self.registry,
+            ConnectionManagerClass::new(connection_manager.clone()),
+        )?;
+
+        log::info!("Registering Connection Configuration Class");
+        let originator = self.originator.unwrap_or_else(|| Arc::new(NoOriginator));
+        register_class(
+            &mut self.registry,
+            ConnectionConfigurationClass::new(originator, self.storage.clone()),
+        )?;
+
+        log::info!("Registering Parameter Class");
+        register_class(
+            &mut self.registry,
+            ParameterClass::with_parameters(self.parameters),
+        )?;
+
+        log::info!("Registering File Class");
+        if let Some(eds) = self.eds {
+            let revision = Revision {
+                major: self.config.identity.revision_major,
+                minor: self.config.identity.revision_minor,
+            };
+            self.files.push(FileInstance::eds(eds, revision));
+        }
+        register_class(&mut self.registry, FileClass::with_instances(self.files))?;
+
+        log::info!("Registering Time Sync Class");
+        let ptp_clock = self
+            .ptp_clock
+            .unwrap_or_else(|| Arc::new(SoftwareClock::default()));
+        let time_sync_instance = Arc::new(TimeSyncInstance::new(ptp_clock));
+        register_class(&mut self.registry, TimeSyncClass::new(time_sync_instance))?;
+
+        log::info!("Registering DLR Class");
+        let dlr_provider = self.dlr_provider.unwrap_or_else(|| Arc::new(LinearOnlyDlr));
+        register_class(
+            &mut self.registry,
+            DlrClass::new(Arc::new(DlrInstance::new(dlr_provider))),
+        )?;
+
+        log::info!("Registering LLDP Classes");
+        let lldp_source = self
+            .lldp_source
+            .unwrap_or_else(|| Arc::new(NoLldpNeighbors));
+        let lldp_management_instance =
+            Arc::new(LldpManagementInstance::new(1, lldp_source.clone()));
+        register_class(
+            &mut self.registry,
+            LldpManagementClass::new(lldp_management_instance),
+        )?;
+        register_class(&mut self.registry, LldpDataTableClass::new(lldp_source))?;
+
+        log::info!("Registering QoS Class");
+        let qos_instance = Arc::new(QosInstance::new(self.storage.clone()));
+        register_class(&mut self.registry, QosClass::new(qos_instance.clone()))?;
+
+        for class in self.classes {
+            log::info!("Registering {} Class", class.name());
+            register_class(&mut self.registry, class)?;
+        }
 
         let registry = Arc::new(self.registry);
         let shutdown_tx = Arc::new(Sender::new(1));
-        let handler = Arc::new(EncapsulationHandler::new(
+        let message_router = self
+            .service_deadline
+            .map_or_else(MessageRouter::default, MessageRouter::new);
+        let handler = Arc::new(EncapsulationHandler::with_message_router(
             registry.clone(),
             Arc::new(SessionManager::new()),
+            message_router,
         ));
 
         let udp_transport = UdpTransport::new(
             handler.clone(),
             self.config.udp_broadcast_port,
             shutdown_tx.clone(),
+            qos_instance.clone(),
         )
         .await?;
 
-        let tcp_transport =
-            TcpTransport::new(handler, self.config.tcp_port, shutdown_tx.clone()).await?;
+        let tcp_transport = TcpTransport::new(
+            handler,
+            self.config.tcp_port,
+            shutdown_tx.clone(),
+            qos_instance,
+        )
+        .await?;
 
         Ok(EipStack {
             registry,
+            connection_manager,
+            message_router,
             udp_transport: Arc::new(Mutex::new(udp_transport)),
             tcp_transport: Arc::new(Mutex::new(tcp_transport)),
             shutdown_tx,
@@ -1,5 +1,7 @@
 pub mod list_identity;
 pub mod register_session;
+pub mod send_rr_data;
+pub mod send_unit_data;
 pub mod unregister_session;
 
 #[repr(u16)]
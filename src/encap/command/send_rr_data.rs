@@ -0,0 +1,226 @@
+use bytes::{Buf, BufMut, BytesMut};
+use std::sync::Arc;
+
+use crate::{
+    cip::{
+        common::fragment::MAX_UNCONNECTED_REPLY_SIZE, connection_manager::MessageRequest,
+        message_router::MessageRouter, registry::Registry,
+    },
+    common::binary::{BinaryError, FromBytes, ToBytes},
+    encap::{
+        Encapsulation, EncapsulationHeader,
+        cpf::{Cpf, cpf_item::CpfItem},
+        error::{EncapsulationError, HandlerError, InternalError},
+        handler::{ConnectionContext, HandlerAction},
+        header::EncapsulationStatus,
+        payload::EncapsulationPayload,
+    },
+};
+
+/// Payload of SendRRData and SendUnitData: interface handle, timeout and the CPF packet.
+#[derive(Debug, PartialEq)]
+pub struct SendData {
+    pub interface_handle: u32,
+    pub timeout: u16,
+    pub cpf: Cpf,
+}
+
+impl SendData {
+    const HEADER_LEN: usize = 6;
+}
+
+impl FromBytes for SendData {
+    fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        if buffer.remaining() < SendData::HEADER_LEN {
+            return Err(BinaryError::Truncated {
+                expected: SendData::HEADER_LEN,
+                actual: buffer.remaining(),
+            });
+        }
+
+        let interface_handle = buffer.get_u32_le();
+        let timeout = buffer.get_u16_le();
+        let cpf = Cpf::decode(buffer)?;
+        Ok(Self {
+            interface_handle,
+            timeout,
+            cpf,
+        })
+    }
+}
+
+impl ToBytes for SendData {
+    fn encode<T: BufMut>(&self, buffer: &mut T) -> Result<(), BinaryError> {
+        if buffer.remaining_mut() < self.encoded_len() {
+            return Err(BinaryError::BufferTooSmall {
+                expected: self.encoded_len(),
+                actual: buffer.remaining_mut(),
+            });
+        }
+
+        buffer.put_u32_le(self.interface_handle);
+        buffer.put_u16_le(self.timeout);
+        self.cpf.encode(buffer)
+    }
+
+    fn encoded_len(&self) -> usize {
+        SendData::HEADER_LEN + self.cpf.encoded_len()
+    }
+}
+
+/// Executes unconnected explicit messages through the message router.
+pub struct SendRRDataHandler {
+    registry: Arc<Registry>,
+    message_router: MessageRouter,
+}
+
+impl SendRRDataHandler {
+    pub fn new(registry: Arc<Registry>, message_router: MessageRouter) -> Self {
+        Self {
+            registry,
+            message_router,
+        }
+    }
+
+    /// The request must come from the registered session and carry a null address item
+    /// followed by an unconnected data item. Attribute changes are reported with the peer
+    /// of the session as their source. Replies with more data than an unconnected reply
+    /// holds are answered with Reply Data Too Large, larger values are read in fragments.
+    pub async fn handle(
+        &self,
+        req_header: &EncapsulationHeader,
+        req_payload: &SendData,
+        context: &ConnectionContext,
+    ) -> Result<HandlerAction, HandlerError> {
+        if context.session_handle != Some(req_header.session_handle) {
+            return Err(EncapsulationError::InvalidSessionHandle(req_header.session_handle).into());
+        }
+
+        let [CpfItem::NullAddress, CpfItem::UnconnectedData(data)] =
+            req_payload.cpf.items.as_slice()
+        else {
+            log::warn!("Invalid SendRRData items: {:?}", req_payload.cpf.items);
+            return Err(EncapsulationError::IncorrectData.into());
+        };
+
+        let request = MessageRequest::decode(&mut data.clone())
+            .map_err(|error| HandlerError::from(EncapsulationError::from(error)))?;
+        let response = self
+            .message_router
+            .dispatch(&self.registry, &request, Some(context.peer_addr))
+            .await
+            .limit_data(MAX_UNCONNECTED_REPLY_SIZE);
+
+        let mut reply_data = BytesMut::with_capacity(response.encoded_len());
+        response
+            .encode(&mut reply_data)
+            .map_err(|error| InternalError::Other(format!("{:?}", error)))?;
+
+        let mut cpf = Cpf::new();
+        cpf.add_item(CpfItem::NullAddress);
+        cpf.add_item(CpfItem::UnconnectedData(reply_data.freeze()));
+        let reply_payload = EncapsulationPayload::SendData(SendData {
+            interface_handle: 0,
+            timeout: 0,
+            cpf,
+        });
+        let reply_header = EncapsulationHeader {
+            status: EncapsulationStatus::Success,
+            length: reply_payload.encoded_len() as u16,
+            ..*req_header
+        };
+
+        Encapsulation::new(reply_header, reply_payload)
+            .map(HandlerAction::Reply)
+            .map_err(|error| InternalError::Other(error.to_string()).into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        cip::{
+            common::error::CipError,
+            data_types::epath::EPathBuilder,
+            message_router::MessageResponse,
+            parameter::{BlobParameter, ParameterClass},
+        },
+        encap::{command::EncapsulationCommand, handler::TransportType},
+    };
+    use bytes::Bytes;
+    use std::net::{Ipv4Addr, SocketAddr, SocketAddrV4};
+
+    async fn get_table(handler: &SendRRDataHandler, service: u8, data: &[u8]) -> MessageResponse {
+        let mut context = ConnectionContext::new(
+            SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 44818)),
+            TransportType::TCP,
+        );
+        context.session_handle = Some(1);
+        let request = MessageRequest {
+            service,
+            path: EPathBuilder::new()
+                .class(0x0F)
+                .instance(1)
+                .attribute(1)
+                .build(),
+            data: Bytes::copy_from_slice(data),
+        };
+        let mut request_data = BytesMut::new();
+        request
+            .encode(&mut request_data)
+            .expect("Failed to encode request");
+        let mut cpf = Cpf::new();
+        cpf.add_item(CpfItem::NullAddress);
+        cpf.add_item(CpfItem::UnconnectedData(request_data.freeze()));
+        let header = EncapsulationHeader {
+            command: EncapsulationCommand::SendRRData,
+            length: 0,
+            session_handle: 1,
+            status: EncapsulationStatus::Success,
+            context: [0; 8],
+            options: 0,
+        };
+        let payload = SendData {
+            interface_handle: 0,
+            timeout: 0,
+            cpf,
+        };
+
+        let Ok(HandlerAction::Reply(reply)) = handler.handle(&header, &payload, &context).await
+        else {
+            panic!("Missing SendRRData reply");
+        };
+        let EncapsulationPayload::SendData(reply) = reply.payload else {
+            panic!("Unexpected reply payload");
+        };
+        let [CpfItem::NullAddress, CpfItem::UnconnectedData(data)] = reply.cpf.items.as_slice()
+        else {
+            panic!("Unexpected reply items: {:?}", reply.cpf.items);
+        };
+        MessageResponse::decode(&mut data.clone()).expect("Failed to decode response")
+    }
+
+    #[tokio::test]
+    async fn reply_larger_than_unconnected_reply_returns_reply_data_too_large() {
+        let table: Vec<u8> = (0..=255).cycle().take(600).collect();
+        let mut registry = Registry::new();
+        registry
+            .register(ParameterClass::with_parameters(vec![Box::new(
+                BlobParameter::new("Table", table, 1024),
+            )]))
+            .expect("Failed to register Parameter class");
+        let handler = SendRRDataHandler::new(Arc::new(registry), MessageRouter::default());
+
+        let single = get_table(&handler, 0x0E, &[]).await;
+        // Get_Attribute_Fragment: attribute 1 from offset 0
+        let fragment = get_table(&handler, 0x4C, &[0x01, 0x00, 0x00, 0x00, 0x00, 0x00]).await;
+
+        assert_eq!(
+            single,
+            MessageResponse::error(0x0E, CipError::ReplyDataTooLarge)
+        );
+        assert_eq!(fragment.general_status, CipError::PartialTransfer as u8);
+        assert_eq!(fragment.data.len(), MAX_UNCONNECTED_REPLY_SIZE);
+    }
+}
@@ -0,0 +1,113 @@
+use bytes::{Buf, BufMut, BytesMut};
+use std::sync::Arc;
+
+use crate::{
+    cip::{
+        ClassCode,
+        connection_manager::{ConnectionManagerInstance, MessageRequest},
+        message_router::MessageRouter,
+        registry::Registry,
+    },
+    common::binary::{FromBytes, ToBytes},
+    encap::{
+        Encapsulation, EncapsulationHeader,
+        command::send_rr_data::SendData,
+        cpf::{Cpf, cpf_item::CpfItem},
+        error::{EncapsulationError, HandlerError, InternalError},
+        handler::{ConnectionContext, HandlerAction},
+        header::EncapsulationStatus,
+        payload::EncapsulationPayload,
+    },
+};
+
+/// Executes connected explicit messages of class 3 connections through the message router.
+pub struct SendUnitDataHandler {
+    registry: Arc<Registry>,
+    message_router: MessageRouter,
+}
+
+impl SendUnitDataHandler {
+    pub fn new(registry: Arc<Registry>, message_router: MessageRouter) -> Self {
+        Self {
+            registry,
+            message_router,
+        }
+    }
+
+    /// The request must come from the registered session and carry a connected address
+    /// item followed by a connected data item. Each request resets the inactivity timer of
+    /// its connection, requests on connections that are not open are dropped without a
+    /// reply. The reply is sent on the T->O connection id with the sequence count of the
+    /// request.
+    pub async fn handle(
+        &self,
+        req_header: &EncapsulationHeader,
+        req_payload: &SendData,
+        context: &ConnectionContext,
+    ) -> Result<HandlerAction, HandlerError> {
+        if context.session_handle != Some(req_header.session_handle) {
+            return Err(EncapsulationError::InvalidSessionHandle(req_header.session_handle).into());
+        }
+
+        let [
+            CpfItem::ConnectedAddress(connection_id),
+            CpfItem::ConnectedData(data),
+        ] = req_payload.cpf.items.as_slice()
+        else {
+            log::warn!("Invalid SendUnitData items: {:?}", req_payload.cpf.items);
+            return Err(EncapsulationError::IncorrectData.into());
+        };
+        if data.len() < 2 {
+            return Err(EncapsulationError::InvalidLength {
+                expected: 2,
+                actual: data.len(),
+            }
+            .into());
+        }
+
+        let manager = self
+            .registry
+            .get_instance::<ConnectionManagerInstance>(ClassCode::ConnectionManager, 1)
+            .map_err(InternalError::Other)?;
+        let Some(connection) = manager.connection_received(*connection_id) else {
+            log::warn!(
+                "SendUnitData on connection {:#010X} that is not open",
+                connection_id
+            );
+            return Ok(HandlerAction::None);
+        };
+
+        let mut data = data.clone();
+        let sequence_count = data.get_u16_le();
+        let request = MessageRequest::decode(&mut data)
+            .map_err(|error| HandlerError::from(EncapsulationError::from(error)))?;
+        let response = self
+            .message_router
+            .dispatch(&self.registry, &request, Some(context.peer_addr))
+            .await;
+
+        let mut reply_data = BytesMut::with_capacity(2 + response.encoded_len());
+        reply_data.put_u16_le(sequence_count);
+        response
+            .encode(&mut reply_data)
+            .map_err(|error| InternalError::Other(format!("{:?}", error)))?;
+
+        let mut cpf = Cpf::new();
+        cpf.add_item(CpfItem::ConnectedAddress(connection.t_to_o_connection_id));
+        cpf.add_item(CpfItem::ConnectedData(reply_data.freeze()));
+        let reply_payload = EncapsulationPayload::SendData(SendData {
+            interface_handle: 0,
+            timeout: 0,
+            cpf,
+        });
+        let reply_header = EncapsulationHeader {
+            status: EncapsulationStatus::Success,
+            length: reply_payload.encoded_len() as u16,
+            ..*req_header
+        };
+
+        Encapsulation::new(reply_header, reply_payload)
+            .map(HandlerAction::Reply)
+            .map_err(|error| InternalError::Other(error.to_string()).into())
+    }
+}
@@ -25,6 +25,13 @@ impl Cpf {
 
 impl FromBytes for Cpf {
     fn decode<T: Buf>(buffer: &mut T) -> Result<Self, BinaryError> {
+        if buffer.remaining() < Self::HEADER_LEN {
+            return Err(BinaryError::Truncated {
+                expected: Self::HEADER_LEN,
+                actual: buffer.remaining(),
+            });
+        }
+
         let item_count = buffer.get_u16_le();
         let mut items = Vec::new();
 
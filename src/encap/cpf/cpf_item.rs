@@ -1,4 +1,4 @@
-use bytes::{Buf, BufMut};
+use bytes::{Buf, BufMut, Bytes};
 use std::fmt::{self, Display};
 
 use crate::common::binary::{BinaryError, FromBytes, ToBytes};
@@ -66,10 +66,14 @@ pub trait CpfItemDataToBytes: ToBytes {}
 #[derive(Debug, PartialEq)]
 pub enum CpfItem {
     NullAddress,
-    ConnectedAddress,
+    /// Connection id a connected message is sent on.
+    ConnectedAddress(u32),
     SequencedAddress,
-    UnconnectedData,
-    ConnectedData,
+    /// Message router request or reply of an unconnected explicit message.
+    UnconnectedData(Bytes),
+    /// Sequence count followed by the message router request or reply of a connected
+    /// explicit message.
+    ConnectedData(Bytes),
     IdentityItem(IdentityItem),
     SockAddrInfoOtoT,
     SockAddrInfoTtoO,
@@ -81,10 +85,10 @@ impl CpfItem {
     pub fn id(&self) -> CpfItemId {
         match self {
             CpfItem::NullAddress => CpfItemId::NullAddress,
-            CpfItem::ConnectedAddress => CpfItemId::ConnectedAddress,
+            CpfItem::ConnectedAddress(_) => CpfItemId::ConnectedAddress,
             CpfItem::SequencedAddress => CpfItemId::SequencedAddress,
-            CpfItem::UnconnectedData => CpfItemId::UnconnectedData,
-            CpfItem::ConnectedData => CpfItemId::ConnectedData,
+            CpfItem::UnconnectedData(_) => CpfItemId::UnconnectedData,
+            CpfItem::ConnectedData(_) => CpfItemId::ConnectedData,
             CpfItem::IdentityItem(_) => CpfItemId::IdentityItem,
             CpfItem::SockAddrInfoOtoT => CpfItemId::SockAddrInfoOtoT,
             CpfItem::SockAddrInfoTtoO => CpfItemId::SockAddrInfoTtoO,
@@ -110,20 +114,42 @@ impl FromBytes for CpfItem {
                 CpfItem::NullAddress
             }
             0x00A1 => {
-                buffer.advance(item_len as usize);
-                CpfItem::ConnectedAddress
+                if item_len != 4 {
+                    return Err(BinaryError::InvalidData {
+                        message: "Invalid connected address length".to_string(),
+                        expected: "4".to_string(),
+                        actual: item_len.to_string(),
+                    });
+                }
+                if buffer.remaining() < 4 {
+                    return Err(BinaryError::Truncated {
+                        expected: 4,
+                        actual: buffer.remaining(),
+                    });
+                }
+                CpfItem::ConnectedAddress(buffer.get_u32_le())
             }
             0x0080 => {
                 buffer.advance(item_len as usize);
                 CpfItem::SequencedAddress
             }
             0x00B2 => {
-                buffer.advance(item_len as usize);
-                CpfItem::UnconnectedData
+                if buffer.remaining() < item_len as usize {
+                    return Err(BinaryError::Truncated {
+                        expected: item_len as usize,
+                        actual: buffer.remaining(),
+                    });
+                }
+                CpfItem::UnconnectedData(buffer.copy_to_bytes(item_len as usize))
             }
             0x00B1 => {
-                buffer.advance(item_len as usize);
-                CpfItem::ConnectedData
+                if buffer.remaining() < item_len as usize {
+                    return Err(BinaryError::Truncated {
+                        expected: item_len as usize,
+                        actual: buffer.remaining(),
+                    });
+                }
+                CpfItem::ConnectedData(buffer.copy_to_bytes(item_len as usize))
             }
             0x000C => CpfItem::IdentityItem(IdentityItem::decode(buffer, item_len)?),
             0x8000 => {
@@ -155,6 +181,16 @@ impl ToBytes for CpfItem {
                 buffer.put_u16_le(item.encoded_len() as u16);
                 item.encode(buffer)
             }
+            CpfItem::ConnectedAddress(connection_id) => {
+                buffer.put_u16_le(4);
+                buffer.put_u32_le(*connection_id);
+                Ok(())
+            }
+            CpfItem::UnconnectedData(data) | CpfItem::ConnectedData(data) => {
+                buffer.put_u16_le(data.len() as u16);
+                buffer.put_slice(data);
+                Ok(())
+            }
             _ => {
                 buffer.put_u16_le(0);
                 Ok(())
@@ -165,6 +201,10 @@ impl ToBytes for CpfItem {
     fn encoded_len(&self) -> usize {
         match self {
             CpfItem::IdentityItem(item) => Self::HEADER_LEN + item.encoded_len(),
+            CpfItem::ConnectedAddress(_) => Self::HEADER_LEN + 4,
+            CpfItem::UnconnectedData(data) | CpfItem::ConnectedData(data) => {
+                Self::HEADER_LEN + data.len()
+            }
             _ => Self::HEADER_LEN,
         }
     }
@@ -4,14 +4,18 @@ use super::{
     Encapsulation, RawEncapsulation,
     command::{
         EncapsulationCommand, list_identity::ListIdentityHandler,
-        register_session::RegisterSessionHandler, unregister_session::UnregisterSessionHandler,
+        register_session::RegisterSessionHandler, send_rr_data::SendRRDataHandler,
+        send_unit_data::SendUnitDataHandler, unregister_session::UnregisterSessionHandler,
     },
     error::{EncapsulationError, HandlerError, InternalError},
     header::{EncapsulationHeader, EncapsulationStatus},
     payload::EncapsulationPayload,
     session_manager::SessionManager,
 };
-use crate::cip::registry::Registry;
+use crate::cip::{
+    ClassCode, connection_manager::ConnectionManagerInstance, message_router::MessageRouter,
+    registry::Registry,
+};
 use crate::common::binary::ToBytes;
 
 #[derive(Debug, PartialEq)]
@@ -81,25 +85,54 @@ pub enum HandlerAction {
 }
 
 pub struct EncapsulationHandler {
-    _registry: Arc<Registry>,
+    registry: Arc<Registry>,
     _session_manager: Arc<SessionManager>,
     list_identity_handler: ListIdentityHandler,
     register_session_handler: RegisterSessionHandler,
     unregister_session_handler: UnregisterSessionHandler,
+    send_rr_data_handler: SendRRDataHandler,
+    send_unit_data_handler: SendUnitDataHandler,
 }
 
 impl EncapsulationHandler {
     pub fn new(registry: Arc<Registry>, session_manager: Arc<SessionManager>) -> Self {
+        Self::with_message_router(registry, session_manager, MessageRouter::default())
+    }
+
+    /// Executes explicit messages through `message_router`.
+    pub fn with_message_router(
+        registry: Arc<Registry>,
+        session_manager: Arc<SessionManager>,
+        message_router: MessageRouter,
+    ) -> Self {
         Self {
-            _registry: registry.clone(),
+            registry: registry.clone(),
             _session_manager: session_manager.clone(),
-            list_identity_handler: ListIdentityHandler::new(registry),
+            list_identity_handler: ListIdentityHandler::new(registry.clone()),
             register_session_handler: RegisterSessionHandler::new(session_manager),
             unregister_session_handler: UnregisterSessionHandler,
+            send_rr_data_handler: SendRRDataHandler::new(registry.clone(), message_router),
+            send_unit_data_handler: SendUnitDataHandler::new(registry, message_router),
+        }
+    }
+
+    /// Closes the connections opened through the session of `context` once its TCP
+    /// connection ends, they cannot receive data anymore.
+    pub fn session_closed(&self, context: &ConnectionContext) {
+        match self
+            .registry
+            .get_instance::<ConnectionManagerInstance>(ClassCode::ConnectionManager, 1)
+        {
+            Ok(manager) => {
+                for connection in manager.close_connections_of(context.peer_addr) {
+                    log::info!("Connection {:?} closed with its session", connection.key);
+                }
+            }
+            Err(error) => log::debug!("No connections to close: {}", error),
         }
     }
 
-    pub fn handle(
+    pub async fn handle(
         &self,
         req: &mut RawEncapsulation,
         context: &mut ConnectionContext,
@@ -156,7 +189,7 @@ impl EncapsulationHandler {
             req_encapsulation.payload
         );
 
-        match self.dispatch(&req_encapsulation, context) {
+        match self.dispatch(&req_encapsulation, context).await {
             Ok(action) => Ok(action),
             Err(error) => match error {
                 HandlerError::Protocol(p_error) => {
@@ -200,7 +233,7 @@ impl EncapsulationHandler {
         }))
     }
 
-    fn dispatch(
+    async fn dispatch(
         &self,
         req: &Encapsulation,
         context: &mut ConnectionContext,
@@ -237,6 +270,26 @@ impl EncapsulationHandler {
                     actual: req.payload.encoded_len(),
                 }))
             }
+            EncapsulationCommand::SendRRData => {
+                if let EncapsulationPayload::SendData(data) = &req.payload {
+                    return self
+                        .send_rr_data_handler
+                        .handle(&req.header, data, context)
+                        .await;
+                }
+
+                Err(HandlerError::from(EncapsulationError::IncorrectData))
+            }
+            EncapsulationCommand::SendUnitData => {
+                if let EncapsulationPayload::SendData(data) = &req.payload {
+                    return self
+                        .send_unit_data_handler
+                        .handle(&req.header, data, context)
+                        .await;
+                }
+
+                Err(HandlerError::from(EncapsulationError::IncorrectData))
+            }
             _ => Err(HandlerError::from(
                 EncapsulationError::InvalidOrUnsupportedCommand(req.header.command),
             )),
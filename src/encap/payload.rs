@@ -2,7 +2,9 @@ use bytes::{Buf, BufMut, Bytes};
 
 use crate::common::binary::{BinaryError, FromBytes, ToBytes};
 use crate::encap::{
-    command::{EncapsulationCommand, register_session::RegisterSessionData},
+    command::{
+        EncapsulationCommand, register_session::RegisterSessionData, send_rr_data::SendData,
+    },
     cpf::Cpf,
 };
 
@@ -12,6 +14,7 @@ pub enum EncapsulationPayload {
     Nop(Bytes),
     RegisterSession(RegisterSessionData),
     Cpf(Cpf),
+    SendData(SendData),
 }
 
 pub trait EncapsulationPayloadFromBytes: Sized {
@@ -31,9 +34,11 @@ impl EncapsulationPayloadFromBytes for EncapsulationPayload {
                 RegisterSessionData::decode(buffer)?,
             )),
             EncapsulationCommand::UnregisterSession => Ok(EncapsulationPayload::None),
-            EncapsulationCommand::SendRRData => Ok(EncapsulationPayload::Cpf(Cpf::decode(buffer)?)),
+            EncapsulationCommand::SendRRData => {
+                Ok(EncapsulationPayload::SendData(SendData::decode(buffer)?))
+            }
             EncapsulationCommand::SendUnitData => {
-                Ok(EncapsulationPayload::Cpf(Cpf::decode(buffer)?))
+                Ok(EncapsulationPayload::SendData(SendData::decode(buffer)?))
             }
             EncapsulationCommand::IndicateStatus => Ok(EncapsulationPayload::None),
             EncapsulationCommand::Cancel => Ok(EncapsulationPayload::None),
@@ -49,6 +54,7 @@ impl ToBytes for EncapsulationPayload {
             EncapsulationPayload::Nop(data) => Ok(buffer.put(data.as_ref())),
             EncapsulationPayload::RegisterSession(data) => data.encode(buffer),
             EncapsulationPayload::Cpf(cpf) => cpf.encode(buffer),
+            EncapsulationPayload::SendData(data) => data.encode(buffer),
         }
     }
 
@@ -58,6 +64,7 @@ impl ToBytes for EncapsulationPayload {
             EncapsulationPayload::Nop(data) => data.len(),
             EncapsulationPayload::RegisterSession(data) => data.encoded_len(),
             EncapsulationPayload::Cpf(cpf) => cpf.encoded_len(),
+            EncapsulationPayload::SendData(data) => data.encoded_len(),
         }
     }
 }
@@ -12,14 +12,16 @@ use tokio::{
 use tokio_util::codec::Framed;
 
 use super::codec::EncapsulationCodec;
-use crate::encap::{
-    ConnectionContext, EncapsulationHandler, TransportType, handler::HandlerAction,
+use crate::{
+    cip::qos::{QosInstance, apply_dscp},
+    encap::{ConnectionContext, EncapsulationHandler, TransportType, handler::HandlerAction},
 };
 
 pub struct TcpTransport {
     tcp_listener: TcpListener,
     handler: Arc<EncapsulationHandler>,
     shutdown: Arc<Sender<()>>,
+    qos: Arc<QosInstance>,
 }
 
 impl TcpTransport {
@@ -27,6 +29,7 @@ impl TcpTransport {
         handler: Arc<EncapsulationHandler>,
         port: u16,
         shutdown: Arc<Sender<()>>,
+        qos: Arc<QosInstance>,
     ) -> io::Result<Self> {
         let tcp_listener = match TcpListener::bind((Ipv4Addr::UNSPECIFIED, port)).await {
             Ok(listener) => {
@@ -40,6 +43,7 @@ impl TcpTransport {
             tcp_listener,
             handler,
             shutdown,
+            qos,
         })
     }
 
@@ -72,6 +76,16 @@ impl TcpTransport {
     }
 
     async fn handle_connection(&mut self, stream: TcpStream, src: SocketAddr) {
+        let dscp = self.qos.config().dscp_explicit;
+        if let Err(err) = apply_dscp(&stream, dscp) {
+            log::warn!(
+                "Failed to apply DSCP {} to TCP connection {}: {}",
+                dscp,
+                src,
+                err
+            );
+        }
+
         let mut context = ConnectionContext::new(src, TransportType::TCP);
         let mut framed = Framed::new(stream, EncapsulationCodec::new());
         let mut connection_shutdown_rx = self.shutdown.subscribe();
@@ -95,6 +109,7 @@ impl TcpTransport {
                 }
             }
         }
+        self.handler.session_closed(&context);
     }
 
     async fn handle_framed(
@@ -105,13 +120,13 @@ impl TcpTransport {
         let frame_result_opt = framed.next().await;
 
         if frame_result_opt.is_none() {
-            log::error!("Failed to receive TCP frame");
-            return Some(());
+            log::info!("TCP connection ended by peer: {}", context.peer_addr);
+            return None;
         }
 
         let frame_result = frame_result_opt.unwrap();
         if let Ok(mut frame) = frame_result {
-            return match self.handler.handle(&mut frame, context) {
+            return match self.handler.handle(&mut frame, context).await {
                 Ok(HandlerAction::Reply(reply)) => {
                     log::debug!("Sending reply: ({:?})", reply);
 
@@ -5,8 +5,11 @@ use tokio::{net::UdpSocket, sync::broadcast::Sender};
 use tokio_util::udp::UdpFramed;
 
 use super::udp_codec::EncapsulationUdpCodec;
-use crate::encap::{
-    CastMode, ConnectionContext, EncapsulationHandler, TransportType, handler::HandlerAction,
+use crate::{
+    cip::qos::{QosInstance, apply_dscp},
+    encap::{
+        CastMode, ConnectionContext, EncapsulationHandler, TransportType, handler::HandlerAction,
+    },
 };
 
 pub struct UdpTransport {
@@ -14,6 +17,7 @@ pub struct UdpTransport {
     port: u16,
     handler: Arc<EncapsulationHandler>,
     shutdown_tx: Arc<Sender<()>>,
+    qos: Arc<QosInstance>,
 }
 
 impl UdpTransport {
@@ -21,12 +25,14 @@ impl UdpTransport {
         handler: Arc<EncapsulationHandler>,
         port: u16,
         shutdown_tx: Arc<Sender<()>>,
+        qos: Arc<QosInstance>,
     ) -> io::Result<Self> {
         Ok(Self {
             ip_address: Ipv4Addr::UNSPECIFIED,
             port,
             handler,
             shutdown_tx,
+            qos,
         })
     }
 
@@ -39,6 +45,11 @@ impl UdpTransport {
             Err(err) => return Err(err),
         };
 
+        let dscp = self.qos.config().dscp_explicit;
+        if let Err(err) = apply_dscp(&socket, dscp) {
+            log::warn!("Failed to apply DSCP {} to UDP socket: {}", dscp, err);
+        }
+
         log::info!(
             "Listening for UDP broadcast packets on {}",
             socket.local_addr()?
@@ -72,7 +83,7 @@ impl UdpTransport {
             let mut context =
                 ConnectionContext::new(peer_addr, TransportType::UDP(CastMode::Broadcast));
 
-            match self.handler.handle(&mut frame, &mut context) {
+            match self.handler.handle(&mut frame, &mut context).await {
                 Ok(HandlerAction::Reply(reply)) => {
                     if let Err(err) = framed.send((reply, peer_addr)).await {
                         log::error!("Failed to send reply to {} : {}", peer_addr, err);
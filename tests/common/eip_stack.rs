@@ -1,9 +1,9 @@
 use std::{io::Error, net::Ipv4Addr, sync::Arc};
-use tokio::{task::JoinHandle, time};
+use tokio::{sync::broadcast, task::JoinHandle, time};
 
 use crate::common::{tcp, udp};
 use rs_eip_adapter::{
-    cip::cip_identity::IdentityInfo,
+    cip::{cip_identity::IdentityInfo, events::AttributeChanged},
     eip_stack::{EipStack, EipStackBuilder},
 };
 
@@ -28,6 +28,10 @@ pub struct TestContext {
 }
 
 impl TestContext {
+    pub fn attribute_changes(&self) -> broadcast::Receiver<AttributeChanged> {
+        self.eip_stack.attribute_changes()
+    }
+
     pub async fn stop(self) {
         _ = self.eip_stack.stop();
         _ = self
@@ -1,4 +1,4 @@
-use std::io;
+use std::{io, net::SocketAddr};
 
 use bytes::Bytes;
 use tokio::{
@@ -64,6 +64,10 @@ impl TcpConnection {
         Self { client }
     }
 
+    pub fn local_addr(&self) -> SocketAddr {
+        self.client.local_addr().expect("Fail on get local address")
+    }
+
     pub async fn is_connected(&self) -> bool {
         let mut buf = [0u8; 1];
         match time::timeout(
@@ -0,0 +1,427 @@
+use std::time::Duration;
+
+use bytes::{BufMut, Bytes, BytesMut};
+
+use crate::common::{eip_stack, tcp};
+use rs_eip_adapter::{
+    cip::{
+        ClassCode, connection_manager::MessageRequest, data_types::epath::EPathBuilder,
+        message_router::MessageResponse,
+    },
+    common::binary::{FromBytes, ToBytes},
+    encap::{
+        command::{self, register_session::RegisterSessionData, send_rr_data::SendData},
+        cpf::{Cpf, cpf_item::CpfItem},
+        header::{EncapsulationHeader, EncapsulationStatus},
+    },
+};
+
+const DEFAULT_REQUEST_HEADER: EncapsulationHeader = EncapsulationHeader {
+    command: command::EncapsulationCommand::SendRRData,
+    length: 0x00,
+    session_handle: 0x00000000,
+    status: EncapsulationStatus::Success,
+    context: [0u8; 8],
+    options: 0x00000000,
+};
+
+/// Encapsulation header, interface handle, timeout, item count and both item headers.
+const REPLY_HEADERS_LEN: usize = EncapsulationHeader::LEN + 16;
+
+async fn register_session(connection: &mut tcp::TcpConnection) -> u32 {
+    let request_header = EncapsulationHeader {
+        command: command::EncapsulationCommand::RegisterSession,
+        length: 0x04,
+        ..DEFAULT_REQUEST_HEADER
+    };
+
+    let mut request_buf = BytesMut::with_capacity(EncapsulationHeader::LEN + 4);
+    request_header
+        .encode(&mut request_buf)
+        .expect("Error on encode request header");
+    RegisterSessionData {
+        protocol_version: 1,
+        options: 0,
+    }
+    .encode(&mut request_buf)
+    .expect("Error on encode register session data");
+
+    let mut reply_buf = connection
+        .send_and_receive(request_buf.freeze(), 28, 1000)
+        .await
+        .expect("Missing register session reply");
+    EncapsulationHeader::decode(&mut reply_buf)
+        .expect("Error on decode reply header")
+        .session_handle
+}
+
+fn send_rr_data_request(session_handle: u32, message: &MessageRequest) -> Bytes {
+    let mut message_buf = BytesMut::new();
+    message
+        .encode(&mut message_buf)
+        .expect("Error on encode message request");
+
+    let mut cpf = Cpf::new();
+    cpf.add_item(CpfItem::NullAddress);
+    cpf.add_item(CpfItem::UnconnectedData(message_buf.freeze()));
+    let payload = SendData {
+        interface_handle: 0,
+        timeout: 0,
+        cpf,
+    };
+
+    let request_header = EncapsulationHeader {
+        length: payload.encoded_len() as u16,
+        session_handle,
+        ..DEFAULT_REQUEST_HEADER
+    };
+    let mut request_buf = BytesMut::new();
+    request_header
+        .encode(&mut request_buf)
+        .expect("Error on encode request header");
+    payload
+        .encode(&mut request_buf)
+        .expect("Error on encode SendRRData payload");
+    request_buf.freeze()
+}
+
+fn message_response(mut reply_buf: Bytes) -> MessageResponse {
+    let reply_header =
+        EncapsulationHeader::decode(&mut reply_buf).expect("Error on decode reply header");
+    assert_eq!(reply_header.status, EncapsulationStatus::Success);
+
+    let payload = SendData::decode(&mut reply_buf).expect("Error on decode SendRRData payload");
+    let [CpfItem::NullAddress, CpfItem::UnconnectedData(data)] = payload.cpf.items.as_slice()
+    else {
+        panic!("Unexpected reply items: {:?}", payload.cpf.items);
+    };
+    MessageResponse::decode(&mut data.clone()).expect("Error on decode message response")
+}
+
+fn heartbeat_request(service: u8, data: &'static [u8]) -> MessageRequest {
+    MessageRequest {
+        service,
+        path: EPathBuilder::new()
+            .class(0x01)
+            .instance(1)
+            .attribute(10)
+            .build(),
+        data: Bytes::from_static(data),
+    }
+}
+
+#[tokio::test]
+async fn send_rr_data_sets_and_gets_identity_attribute() {
+    let context = eip_stack::run_stack(eip_stack::DEFAULT_IDENTITY_INFO)
+        .await
+        .expect("Error on run Eip stack");
+
+    let mut connection = tcp::TcpConnection::new(&format!("127.0.0.1:{}", context.tcp_port)).await;
+    let session_handle = register_session(&mut connection).await;
+
+    let set_reply = connection
+        .send_and_receive(
+            send_rr_data_request(session_handle, &heartbeat_request(0x10, &[0x0A])),
+            REPLY_HEADERS_LEN + 4,
+            1000,
+        )
+        .await
+        .expect("Missing Set_Attribute_Single reply");
+    let get_reply = connection
+        .send_and_receive(
+            send_rr_data_request(session_handle, &heartbeat_request(0x0E, &[])),
+            REPLY_HEADERS_LEN + 5,
+            1000,
+        )
+        .await
+        .expect("Missing Get_Attribute_Single reply");
+    let _ = context.stop().await;
+
+    assert_eq!(
+        message_response(set_reply),
+        MessageResponse::success(0x10, Bytes::new())
+    );
+    assert_eq!(
+        message_response(get_reply),
+        MessageResponse::success(0x0E, Bytes::from_static(&[0x0A]))
+    );
+}
+
+#[tokio::test]
+async fn send_rr_data_host_name_write_publishes_change_from_session_peer() {
+    let context = eip_stack::run_stack(eip_stack::DEFAULT_IDENTITY_INFO)
+        .await
+        .expect("Error on run Eip stack");
+    let mut changes = context.attribute_changes();
+
+    let mut connection = tcp::TcpConnection::new(&format!("127.0.0.1:{}", context.tcp_port)).await;
+    let session_handle = register_session(&mut connection).await;
+    let request = MessageRequest {
+        service: 0x10,
+        path: EPathBuilder::new()
+            .class(0xF5)
+            .instance(1)
+            .attribute(6)
+            .build(),
+        data: Bytes::from_static(&[0x03, 0x00, b'p', b'l', b'c']),
+    };
+
+    let reply = connection
+        .send_and_receive(
+            send_rr_data_request(session_handle, &request),
+            REPLY_HEADERS_LEN + 4,
+            1000,
+        )
+        .await
+        .expect("Missing Set_Attribute_Single reply");
+    let change = changes.try_recv();
+    let _ = context.stop().await;
+
+    assert!(message_response(reply).is_success());
+    let change = change.expect("Missing attribute change");
+    assert_eq!(change.class, ClassCode::TcpIpInterface);
+    assert_eq!((change.instance, change.attribute), (1, 6));
+    assert_eq!(change.new_value_bytes, request.data);
+    assert_eq!(change.source_peer, Some(connection.local_addr()));
+}
+
+/// Forward_Open of a class 3 connection with a 1 ms O->T RPI, timing out without data
+/// after 4 ms for `timeout_multiplier` 0, doubling per step.
+fn forward_open_request(timeout_multiplier: u8) -> MessageRequest {
+    let path = EPathBuilder::new().class(0x02).instance(1).build();
+    let mut data = BytesMut::new();
+    data.put_u8(0x0A);
+    data.put_u8(0x0E);
+    data.put_u32_le(0);
+    data.put_u32_le(0x8000_0001);
+    data.put_u16_le(0x0001);
+    data.put_u16_le(0x0001);
+    data.put_u32_le(0x12345678);
+    data.put_u8(timeout_multiplier);
+    data.put_slice(&[0x00; 3]);
+    data.put_u32_le(1_000);
+    data.put_u16_le(0x43F4);
+    data.put_u32_le(1_000);
+    data.put_u16_le(0x43F4);
+    data.put_u8(0xA3);
+    data.put_u8((path.encoded_len() / 2) as u8);
+    path.encode(&mut data)
+        .expect("Error on encode connection path");
+
+    MessageRequest {
+        service: 0x54,
+        path: EPathBuilder::new().class(0x06).instance(1).build(),
+        data: data.freeze(),
+    }
+}
+
+#[tokio::test]
+async fn send_rr_data_forward_open_times_out_without_data() {
+    let context = eip_stack::run_stack(eip_stack::DEFAULT_IDENTITY_INFO)
+        .await
+        .expect("Error on run Eip stack");
+
+    let mut connection = tcp::TcpConnection::new(&format!("127.0.0.1:{}", context.tcp_port)).await;
+    let session_handle = register_session(&mut connection).await;
+    let open_reply = connection
+        .send_and_receive(
+            send_rr_data_request(session_handle, &forward_open_request(0)),
+            REPLY_HEADERS_LEN + 30,
+            1000,
+        )
+        .await
+        .expect("Missing Forward_Open reply");
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    let timeouts_reply = connection
+        .send_and_receive(
+            send_rr_data_request(session_handle, &connection_timeouts_request()),
+            REPLY_HEADERS_LEN + 6,
+            1000,
+        )
+        .await
+        .expect("Missing Get_Attribute_Single reply");
+    let _ = context.stop().await;
+
+    let open_response = message_response(open_reply);
+    assert!(open_response.is_success());
+    assert_eq!(open_response.service, 0xD4);
+    assert_eq!(
+        message_response(timeouts_reply),
+        MessageResponse::success(0x0E, Bytes::from_static(&[0x01, 0x00]))
+    );
+}
+
+fn connection_timeouts_request() -> MessageRequest {
+    MessageRequest {
+        service: 0x0E,
+        path: EPathBuilder::new()
+            .class(0x06)
+            .instance(1)
+            .attribute(8)
+            .build(),
+        data: Bytes::new(),
+    }
+}
+
+fn send_unit_data_request(
+    session_handle: u32,
+    connection_id: u32,
+    sequence_count: u16,
+    message: &MessageRequest,
+) -> Bytes {
+    let mut data = BytesMut::new();
+    data.put_u16_le(sequence_count);
+    message
+        .encode(&mut data)
+        .expect("Error on encode message request");
+
+    let mut cpf = Cpf::new();
+    cpf.add_item(CpfItem::ConnectedAddress(connection_id));
+    cpf.add_item(CpfItem::ConnectedData(data.freeze()));
+    let payload = SendData {
+        interface_handle: 0,
+        timeout: 0,
+        cpf,
+    };
+
+    let request_header = EncapsulationHeader {
+        command: command::EncapsulationCommand::SendUnitData,
+        length: payload.encoded_len() as u16,
+        session_handle,
+        ..DEFAULT_REQUEST_HEADER
+    };
+    let mut request_buf = BytesMut::new();
+    request_header
+        .encode(&mut request_buf)
+        .expect("Error on encode request header");
+    payload
+        .encode(&mut request_buf)
+        .expect("Error on encode SendUnitData payload");
+    request_buf.freeze()
+}
+
+#[tokio::test]
+async fn send_unit_data_executes_request_on_open_connection() {
+    let context = eip_stack::run_stack(eip_stack::DEFAULT_IDENTITY_INFO)
+        .await
+        .expect("Error on run Eip stack");
+
+    let mut connection = tcp::TcpConnection::new(&format!("127.0.0.1:{}", context.tcp_port)).await;
+    let session_handle = register_session(&mut connection).await;
+    let open_reply = connection
+        .send_and_receive(
+            send_rr_data_request(session_handle, &forward_open_request(7)),
+            REPLY_HEADERS_LEN + 30,
+            1000,
+        )
+        .await
+        .expect("Missing Forward_Open reply");
+    let open_response = message_response(open_reply);
+    let o_to_t_connection_id = u32::from_le_bytes(
+        open_response.data[..4]
+            .try_into()
+            .expect("Missing O->T connection id"),
+    );
+
+    // Connected address item with its id, connected data item with the sequence count
+    let unit_reply = connection
+        .send_and_receive(
+            send_unit_data_request(
+                session_handle,
+                o_to_t_connection_id,
+                7,
+                &connection_timeouts_request(),
+            ),
+            REPLY_HEADERS_LEN + 4 + 2 + 6,
+            1000,
+        )
+        .await;
+    let _ = context.stop().await;
+
+    let mut reply_buf = unit_reply.expect("Missing SendUnitData reply");
+    let reply_header =
+        EncapsulationHeader::decode(&mut reply_buf).expect("Error on decode reply header");
+    assert_eq!(
+        reply_header.command,
+        command::EncapsulationCommand::SendUnitData
+    );
+    let payload = SendData::decode(&mut reply_buf).expect("Error on decode SendUnitData payload");
+    let [
+        CpfItem::ConnectedAddress(connection_id),
+        CpfItem::ConnectedData(data),
+    ] = payload.cpf.items.as_slice()
+    else {
+        panic!("Unexpected reply items: {:?}", payload.cpf.items);
+    };
+    assert_eq!(*connection_id, 0x8000_0001);
+    assert_eq!(data[..2], [0x07, 0x00]);
+    assert_eq!(
+        MessageResponse::decode(&mut data.slice(2..)).expect("Error on decode message response"),
+        MessageResponse::success(0x0E, Bytes::from_static(&[0x00, 0x00]))
+    );
+}
+
+#[tokio::test]
+async fn connections_close_with_their_session() {
+    let context = eip_stack::run_stack(eip_stack::DEFAULT_IDENTITY_INFO)
+        .await
+        .expect("Error on run Eip stack");
+    let address = format!("127.0.0.1:{}", context.tcp_port);
+
+    let mut originator = tcp::TcpConnection::new(&address).await;
+    let session_handle = register_session(&mut originator).await;
+    let open_reply = originator
+        .send_and_receive(
+            send_rr_data_request(session_handle, &forward_open_request(5)),
+            REPLY_HEADERS_LEN + 30,
+            1000,
+        )
+        .await
+        .expect("Missing Forward_Open reply");
+    drop(originator);
+    // Past the 128 ms connection timeout
+    tokio::time::sleep(Duration::from_millis(200)).await;
+
+    let mut connection = tcp::TcpConnection::new(&address).await;
+    let session_handle = register_session(&mut connection).await;
+    let timeouts_reply = connection
+        .send_and_receive(
+            send_rr_data_request(session_handle, &connection_timeouts_request()),
+            REPLY_HEADERS_LEN + 6,
+            1000,
+        )
+        .await
+        .expect("Missing Get_Attribute_Single reply");
+    let _ = context.stop().await;
+
+    assert!(message_response(open_reply).is_success());
+    assert_eq!(
+        message_response(timeouts_reply),
+        MessageResponse::success(0x0E, Bytes::from_static(&[0x00, 0x00]))
+    );
+}
+
+#[tokio::test]
+async fn send_rr_data_without_session_returns_invalid_session_handle() {
+    let context = eip_stack::run_stack(eip_stack::DEFAULT_IDENTITY_INFO)
+        .await
+        .expect("Error on run Eip stack");
+
+    let reply = tcp::send_and_receive(
+        &format!("127.0.0.1:{}", context.tcp_port),
+        send_rr_data_request(0x1234, &heartbeat_request(0x0E, &[])),
+        EncapsulationHeader::LEN,
+        1000,
+    )
+    .await;
+    let _ = context.stop().await;
+
+    let mut reply_buf = reply.expect("Missing SendRRData reply");
+    let reply_header =
+        EncapsulationHeader::decode(&mut reply_buf).expect("Error on decode reply header");
+    assert_eq!(
+        reply_header.status,
+        EncapsulationStatus::InvalidSessionHandle
+    );
+    assert_eq!(reply_header.length, 0);
+}
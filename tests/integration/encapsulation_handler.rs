@@ -35,22 +35,26 @@ fn build_handler() -> EncapsulationHandler {
         serial_number: 1234,
         product_name: "Integration Test Device",
     };
-    registry_instance.register(IdentityClass::with_default_instance(&identity_data));
+    registry_instance
+        .register(IdentityClass::with_default_instance(&identity_data))
+        .expect("register identity class");
 
     let tcp_interface_class = Arc::new(TcpIpInterfaceClass::new());
     let tcp_interface_instance = Arc::new(TcpIpInterfaceInstance::new(1, Ipv4Addr::LOCALHOST));
     tcp_interface_class
         .add_instance(tcp_interface_instance)
         .expect("register tcp instance");
-    registry_instance.register(tcp_interface_class);
+    registry_instance
+        .register(tcp_interface_class)
+        .expect("register tcp interface class");
 
     let session_manager = Arc::new(SessionManager::new());
 
     EncapsulationHandler::new(Arc::new(registry_instance), session_manager)
 }
 
-#[test]
-fn handler_reply_status_success_for_list_identity() {
+#[tokio::test]
+async fn handler_reply_status_success_for_list_identity() {
     env_logger::init();
     let handler = build_handler();
 
@@ -71,6 +75,7 @@ fn handler_reply_status_success_for_list_identity() {
 
     let action = handler
         .handle(&mut encapsulation, &mut context)
+        .await
         .expect("Should handle request");
 
     let HandlerAction::Reply(reply) = action else {
@@ -82,8 +87,8 @@ fn handler_reply_status_success_for_list_identity() {
     assert!(reply.header.length > 0);
 }
 
-#[test]
-fn handler_should_reply_status_error_for_unsupported_command() {
+#[tokio::test]
+async fn handler_should_reply_status_error_for_unsupported_command() {
     let handler = build_handler();
 
     let request_header = EncapsulationHeader {
@@ -103,6 +108,7 @@ fn handler_should_reply_status_error_for_unsupported_command() {
 
     let action = handler
         .handle(&mut encapsulation, &mut context)
+        .await
         .expect("Should handle request");
 
     let HandlerAction::Reply(reply) = action else {
@@ -115,8 +121,8 @@ fn handler_should_reply_status_error_for_unsupported_command() {
     );
 }
 
-#[test]
-fn handler_should_reply_status_error_for_partially_supported_commands() {
+#[tokio::test]
+async fn handler_should_reply_status_error_for_partially_supported_commands() {
     let handler = build_handler();
 
     for command in &[
@@ -140,6 +146,7 @@ fn handler_should_reply_status_error_for_partially_supported_commands() {
 
         let action = handler
             .handle(&mut encapsulation, &mut context)
+            .await
             .expect("Should handle request");
 
         let HandlerAction::Reply(reply) = action else {
@@ -153,8 +160,8 @@ fn handler_should_reply_status_error_for_partially_supported_commands() {
     }
 }
 
-#[test]
-fn handler_should_not_reply_on_list_identity_error() {
+#[tokio::test]
+async fn handler_should_not_reply_on_list_identity_error() {
     let empty_registry = Arc::new(Registry::new());
     let handler = EncapsulationHandler::new(empty_registry, Arc::new(SessionManager::new()));
 
@@ -173,13 +180,13 @@ fn handler_should_not_reply_on_list_identity_error() {
         TransportType::UDP(CastMode::Broadcast),
     );
 
-    let result = handler.handle(&mut encapsulation, &mut context);
+    let result = handler.handle(&mut encapsulation, &mut context).await;
 
     assert!(matches!(result, Err(_)));
 }
 
-#[test]
-fn handler_should_reply_error_status_for_list_identity_payload_is_not_empty() {
+#[tokio::test]
+async fn handler_should_reply_error_status_for_list_identity_payload_is_not_empty() {
     let handler = build_handler();
 
     let request_header = EncapsulationHeader {
@@ -207,6 +214,7 @@ fn handler_should_reply_error_status_for_list_identity_payload_is_not_empty() {
 
     let action = handler
         .handle(&mut encapsulation, &mut context)
+        .await
         .expect("Should handle request");
 
     let HandlerAction::Reply(reply) = action else {
@@ -1 +1,3 @@
 pub mod encapsulation_handler;
+#[cfg(feature = "serde")]
+pub mod serde_config;
@@ -0,0 +1,132 @@
+use std::net::Ipv4Addr;
+
+use rs_eip_adapter::{
+    cip::{
+        cip_identity::{IdentityInfo, Revision},
+        data_types::{
+            CipDataType, CipString, CipValue, Date, ShortString, StringI, TypeDescriptor, UInt,
+            epath::EPathBuilder,
+        },
+        tcp_ip_interface::InterfaceConfiguration,
+    },
+    eip_stack::EipConfig,
+};
+use serde_json::json;
+
+#[test]
+fn eip_config_json_round_trip_success() {
+    let text = json!({
+        "identity": {
+            "vendor_id": 1,
+            "device_type": 12,
+            "product_code": 65,
+            "revision": "1.2",
+            "serial_number": 1234,
+            "product_name": "Config Device"
+        },
+        "local_address": "192.168.1.10",
+        "tcp_port": 44818,
+        "udp_broadcast_port": 44818
+    });
+
+    let config: EipConfig = serde_json::from_value(text.clone()).expect("Failed to deserialize");
+
+    assert_eq!(config.identity.revision_major, 1);
+    assert_eq!(config.identity.revision_minor, 2);
+    assert_eq!(config.identity.product_name, "Config Device");
+    assert_eq!(config.local_address, Ipv4Addr::new(192, 168, 1, 10));
+    assert_eq!(
+        serde_json::to_value(&config).expect("Failed to serialize"),
+        text
+    );
+}
+
+#[test]
+fn identity_product_name_is_shared_between_loads() {
+    let text = json!({
+        "vendor_id": 1,
+        "device_type": 12,
+        "product_code": 65,
+        "revision": "1.2",
+        "serial_number": 1234,
+        "product_name": "Reloaded Device"
+    });
+
+    let first: IdentityInfo = serde_json::from_value(text.clone()).expect("Failed to deserialize");
+    let second: IdentityInfo = serde_json::from_value(text).expect("Failed to deserialize");
+
+    assert_eq!(first.product_name, "Reloaded Device");
+    assert!(std::ptr::eq(first.product_name, second.product_name));
+}
+
+#[test]
+fn interface_configuration_uses_dotted_addresses() {
+    let config = InterfaceConfiguration::new(Ipv4Addr::new(10, 0, 0, 5));
+
+    let text = serde_json::to_value(config).expect("Failed to serialize");
+
+    assert_eq!(
+        text,
+        json!({
+            "ip_address": "10.0.0.5",
+            "network_mask": "0.0.0.0",
+            "gateway_address": "0.0.0.0",
+            "name_server": "0.0.0.0",
+            "name_server_2": "0.0.0.0",
+            "domain_name": ""
+        })
+    );
+    assert_eq!(
+        serde_json::from_value::<InterfaceConfiguration>(text).expect("Failed to deserialize"),
+        config
+    );
+}
+
+#[test]
+fn data_types_use_plain_text() {
+    assert_eq!(
+        serde_json::to_value(ShortString::new("Valve")).expect("Failed to serialize"),
+        json!("Valve")
+    );
+    assert_eq!(
+        serde_json::to_value(Revision {
+            major: 3,
+            minor: 14
+        })
+        .expect("Failed to serialize"),
+        json!("3.14")
+    );
+    assert_eq!(
+        serde_json::to_value(Date::from_ymd(2024, 2, 29)).expect("Failed to serialize"),
+        json!("2024-02-29")
+    );
+    assert_eq!(
+        serde_json::to_value(EPathBuilder::new().class(4).instance(100).build_sized())
+            .expect("Failed to serialize"),
+        json!("@4/100")
+    );
+    assert_eq!(
+        serde_json::to_value(UInt::new(7)).expect("Failed to serialize"),
+        json!(7)
+    );
+    assert_eq!(
+        serde_json::to_value(StringI::new("Valve")).expect("Failed to serialize"),
+        json!({ "eng": "Valve" })
+    );
+    assert_eq!(
+        serde_json::to_value(TypeDescriptor::array(CipDataType::UInt.into(), 2))
+            .expect("Failed to serialize"),
+        json!({ "array": { "lower_bound": 0, "upper_bound": 1, "element": { "elementary": "UINT" } } })
+    );
+    assert_eq!(
+        serde_json::from_value::<CipValue>(json!({ "UInt": 42 })).expect("Failed to deserialize"),
+        CipValue::UInt(UInt::new(42))
+    );
+}
+
+#[test]
+fn string_too_long_returns_error() {
+    let result = serde_json::from_value::<CipString<4>>(json!("Too long"));
+
+    assert!(result.is_err());
+}